@@ -0,0 +1,78 @@
+use std::time::{Duration, Instant};
+
+/// Who issued a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommandSource {
+    Operator,
+    Autopilot,
+}
+
+/// Arbitration between an operator and an autopilot driving the same gimbal: after any operator
+/// motion command, autopilot motion commands are rejected for `operator_hold`.
+#[derive(Debug, Clone)]
+pub struct Arbitration {
+    operator_hold: Duration,
+    last_operator_input: Option<Instant>,
+}
+
+impl Arbitration {
+    pub fn new(operator_hold: Duration) -> Self {
+        Self {
+            operator_hold,
+            last_operator_input: None,
+        }
+    }
+
+    pub fn operator_hold(&self) -> Duration {
+        self.operator_hold
+    }
+
+    /// Whether the operator currently overrides the autopilot.
+    pub fn operator_override(&self, now: Instant) -> bool {
+        self.last_operator_input
+            .is_some_and(|last| now.saturating_duration_since(last) < self.operator_hold)
+    }
+
+    /// Decides whether a motion command from `source` may be sent. Operator input always wins
+    /// and restarts the hold.
+    pub fn admit(&mut self, source: CommandSource, now: Instant) -> bool {
+        match source {
+            CommandSource::Operator => {
+                self.last_operator_input = Some(now);
+                true
+            }
+            CommandSource::Autopilot => !self.operator_override(now),
+        }
+    }
+
+    /// Ends the operator hold early, handing control back to the autopilot.
+    pub fn release(&mut self) {
+        self.last_operator_input = None;
+    }
+}
+
+impl Default for Arbitration {
+    fn default() -> Self {
+        Self::new(crate::constants::OPERATOR_HOLD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operator_overrides_autopilot() {
+        let now = Instant::now();
+        let mut arbitration = Arbitration::new(Duration::from_secs(5));
+        assert!(arbitration.admit(CommandSource::Autopilot, now));
+
+        assert!(arbitration.admit(CommandSource::Operator, now));
+        assert!(!arbitration.admit(CommandSource::Autopilot, now + Duration::from_secs(4)));
+        assert!(arbitration.admit(CommandSource::Autopilot, now + Duration::from_secs(5)));
+
+        arbitration.admit(CommandSource::Operator, now);
+        arbitration.release();
+        assert!(arbitration.admit(CommandSource::Autopilot, now));
+    }
+}
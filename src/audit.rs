@@ -0,0 +1,127 @@
+use crate::arbitration::CommandSource;
+use crate::correlation::CorrelationId;
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+/// What happened to a command after it was encoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditOutcome {
+    Sent,
+    /// Encoded and validated but not transmitted because the client is in dry-run mode.
+    DryRun,
+    /// Held in the offline queue after a failed send.
+    Queued,
+    /// Dropped by setpoint pacing because a newer angle setpoint arrived before it was sent.
+    Superseded,
+    /// Dropped by delta suppression because it matched the last acknowledged setpoint.
+    Suppressed,
+    /// Not transmitted because a middleware layer skipped it.
+    Skipped,
+    Failed(String),
+}
+
+/// One encoded command and its outcome.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub time: SystemTime,
+    pub bytes: Vec<u8>,
+    /// Operation the command was sent as part of.
+    pub correlation: Option<CorrelationId>,
+    /// Source tag of the command, `None` for untagged sends.
+    pub source: Option<CommandSource>,
+    pub outcome: AuditOutcome,
+}
+
+/// Bounded record of the most recent commands issued by a client. The oldest entry is dropped when full.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    capacity: usize,
+    entries: VecDeque<AuditEntry>,
+}
+
+impl AuditLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: VecDeque::new(),
+        }
+    }
+
+    pub fn record(
+        &mut self,
+        bytes: Vec<u8>,
+        correlation: Option<CorrelationId>,
+        source: Option<CommandSource>,
+        outcome: AuditOutcome,
+    ) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(AuditEntry {
+            time: SystemTime::now(),
+            bytes,
+            correlation,
+            source,
+            outcome,
+        });
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &AuditEntry> {
+        self.entries.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new(crate::constants::AUDIT_LOG_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_log_capacity() {
+        let mut log = AuditLog::new(2);
+        log.record(vec![1], None, None, AuditOutcome::Sent);
+        let id = CorrelationId::generate();
+        log.record(
+            vec![2],
+            Some(id),
+            Some(CommandSource::Operator),
+            AuditOutcome::DryRun,
+        );
+        log.record(
+            vec![3],
+            None,
+            None,
+            AuditOutcome::Failed("No bytes sent.".to_string()),
+        );
+
+        let outcomes: Vec<_> = log.entries().map(|entry| entry.outcome.clone()).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                AuditOutcome::DryRun,
+                AuditOutcome::Failed("No bytes sent.".to_string())
+            ]
+        );
+        let oldest = log.entries().next().unwrap();
+        assert_eq!(oldest.bytes, vec![2]);
+        assert_eq!(oldest.correlation, Some(id));
+        assert_eq!(oldest.source, Some(CommandSource::Operator));
+    }
+}
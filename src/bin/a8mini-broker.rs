@@ -0,0 +1,36 @@
+//! Owns the camera's UDP connection and shares it with local processes over a unix socket.
+//!
+//! Usage: `a8mini-broker [SOCKET_PATH] [CAMERA_IP]`
+
+#[cfg(unix)]
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use a8mini_camera_rs::broker::Broker;
+    use a8mini_camera_rs::constants;
+    use tokio::net::UdpSocket;
+
+    let mut args = std::env::args().skip(1);
+    let socket_path = args
+        .next()
+        .unwrap_or_else(|| constants::BROKER_SOCKET_PATH.to_string());
+    let camera_ip = args
+        .next()
+        .unwrap_or_else(|| constants::CAMERA_IP.to_string());
+
+    let camera = UdpSocket::bind("0.0.0.0:0").await?;
+    camera
+        .connect(format!("{}:{}", camera_ip, constants::CAMERA_COMMAND_PORT))
+        .await?;
+    println!(
+        "[BROKER] Forwarding to {}:{}.",
+        camera_ip,
+        constants::CAMERA_COMMAND_PORT
+    );
+    Broker::new(camera).serve(socket_path).await?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn main() {
+    eprintln!("a8mini-broker requires unix domain sockets.");
+}
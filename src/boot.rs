@@ -0,0 +1,107 @@
+use crate::control::{A8MiniSimpleCommand, MotionMode};
+use std::time::{Duration, Instant};
+
+/// Settings re-applied every time the gimbal boots, since it does not persist its mode
+/// across power cycles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BootDefaults {
+    pub mode: MotionMode,
+    /// Center the gimbal after setting the mode.
+    pub center: bool,
+    /// How long the gimbal must stay silent before its next response counts as a boot.
+    pub silence: Duration,
+}
+
+impl BootDefaults {
+    pub fn new(mode: MotionMode) -> Self {
+        Self {
+            mode,
+            center: true,
+            silence: crate::constants::BOOT_SILENCE,
+        }
+    }
+
+    pub fn with_center(mut self, center: bool) -> Self {
+        self.center = center;
+        self
+    }
+
+    pub fn with_silence(mut self, silence: Duration) -> Self {
+        self.silence = silence;
+        self
+    }
+
+    /// Commands to send once a boot is detected, in order.
+    pub fn commands(&self) -> Vec<A8MiniSimpleCommand> {
+        let mut commands = vec![self.mode.command()];
+        if self.center {
+            commands.push(A8MiniSimpleCommand::AutoCenter);
+        }
+        commands
+    }
+}
+
+/// Detects gimbal boots from a stream of poll results: a response after at least `silence`
+/// without one (or the very first response) is a boot.
+#[derive(Debug, Clone)]
+pub struct BootDetector {
+    silence: Duration,
+    last_response: Option<Instant>,
+}
+
+impl BootDetector {
+    pub fn new(silence: Duration) -> Self {
+        Self {
+            silence,
+            last_response: None,
+        }
+    }
+
+    /// Records a poll result. Returns `true` if the gimbal has just booted.
+    pub fn observe(&mut self, responded: bool, now: Instant) -> bool {
+        if !responded {
+            return false;
+        }
+
+        let booted = self
+            .last_response
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.silence);
+        self.last_response = Some(now);
+        booted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boot_detection() {
+        let now = Instant::now();
+        let mut detector = BootDetector::new(Duration::from_secs(5));
+        assert!(!detector.observe(false, now));
+        assert!(detector.observe(true, now));
+        assert!(!detector.observe(true, now + Duration::from_secs(1)));
+
+        assert!(!detector.observe(false, now + Duration::from_secs(2)));
+        assert!(!detector.observe(false, now + Duration::from_secs(4)));
+        assert!(detector.observe(true, now + Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn test_boot_defaults_commands() {
+        assert_eq!(
+            BootDefaults::new(MotionMode::Follow).commands(),
+            vec![
+                A8MiniSimpleCommand::SetFollowMode,
+                A8MiniSimpleCommand::AutoCenter
+            ]
+        );
+        assert_eq!(
+            BootDefaults::new(MotionMode::Lock)
+                .with_center(false)
+                .commands(),
+            vec![A8MiniSimpleCommand::SetLockMode]
+        );
+    }
+}
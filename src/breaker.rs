@@ -0,0 +1,104 @@
+use std::collections::VecDeque;
+
+/// Link state as judged by the circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkState {
+    Up,
+    Down,
+}
+
+/// Trips when too many of the most recent acknowledged commands time out. While tripped the
+/// client fails fast; the breaker closes again on the first successful probe.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    window: usize,
+    trip_ratio: f32,
+    outcomes: VecDeque<bool>,
+    state: LinkState,
+}
+
+impl CircuitBreaker {
+    /// Trips once at least `trip_ratio` of the last `window` commands have failed.
+    pub fn new(window: usize, trip_ratio: f32) -> Self {
+        Self {
+            window: window.max(1),
+            trip_ratio: trip_ratio.clamp(0.0, 1.0),
+            outcomes: VecDeque::with_capacity(window.max(1)),
+            state: LinkState::Up,
+        }
+    }
+
+    pub fn state(&self) -> LinkState {
+        self.state
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state == LinkState::Down
+    }
+
+    /// Records the outcome of a command. Returns the new link state if it changed.
+    pub fn record(&mut self, success: bool) -> Option<LinkState> {
+        if self.is_open() {
+            if !success {
+                return None;
+            }
+            self.outcomes.clear();
+            self.state = LinkState::Up;
+            return Some(LinkState::Up);
+        }
+
+        if self.outcomes.len() == self.window {
+            self.outcomes.pop_front();
+        }
+        self.outcomes.push_back(success);
+
+        if self.outcomes.len() < self.window {
+            return None;
+        }
+        let failures = self.outcomes.iter().filter(|&&success| !success).count();
+        if failures as f32 >= self.trip_ratio * self.window as f32 {
+            self.state = LinkState::Down;
+            return Some(LinkState::Down);
+        }
+        None
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(
+            crate::constants::BREAKER_WINDOW,
+            crate::constants::BREAKER_TRIP_RATIO,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breaker_trips_on_failure_ratio() {
+        let mut breaker = CircuitBreaker::new(4, 0.5);
+        assert_eq!(breaker.record(false), None);
+        assert_eq!(breaker.record(true), None);
+        assert_eq!(breaker.record(true), None);
+        assert_eq!(breaker.record(true), None);
+        assert_eq!(breaker.record(false), None);
+        assert_eq!(breaker.record(false), Some(LinkState::Down));
+        assert!(breaker.is_open());
+    }
+
+    #[test]
+    fn test_breaker_closes_on_success() {
+        let mut breaker = CircuitBreaker::new(2, 1.0);
+        breaker.record(false);
+        assert_eq!(breaker.record(false), Some(LinkState::Down));
+        assert_eq!(breaker.record(false), None);
+        assert_eq!(breaker.record(true), Some(LinkState::Up));
+
+        // The window starts over after closing.
+        assert_eq!(breaker.record(false), None);
+        assert_eq!(breaker.state(), LinkState::Up);
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{checksum, clock, constants, transport};
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicU16, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{UdpSocket, UnixListener, UnixStream};
+use tokio::sync::mpsc;
+
+/// Shares one camera connection between local processes. The broker owns the UDP socket;
+/// clients attach over a unix socket with `A8Mini::connect_broker` and use the normal API.
+///
+/// Each forwarded frame gets a broker-wide SEQ so the camera's ACK can be routed back to the
+/// client that sent it, with the client's own SEQ restored. Only frames with the CTRL need_ack
+/// bit set are awaited, and each only for the ack timeout. Frames that answer no pending
+/// request, such as function feedback, attitude pushes and late ACKs, go to every client.
+#[derive(Debug)]
+pub struct Broker {
+    camera: UdpSocket,
+    clients: Mutex<HashMap<u64, mpsc::UnboundedSender<Vec<u8>>>>,
+    pending: Mutex<HashMap<u16, Pending>>,
+    ack_timeout: Duration,
+    next_client: AtomicU64,
+    next_seq: AtomicU16,
+}
+
+/// Client and client SEQ of a forwarded frame awaiting its ACK.
+#[derive(Debug, Clone, Copy)]
+struct Pending {
+    client: u64,
+    seq: u16,
+    sent: Instant,
+}
+
+impl Broker {
+    /// `camera` must already be connected to the camera's SDK port. ACKs are awaited for
+    /// `RECV_TIMEOUT`.
+    pub fn new(camera: UdpSocket) -> Arc<Self> {
+        Self::with_ack_timeout(camera, constants::RECV_TIMEOUT)
+    }
+
+    /// Like `new`, routing ACKs back only while they arrive within `ack_timeout` of the request.
+    pub fn with_ack_timeout(camera: UdpSocket, ack_timeout: Duration) -> Arc<Self> {
+        Arc::new(Self {
+            camera,
+            clients: Mutex::new(HashMap::new()),
+            pending: Mutex::new(HashMap::new()),
+            ack_timeout,
+            next_client: AtomicU64::new(0),
+            next_seq: AtomicU16::new(0),
+        })
+    }
+
+    /// Binds `path`, replacing a stale socket file, and serves clients until an I/O error.
+    pub async fn serve(self: Arc<Self>, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        let listener = UnixListener::bind(path)?;
+        log_line!("[BROKER] Listening on {}.", path.display());
+        self.serve_listener(listener).await
+    }
+
+    pub async fn serve_listener(self: Arc<Self>, listener: UnixListener) -> io::Result<()> {
+        let camera_reader = tokio::spawn(self.clone().forward_from_camera());
+        let result = loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(self.clone().serve_client(stream));
+                }
+                Err(e) => break Err(e),
+            }
+        };
+        camera_reader.abort();
+        result
+    }
+
+    async fn serve_client(self: Arc<Self>, stream: UnixStream) {
+        let client = self.next_client.fetch_add(1, Ordering::Relaxed);
+        let (sender, mut receiver) = mpsc::unbounded_channel::<Vec<u8>>();
+        self.clients.lock().unwrap().insert(client, sender);
+        log_line!("[BROKER] Client {} attached.", client);
+
+        let (mut reader, mut writer) = stream.into_split();
+        let writer_task = tokio::spawn(async move {
+            while let Some(frame) = receiver.recv().await {
+                if writer.write_all(&frame).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut pending_bytes = Vec::new();
+        let mut chunk = [0; constants::RECV_BUFF_SIZE];
+        loop {
+            while let Some(mut frame) = transport::take_frame(&mut pending_bytes) {
+                let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+                {
+                    let now = clock::now();
+                    let mut pending = self.pending.lock().unwrap();
+                    pending.retain(|_, entry| !self.expired(entry, now));
+                    if needs_ack(&frame) {
+                        let entry = Pending {
+                            client,
+                            seq: checksum::seq(&frame),
+                            sent: now,
+                        };
+                        pending.insert(seq, entry);
+                    }
+                }
+                checksum::set_seq(&mut frame, seq);
+                if let Err(e) = self.camera.send(&frame).await {
+                    log_line!("[BROKER] Send to camera failed: {}", e);
+                }
+            }
+            match reader.read(&mut chunk).await {
+                Ok(0) | Err(_) => break,
+                Ok(read) => pending_bytes.extend_from_slice(&chunk[..read]),
+            }
+        }
+
+        self.clients.lock().unwrap().remove(&client);
+        self.pending
+            .lock()
+            .unwrap()
+            .retain(|_, entry| entry.client != client);
+        writer_task.abort();
+        log_line!("[BROKER] Client {} detached.", client);
+    }
+
+    async fn forward_from_camera(self: Arc<Self>) {
+        let mut recv_buffer = [0; constants::RECV_BUFF_SIZE];
+        loop {
+            let recv_len = match self.camera.recv(&mut recv_buffer).await {
+                Ok(recv_len) => recv_len,
+                Err(e) => {
+                    log_line!("[BROKER] Receive from camera failed: {}", e);
+                    continue;
+                }
+            };
+            let mut frame = recv_buffer[..recv_len].to_vec();
+            if checksum::validate_frame(&frame).is_err() {
+                continue;
+            }
+
+            let owner = self
+                .pending
+                .lock()
+                .unwrap()
+                .remove(&checksum::seq(&frame))
+                .filter(|entry| !self.expired(entry, clock::now()));
+            let clients = self.clients.lock().unwrap();
+            match owner.and_then(|entry| Some((clients.get(&entry.client)?, entry.seq))) {
+                Some((sender, seq)) => {
+                    checksum::set_seq(&mut frame, seq);
+                    let _ = sender.send(frame);
+                }
+                None => {
+                    for sender in clients.values() {
+                        let _ = sender.send(frame.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    fn expired(&self, entry: &Pending, now: Instant) -> bool {
+        now.saturating_duration_since(entry.sent) > self.ack_timeout
+    }
+}
+
+/// Whether the frame has the CTRL need_ack bit set.
+fn needs_ack(frame: &[u8]) -> bool {
+    frame.get(2).is_some_and(|ctrl| ctrl & 0x01 != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::control::{A8MiniSimpleCommand, Command};
+    use crate::transport::Transport;
+
+    #[tokio::test]
+    async fn test_broker_routes_acks() {
+        let camera = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let broker_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        broker_socket
+            .connect(camera.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (client_a, broker_a) = UnixStream::pair().unwrap();
+        let (client_b, broker_b) = UnixStream::pair().unwrap();
+
+        let broker = Broker::new(broker_socket);
+        tokio::spawn(broker.clone().forward_from_camera());
+        tokio::spawn(broker.clone().serve_client(broker_a));
+        tokio::spawn(broker.clone().serve_client(broker_b));
+        let client_a = transport::StreamTransport::new(client_a);
+        let client_b = transport::StreamTransport::new(client_b);
+
+        // Both clients use SEQ 7.
+        let mut frame = A8MiniSimpleCommand::AutoFocus.to_bytes();
+        checksum::set_seq(&mut frame, 7);
+        client_a.send(&frame).await.unwrap();
+        let mut request = [0; 64];
+        let (len, broker_addr) = camera.recv_from(&mut request).await.unwrap();
+        let seq_a = checksum::seq(&request[..len]);
+
+        client_b.send(&frame).await.unwrap();
+        let (len, _) = camera.recv_from(&mut request).await.unwrap();
+        let seq_b = checksum::seq(&request[..len]);
+        assert_ne!(seq_a, seq_b);
+
+        // Answer B first, then push unsolicited function feedback.
+        for (seq, sta) in [(seq_b, 0x0b), (seq_a, 0x0a)] {
+            let mut ack = vec![0x55, 0x66, 0x02, 0x01, 0x00, 0x00, 0x00, 0x04, sta];
+            ack.extend_from_slice(&checksum::crc16_calc(&ack, 0));
+            checksum::set_seq(&mut ack, seq);
+            camera.send_to(&ack, broker_addr).await.unwrap();
+        }
+        let mut feedback = vec![0x55, 0x66, 0x02, 0x01, 0x00, 0x00, 0x00, 0x0b, 0x00];
+        feedback.extend_from_slice(&checksum::crc16_calc(&feedback, 0));
+        camera.send_to(&feedback, broker_addr).await.unwrap();
+
+        let mut buf = [0; 64];
+        for (client, sta) in [(&client_a, 0x0a), (&client_b, 0x0b)] {
+            let len = client.recv(&mut buf).await.unwrap();
+            assert_eq!(checksum::seq(&buf[..len]), 7);
+            assert_eq!(buf[8], sta);
+            assert!(checksum::validate_frame(&buf[..len]).is_ok());
+
+            let len = client.recv(&mut buf).await.unwrap();
+            assert_eq!(buf[7], constants::FUNCTION_FEEDBACK_CMD_ID);
+            assert!(checksum::validate_frame(&buf[..len]).is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_broker_expires_pending() {
+        let camera = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let broker_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        broker_socket
+            .connect(camera.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (client, broker_end) = UnixStream::pair().unwrap();
+
+        let broker = Broker::with_ack_timeout(broker_socket, Duration::from_millis(50));
+        tokio::spawn(broker.clone().forward_from_camera());
+        tokio::spawn(broker.clone().serve_client(broker_end));
+        let client = transport::StreamTransport::new(client);
+
+        // Without need_ack nothing is awaited.
+        let mut frame = A8MiniSimpleCommand::AutoFocus.to_bytes();
+        frame[2] = 0x00;
+        checksum::set_seq(&mut frame, 7);
+        client.send(&frame).await.unwrap();
+        let mut request = [0; 64];
+        camera.recv_from(&mut request).await.unwrap();
+        assert!(broker.pending.lock().unwrap().is_empty());
+
+        let mut frame = A8MiniSimpleCommand::AutoFocus.to_bytes();
+        checksum::set_seq(&mut frame, 7);
+        client.send(&frame).await.unwrap();
+        let (len, broker_addr) = camera.recv_from(&mut request).await.unwrap();
+        let seq = checksum::seq(&request[..len]);
+        assert_eq!(broker.pending.lock().unwrap().len(), 1);
+
+        // An ACK after the timeout is no longer routed back with the client's SEQ.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let mut ack = vec![0x55, 0x66, 0x02, 0x01, 0x00, 0x00, 0x00, 0x04, 0x01];
+        ack.extend_from_slice(&checksum::crc16_calc(&ack, 0));
+        checksum::set_seq(&mut ack, seq);
+        camera.send_to(&ack, broker_addr).await.unwrap();
+
+        let mut buf = [0; 64];
+        let len = client.recv(&mut buf).await.unwrap();
+        assert_eq!(checksum::seq(&buf[..len]), seq);
+        assert!(broker.pending.lock().unwrap().is_empty());
+    }
+}
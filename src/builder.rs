@@ -0,0 +1,120 @@
+use crate::config::ClientConfig;
+use crate::{constants, jsonlog, A8Mini, A8MiniError};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+/// Configures and connects an [`A8Mini`] over UDP, see [`A8Mini::builder`]. Everything not set
+/// keeps the defaults of [`A8Mini::connect`]: the camera at `CAMERA_IP` on its standard ports,
+/// local ports 8080 and 8088, and the default [`ClientConfig`].
+#[derive(Debug)]
+pub struct A8MiniBuilder {
+    camera_addr: String,
+    camera_command_port: u16,
+    camera_http_port: u16,
+    local_command_port: u16,
+    local_http_port: u16,
+    client_config: ClientConfig,
+    setpoint_spacing: Option<Duration>,
+    log_target: Option<jsonlog::JsonLog>,
+}
+
+impl Default for A8MiniBuilder {
+    fn default() -> Self {
+        Self {
+            camera_addr: constants::CAMERA_IP.to_string(),
+            camera_command_port: constants::CAMERA_COMMAND_PORT.parse().unwrap(),
+            camera_http_port: constants::CAMERA_HTTP_PORT.parse().unwrap(),
+            local_command_port: 8080,
+            local_http_port: 8088,
+            client_config: ClientConfig::default(),
+            setpoint_spacing: None,
+            log_target: None,
+        }
+    }
+}
+
+impl A8MiniBuilder {
+    /// Host name or IP address of the camera.
+    pub fn camera_addr(mut self, camera_addr: impl Into<String>) -> Self {
+        self.camera_addr = camera_addr.into();
+        self
+    }
+
+    pub fn camera_command_port(mut self, port: u16) -> Self {
+        self.camera_command_port = port;
+        self
+    }
+
+    pub fn camera_http_port(mut self, port: u16) -> Self {
+        self.camera_http_port = port;
+        self
+    }
+
+    /// Local port of the command socket. 0 picks a free port.
+    pub fn local_command_port(mut self, port: u16) -> Self {
+        self.local_command_port = port;
+        self
+    }
+
+    /// Local port of the HTTP socket. 0 picks a free port.
+    pub fn local_http_port(mut self, port: u16) -> Self {
+        self.local_http_port = port;
+        self
+    }
+
+    /// Replaces the whole client configuration, including anything set through `ack_timeout`,
+    /// `retries` and `retry_delay` so far.
+    pub fn client_config(mut self, client_config: ClientConfig) -> Self {
+        self.client_config = client_config;
+        self
+    }
+
+    pub fn ack_timeout(mut self, ack_timeout: Duration) -> Self {
+        self.client_config.ack_timeout = ack_timeout;
+        self
+    }
+
+    /// How many times a command is resent after its response timed out.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.client_config.retries = retries;
+        self
+    }
+
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.client_config.retry_delay = retry_delay;
+        self
+    }
+
+    /// Coalesces angle setpoints issued faster than `min_interval`, see
+    /// `A8Mini::set_setpoint_spacing`. Off unless set.
+    pub fn setpoint_spacing(mut self, min_interval: Duration) -> Self {
+        self.setpoint_spacing = Some(min_interval);
+        self
+    }
+
+    /// Writes commands, responses and events as JSON lines to `log_target`, see
+    /// `A8Mini::set_json_log`.
+    pub fn log_target(mut self, log_target: jsonlog::JsonLog) -> Self {
+        self.log_target = Some(log_target);
+        self
+    }
+
+    pub async fn connect(self) -> Result<A8Mini, A8MiniError> {
+        let command_socket = UdpSocket::bind(("0.0.0.0", self.local_command_port)).await?;
+        command_socket
+            .connect((self.camera_addr.as_str(), self.camera_command_port))
+            .await?;
+        let http_socket = UdpSocket::bind(("0.0.0.0", self.local_http_port)).await?;
+        http_socket
+            .connect((self.camera_addr.as_str(), self.camera_http_port))
+            .await?;
+
+        let mut cam = A8Mini::from_parts(Box::new(command_socket), http_socket);
+        cam.set_client_config(self.client_config);
+        cam.set_setpoint_spacing(self.setpoint_spacing);
+        if let Some(log_target) = self.log_target {
+            cam.set_json_log(Some(log_target));
+        }
+        Ok(cam)
+    }
+}
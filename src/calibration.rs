@@ -0,0 +1,144 @@
+use crate::control::A8MiniAtittude;
+use serde::{Deserialize, Serialize};
+
+/// How the gimbal is mounted relative to the airframe. Attitude in the airframe's frame is
+/// `gimbal * sign + offset` per axis, where the sign is -1 for an inverted axis. A gimbal mounted
+/// facing backwards is `yaw: 1800`; one mounted upside down inverts both axes.
+///
+/// Offsets are in decidegrees. The identity calibration leaves frames untouched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CalibrationOffsets {
+    pub yaw: i16,
+    pub pitch: i16,
+    #[serde(default)]
+    pub invert_yaw: bool,
+    #[serde(default)]
+    pub invert_pitch: bool,
+}
+
+impl CalibrationOffsets {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Converts an airframe yaw/pitch setpoint to the gimbal's, in decidegrees.
+    pub fn setpoint_to_gimbal(&self, theta_yaw: i16, theta_pitch: i16) -> (i16, i16) {
+        (
+            wrap_decidegrees(sign(self.invert_yaw) * (theta_yaw as i32 - self.yaw as i32)),
+            saturate(sign(self.invert_pitch) * (theta_pitch as i32 - self.pitch as i32)),
+        )
+    }
+
+    /// Converts a gimbal yaw/pitch to the airframe setpoint that reaches it, in decidegrees.
+    pub fn setpoint_from_gimbal(&self, theta_yaw: i16, theta_pitch: i16) -> (i16, i16) {
+        (
+            wrap_decidegrees(sign(self.invert_yaw) * theta_yaw as i32 + self.yaw as i32),
+            saturate(sign(self.invert_pitch) * theta_pitch as i32 + self.pitch as i32),
+        )
+    }
+
+    /// Converts a yaw/pitch speed command from the airframe's axes to the gimbal's.
+    pub fn rate_to_gimbal(&self, v_yaw: i8, v_pitch: i8) -> (i8, i8) {
+        let flip = |v: i8, invert: bool| if invert { v.saturating_neg() } else { v };
+        (
+            flip(v_yaw, self.invert_yaw),
+            flip(v_pitch, self.invert_pitch),
+        )
+    }
+
+    /// Converts an attitude reported by the gimbal to the airframe's frame. Roll is unchanged.
+    pub fn attitude_from_gimbal(&self, attitude: &A8MiniAtittude) -> A8MiniAtittude {
+        let (yaw_sign, pitch_sign) = (sign(self.invert_yaw), sign(self.invert_pitch));
+        let (theta_yaw, theta_pitch) =
+            self.setpoint_from_gimbal(attitude.theta_yaw, attitude.theta_pitch);
+        A8MiniAtittude {
+            theta_yaw,
+            theta_pitch,
+            v_yaw: saturate(yaw_sign * attitude.v_yaw as i32),
+            v_pitch: saturate(pitch_sign * attitude.v_pitch as i32),
+            ..*attitude
+        }
+    }
+}
+
+fn sign(invert: bool) -> i32 {
+    if invert {
+        -1
+    } else {
+        1
+    }
+}
+
+/// Wraps decidegrees into the gimbal's yaw range [-1800, 1800).
+fn wrap_decidegrees(theta: i32) -> i16 {
+    ((theta + 1800).rem_euclid(3600) - 1800) as i16
+}
+
+fn saturate(theta: i32) -> i16 {
+    theta.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attitude(theta_yaw: i16, theta_pitch: i16, v_yaw: i16) -> A8MiniAtittude {
+        A8MiniAtittude {
+            theta_yaw,
+            theta_pitch,
+            theta_roll: 7,
+            v_yaw,
+            v_pitch: 0,
+            v_roll: 0,
+        }
+    }
+
+    #[test]
+    fn test_identity() {
+        let calibration = CalibrationOffsets::default();
+        assert!(calibration.is_identity());
+        assert_eq!(calibration.setpoint_to_gimbal(-900, 250), (-900, 250));
+        assert_eq!(calibration.rate_to_gimbal(30, -20), (30, -20));
+        assert_eq!(
+            calibration.attitude_from_gimbal(&attitude(100, -50, 20)),
+            attitude(100, -50, 20)
+        );
+    }
+
+    #[test]
+    fn test_mounted_backwards() {
+        let calibration = CalibrationOffsets {
+            yaw: 1800,
+            ..Default::default()
+        };
+        assert_eq!(calibration.setpoint_to_gimbal(1700, -300), (-100, -300));
+        assert_eq!(
+            calibration.attitude_from_gimbal(&attitude(-100, -300, 20)),
+            attitude(1700, -300, 20)
+        );
+        // Wraps into [-180, 180).
+        assert_eq!(
+            calibration
+                .attitude_from_gimbal(&attitude(0, 0, 0))
+                .theta_yaw,
+            -1800
+        );
+    }
+
+    #[test]
+    fn test_inverted_axes_round_trip() {
+        let calibration = CalibrationOffsets {
+            yaw: 50,
+            pitch: -20,
+            invert_yaw: true,
+            invert_pitch: true,
+        };
+        assert_eq!(calibration.rate_to_gimbal(30, -20), (-30, 20));
+        let reported = calibration.attitude_from_gimbal(&attitude(300, -400, 20));
+        assert_eq!(reported, attitude(-250, 380, -20));
+        assert_eq!(
+            calibration.setpoint_to_gimbal(reported.theta_yaw, reported.theta_pitch),
+            (300, -400)
+        );
+    }
+}
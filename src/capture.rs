@@ -0,0 +1,284 @@
+use crate::{
+    compensation::VehicleAttitude,
+    control::{A8MiniAtittude, A8MiniSimpleCommand},
+};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Kind of media a capture command produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureKind {
+    Photo,
+    Video,
+}
+
+impl CaptureKind {
+    pub fn command(&self) -> A8MiniSimpleCommand {
+        match *self {
+            CaptureKind::Photo => A8MiniSimpleCommand::TakePicture,
+            CaptureKind::Video => A8MiniSimpleCommand::RecordVideo,
+        }
+    }
+}
+
+/// Photo and video functions of the photo function command (CMD 0x0C).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureCommand {
+    TakePhoto,
+    /// Starts recording unless the camera already is.
+    StartRecording,
+    /// Stops recording unless the camera already has.
+    StopRecording,
+    ToggleRecording,
+}
+
+impl CaptureCommand {
+    /// Frame sent for this function. The camera only has a recording toggle, so starting and
+    /// stopping send the same frame.
+    pub fn command(&self) -> A8MiniSimpleCommand {
+        match *self {
+            CaptureCommand::TakePhoto => A8MiniSimpleCommand::TakePicture,
+            CaptureCommand::StartRecording
+            | CaptureCommand::StopRecording
+            | CaptureCommand::ToggleRecording => A8MiniSimpleCommand::RecordVideo,
+        }
+    }
+
+    /// Recording state this command leaves the camera in, given whether it is recording now.
+    /// `None` for photos.
+    pub fn recording_after(&self, recording: bool) -> Option<bool> {
+        match *self {
+            CaptureCommand::TakePhoto => None,
+            CaptureCommand::StartRecording => Some(true),
+            CaptureCommand::StopRecording => Some(false),
+            CaptureCommand::ToggleRecording => Some(!recording),
+        }
+    }
+}
+
+/// Function feedback (CMD 0x0B) the camera sends after capture and mode changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionFeedback {
+    PhotoSaved,
+    PhotoFailed,
+    HdrOn,
+    HdrOff,
+    RecordingFailed,
+    Unknown(u8),
+}
+
+impl FunctionFeedback {
+    pub fn from_u8(info_type: u8) -> Self {
+        match info_type {
+            0 => FunctionFeedback::PhotoSaved,
+            1 => FunctionFeedback::PhotoFailed,
+            2 => FunctionFeedback::HdrOn,
+            3 => FunctionFeedback::HdrOff,
+            4 => FunctionFeedback::RecordingFailed,
+            _ => FunctionFeedback::Unknown(info_type),
+        }
+    }
+}
+
+/// What happened to one photo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShotOutcome {
+    /// The camera confirmed the photo was saved, this long after the trigger was sent.
+    Saved(Duration),
+    /// The camera reported the photo failed.
+    Failed,
+    /// No feedback arrived before the shot timeout.
+    NoFeedback,
+    /// The trigger was not transmitted (dry run or queued).
+    NotSent,
+}
+
+/// Result of one photo, taken alone or as part of a burst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShotResult {
+    pub sent: Option<HostTimestamp>,
+    pub outcome: ShotOutcome,
+}
+
+/// A host instant paired with the wall-clock time it corresponds to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostTimestamp {
+    pub monotonic: Instant,
+    /// Microseconds since the Unix epoch.
+    pub unix_us: u64,
+}
+
+impl HostTimestamp {
+    pub fn now() -> Self {
+        Self {
+            monotonic: crate::clock::now(),
+            unix_us: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since_epoch| since_epoch.as_micros() as u64),
+        }
+    }
+
+    /// Wall-clock time of a later instant, derived from the monotonic clock so that wall-clock
+    /// adjustments in between do not skew it.
+    pub fn unix_us_at(&self, instant: Instant) -> u64 {
+        self.unix_us
+            + instant
+                .saturating_duration_since(self.monotonic)
+                .as_micros() as u64
+    }
+}
+
+/// Host timing of a capture trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerTiming {
+    /// When the capture frame was handed to the socket.
+    pub sent: HostTimestamp,
+    /// When the camera's function feedback for the capture arrived, if it did.
+    pub acked: Option<Instant>,
+}
+
+impl TriggerTiming {
+    pub fn ack_latency(&self) -> Option<Duration> {
+        self.acked
+            .map(|acked| acked.saturating_duration_since(self.sent.monotonic))
+    }
+}
+
+/// Provenance of a capture, written as a JSON sidecar next to the downloaded media file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptureMetadata {
+    pub kind: CaptureKind,
+    /// Host wall-clock time the capture frame was handed to the socket, in milliseconds since
+    /// the Unix epoch.
+    pub trigger_time_unix_ms: u64,
+    /// Same instant in microseconds, for correlating captures with GNSS time.
+    #[serde(default)]
+    pub trigger_time_unix_us: u64,
+    /// Host wall-clock time the camera confirmed the capture, in microseconds since the Unix epoch.
+    #[serde(default)]
+    pub ack_time_unix_us: Option<u64>,
+    /// Monotonic trigger and confirmation instants. Not written to the sidecar.
+    #[serde(skip)]
+    pub timing: Option<TriggerTiming>,
+    /// SEQ field of the capture command frame.
+    pub seq: u16,
+    pub attitude: Option<A8MiniAtittude>,
+    pub zoom: Option<f32>,
+    pub vehicle: Option<VehicleAttitude>,
+}
+
+impl CaptureMetadata {
+    /// Sidecar location for a media file: same path with a `.json` extension.
+    pub fn sidecar_path(media_path: &Path) -> PathBuf {
+        media_path.with_extension("json")
+    }
+
+    pub async fn write_sidecar(&self, media_path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+        let sidecar_path = Self::sidecar_path(media_path);
+        tokio::fs::write(&sidecar_path, serde_json::to_vec_pretty(self)?).await?;
+        Ok(sidecar_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> CaptureMetadata {
+        CaptureMetadata {
+            kind: CaptureKind::Photo,
+            trigger_time_unix_ms: 1_700_000_000_000,
+            trigger_time_unix_us: 1_700_000_000_000_250,
+            ack_time_unix_us: Some(1_700_000_000_180_000),
+            timing: None,
+            seq: 7,
+            attitude: Some(A8MiniAtittude {
+                theta_yaw: 40,
+                theta_pitch: -50,
+                theta_roll: 0,
+                v_yaw: 0,
+                v_pitch: 0,
+                v_roll: 0,
+            }),
+            zoom: None,
+            vehicle: Some(VehicleAttitude {
+                roll: 1.0,
+                pitch: 2.0,
+                yaw: 3.0,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_capture_command() {
+        assert_eq!(
+            CaptureCommand::TakePhoto.command(),
+            A8MiniSimpleCommand::TakePicture
+        );
+        assert_eq!(
+            CaptureCommand::StopRecording.command(),
+            A8MiniSimpleCommand::RecordVideo
+        );
+        assert_eq!(CaptureCommand::TakePhoto.recording_after(true), None);
+        assert_eq!(
+            CaptureCommand::StartRecording.recording_after(true),
+            Some(true)
+        );
+        assert_eq!(
+            CaptureCommand::StopRecording.recording_after(true),
+            Some(false)
+        );
+        assert_eq!(
+            CaptureCommand::ToggleRecording.recording_after(false),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_function_feedback() {
+        assert_eq!(FunctionFeedback::from_u8(0), FunctionFeedback::PhotoSaved);
+        assert_eq!(FunctionFeedback::from_u8(3), FunctionFeedback::HdrOff);
+        assert_eq!(FunctionFeedback::from_u8(9), FunctionFeedback::Unknown(9));
+    }
+
+    #[test]
+    fn test_trigger_timing() {
+        let sent = HostTimestamp::now();
+        let acked = sent.monotonic + Duration::from_millis(180);
+        let timing = TriggerTiming {
+            sent,
+            acked: Some(acked),
+        };
+
+        assert_eq!(timing.ack_latency(), Some(Duration::from_millis(180)));
+        assert_eq!(sent.unix_us_at(acked), sent.unix_us + 180_000);
+        assert_eq!(
+            sent.unix_us_at(sent.monotonic - Duration::from_millis(1)),
+            sent.unix_us
+        );
+    }
+
+    #[test]
+    fn test_sidecar_path() {
+        assert_eq!(
+            CaptureMetadata::sidecar_path(Path::new("media/IMG_0001.jpg")),
+            PathBuf::from("media/IMG_0001.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_sidecar() -> Result<(), Box<dyn Error>> {
+        let media_path = std::env::temp_dir().join("a8mini_test_write_sidecar.jpg");
+        let sidecar_path = metadata().write_sidecar(&media_path).await?;
+
+        let written: CaptureMetadata =
+            serde_json::from_slice(&tokio::fs::read(&sidecar_path).await?)?;
+        tokio::fs::remove_file(&sidecar_path).await?;
+
+        assert_eq!(written, metadata());
+        Ok(())
+    }
+}
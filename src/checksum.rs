@@ -1,4 +1,4 @@
-use crate::constants;
+use crate::{constants, A8MiniError};
 
 /// Computes CRC16 checksum given byte array. Output is little endian.
 /// - G(X) = X^16+X^12+X^5+1
@@ -14,10 +14,103 @@ pub fn crc16_calc(arr: &[u8], crc_init: u16) -> [u8; 2] {
     crc16.to_le_bytes()
 }
 
+/// Writes `seq` into the SEQ field of an encoded frame and recomputes its CRC16.
+pub fn set_seq(frame: &mut [u8], seq: u16) {
+    let crc_at = frame.len() - 2;
+    frame[5..7].copy_from_slice(&seq.to_le_bytes());
+    let crc = crc16_calc(&frame[..crc_at], 0);
+    frame[crc_at..].copy_from_slice(&crc);
+}
+
+/// SEQ field of a frame.
+pub fn seq(frame: &[u8]) -> u16 {
+    u16::from_le_bytes([frame[5], frame[6]])
+}
+
+/// Checks the STX bytes, declared data length and trailing CRC16 of a received SDK frame.
+pub fn validate_frame(frame: &[u8]) -> Result<(), A8MiniError> {
+    if frame.len() < constants::HEADER_LEN + 2 {
+        return Err(A8MiniError::MalformedAck(format!(
+            "{} bytes is shorter than a frame.",
+            frame.len()
+        )));
+    }
+    if frame[..2] != [0x55, 0x66] {
+        return Err(A8MiniError::MalformedAck(format!(
+            "Bad STX {:#04x} {:#04x}.",
+            frame[0], frame[1]
+        )));
+    }
+
+    let data_len = u16::from_le_bytes([frame[3], frame[4]]) as usize;
+    let crc_at = constants::HEADER_LEN + data_len;
+    let Some(received) = frame.get(crc_at..crc_at + 2) else {
+        return Err(A8MiniError::MalformedAck(format!(
+            "Declared data length {} exceeds the {} bytes received.",
+            data_len,
+            frame.len()
+        )));
+    };
+
+    let expected = u16::from_le_bytes(crc16_calc(&frame[..crc_at], 0));
+    let received = u16::from_le_bytes([received[0], received[1]]);
+    if expected != received {
+        return Err(A8MiniError::ChecksumMismatch { expected, received });
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_set_seq() {
+        let mut frame = constants::HARDCODED_COMMANDS[0].to_vec();
+        set_seq(&mut frame, 0x1234);
+        assert_eq!(seq(&frame), 0x1234);
+        assert_eq!(frame[5..7], [0x34, 0x12]);
+        assert!(validate_frame(&frame).is_ok());
+    }
+
+    #[test]
+    fn test_validate_frame() {
+        let frame = [
+            0x55, 0x66, 0x01, 0x01, 0x00, 0x00, 0x00, 0x0c, 0x00, 0x34, 0xce,
+        ];
+        assert!(validate_frame(&frame).is_ok());
+        // Trailing bytes after the CRC are ignored.
+        assert!(validate_frame(&[&frame[..], &[0, 0, 0]].concat()).is_ok());
+
+        let mut corrupted = frame;
+        corrupted[8] = 0x01;
+        assert!(matches!(
+            validate_frame(&corrupted),
+            Err(A8MiniError::ChecksumMismatch {
+                expected: 0xde15,
+                received: 0xce34
+            })
+        ));
+
+        let mut bad_stx = frame;
+        bad_stx[1] = 0x67;
+        assert!(matches!(
+            validate_frame(&bad_stx),
+            Err(A8MiniError::MalformedAck(_))
+        ));
+
+        let mut bad_len = frame;
+        bad_len[3] = 0x10;
+        assert!(matches!(
+            validate_frame(&bad_len),
+            Err(A8MiniError::MalformedAck(_))
+        ));
+        assert!(matches!(
+            validate_frame(&frame[..6]),
+            Err(A8MiniError::MalformedAck(_))
+        ));
+    }
+
     #[test]
     fn test_crc16_hardcoded_checksums() {
         // GROUND TRUTH: https://crccalc.com/?crc=&method=CRC-16/XMODEM&datatype=hex&outtype=hex
@@ -0,0 +1,25 @@
+use std::time::Instant;
+
+/// Current time on tokio's clock. Every internal timer reads time through this, so pausing
+/// and advancing time with `tokio::time::pause`/`advance` (or `#[tokio::test(start_paused = true)]`)
+/// drives pacing, arbitration holds, queue expiry, deadlines and pollers deterministically.
+/// Outside a paused runtime this is the system monotonic clock.
+pub fn now() -> Instant {
+    tokio::time::Instant::now().into_std()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_paused_clock() {
+        let start = now();
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert_eq!(now() - start, Duration::from_secs(30));
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert_eq!(now() - start, Duration::from_millis(30_250));
+    }
+}
@@ -0,0 +1,228 @@
+use crate::decideg::DeciDeg;
+use serde::{Deserialize, Serialize};
+
+/// Vehicle attitude as reported by the autopilot, in degrees. Yaw is the compass heading.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct VehicleAttitude {
+    pub roll: f32,
+    pub pitch: f32,
+    pub yaw: f32,
+}
+
+/// Earth-fixed pointing target, in degrees. Elevation is positive above the horizon.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EarthTarget {
+    pub bearing: f32,
+    pub elevation: f32,
+}
+
+/// Geodetic position: latitude and longitude in degrees, altitude in metres (any consistent datum).
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct GeoPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude: f64,
+}
+
+/// Vehicle position and attitude at one instant, as streamed by the autopilot.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct VehicleState {
+    pub position: GeoPoint,
+    pub attitude: VehicleAttitude,
+}
+
+/// Mean Earth radius in metres.
+const EARTH_RADIUS: f64 = 6_371_000.0;
+
+/// Bearing and elevation from `from` to `to`, using a local flat-earth approximation that is
+/// accurate over the few hundred metres of an orbit.
+pub fn direction_to(from: GeoPoint, to: GeoPoint) -> EarthTarget {
+    let north = (to.latitude - from.latitude).to_radians() * EARTH_RADIUS;
+    let east = (to.longitude - from.longitude).to_radians()
+        * EARTH_RADIUS
+        * from.latitude.to_radians().cos();
+    let up = to.altitude - from.altitude;
+    EarthTarget {
+        bearing: east.atan2(north).to_degrees().rem_euclid(360.0) as f32,
+        elevation: up.atan2(north.hypot(east)).to_degrees() as f32,
+    }
+}
+
+/// Wraps an angle in degrees to the range [-180, 180).
+pub fn wrap_degrees(angle: f32) -> f32 {
+    (angle + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Converts degrees to the degrees * 10 representation used on the wire.
+pub fn to_decidegrees(angle: f32) -> i16 {
+    DeciDeg::saturating_from_degrees(angle).into()
+}
+
+/// Picks the yaw in [`yaw_min`, `yaw_max`] to command for `target` given the current yaw, all in
+/// degrees. Of the equivalent angles `target + k * 360` the gimbal can reach, the one needing the
+/// least rotation wins, so a move across ±180 on a wide-range gimbal goes the short way round.
+/// An unreachable target goes to whichever limit minimizes pointing error plus rotation, rather
+/// than always to the numerically nearest limit, which can mean sweeping across the whole range.
+pub fn shortest_yaw(current: f32, target: f32, yaw_min: f32, yaw_max: f32) -> f32 {
+    let target = wrap_degrees(target);
+    let mut best: Option<((bool, f32), f32)> = None;
+    for turns in -2..=2 {
+        let candidate = target + 360.0 * turns as f32;
+        let reachable = candidate.clamp(yaw_min, yaw_max);
+        let error = (candidate - reachable).abs();
+        let cost = (error > 0.0, error + (reachable - current).abs());
+        if best.is_none_or(|(best_cost, _)| cost < best_cost) {
+            best = Some((cost, reachable));
+        }
+    }
+    best.map_or(target, |(_, reachable)| reachable)
+}
+
+/// Computes the gimbal yaw setpoint (degrees * 10) that keeps the camera on a compass bearing
+/// given the current vehicle heading in degrees.
+pub fn heading_setpoint(bearing: f32, heading: f32) -> i16 {
+    to_decidegrees(wrap_degrees(bearing - heading))
+}
+
+/// Computes the gimbal yaw/pitch setpoint (degrees * 10) that points the camera at an
+/// earth-fixed target given the current vehicle attitude.
+pub fn earth_frame_setpoint(target: EarthTarget, vehicle: VehicleAttitude) -> (i16, i16) {
+    let (bearing, elevation) = (target.bearing.to_radians(), target.elevation.to_radians());
+
+    // Target direction in the local NED frame.
+    let earth = [
+        elevation.cos() * bearing.cos(),
+        elevation.cos() * bearing.sin(),
+        -elevation.sin(),
+    ];
+
+    // Rotate into the body frame: transpose of Rz(yaw) * Ry(pitch) * Rx(roll).
+    let (sr, cr) = vehicle.roll.to_radians().sin_cos();
+    let (sp, cp) = vehicle.pitch.to_radians().sin_cos();
+    let (sy, cy) = vehicle.yaw.to_radians().sin_cos();
+    let body = [
+        cp * cy * earth[0] + cp * sy * earth[1] - sp * earth[2],
+        (sr * sp * cy - cr * sy) * earth[0]
+            + (sr * sp * sy + cr * cy) * earth[1]
+            + sr * cp * earth[2],
+        (cr * sp * cy + sr * sy) * earth[0]
+            + (cr * sp * sy - sr * cy) * earth[1]
+            + cr * cp * earth[2],
+    ];
+
+    let theta_yaw = body[1].atan2(body[0]).to_degrees();
+    let theta_pitch = (-body[2]).atan2(body[0].hypot(body[1])).to_degrees();
+
+    (
+        to_decidegrees(wrap_degrees(theta_yaw)),
+        to_decidegrees(theta_pitch),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_degrees() {
+        assert_eq!(wrap_degrees(190.0), -170.0);
+        assert_eq!(wrap_degrees(-190.0), 170.0);
+        assert_eq!(wrap_degrees(360.0), 0.0);
+        assert_eq!(wrap_degrees(45.0), 45.0);
+    }
+
+    #[test]
+    fn test_shortest_yaw() {
+        // Wide-range gimbal: cross ±180 the short way instead of sweeping back through 0.
+        assert_eq!(shortest_yaw(170.0, -170.0, -270.0, 270.0), 190.0);
+        assert_eq!(shortest_yaw(-170.0, 170.0, -270.0, 270.0), -190.0);
+        assert_eq!(shortest_yaw(10.0, 350.0, -270.0, 270.0), -10.0);
+
+        // Only one equivalent angle is reachable.
+        assert_eq!(shortest_yaw(-130.0, 100.0, -135.0, 135.0), 100.0);
+        assert_eq!(shortest_yaw(250.0, -100.0, -270.0, 270.0), 260.0);
+
+        // A reachable target is never traded for a limit closer to the current yaw.
+        assert_eq!(shortest_yaw(130.0, -100.0, -135.0, 135.0), -100.0);
+
+        // Unreachable targets go to the limit minimizing error plus rotation.
+        assert_eq!(shortest_yaw(0.0, 170.0, -135.0, 135.0), 135.0);
+        assert_eq!(shortest_yaw(0.0, -150.0, -135.0, 135.0), -135.0);
+        assert_eq!(shortest_yaw(-130.0, 175.0, -135.0, 135.0), -135.0);
+    }
+
+    #[test]
+    fn test_direction_to() {
+        let vehicle = GeoPoint {
+            latitude: 40.0,
+            longitude: -86.0,
+            altitude: 100.0,
+        };
+        // 100 m north of the vehicle and 100 m below it.
+        let north = GeoPoint {
+            latitude: 40.0 + (100.0 / EARTH_RADIUS).to_degrees(),
+            altitude: 0.0,
+            ..vehicle
+        };
+        let target = direction_to(vehicle, north);
+        assert!(target.bearing.abs() < 0.01 || (target.bearing - 360.0).abs() < 0.01);
+        assert!((target.elevation + 45.0).abs() < 0.01);
+
+        let west = GeoPoint {
+            longitude: -86.001,
+            ..vehicle
+        };
+        let target = direction_to(vehicle, west);
+        assert!((target.bearing - 270.0).abs() < 0.01);
+        assert!(target.elevation.abs() < 0.01);
+    }
+
+    #[test]
+    fn test_heading_setpoint() {
+        assert_eq!(heading_setpoint(90.0, 45.0), 450);
+        assert_eq!(heading_setpoint(10.0, 350.0), 200);
+        assert_eq!(heading_setpoint(350.0, 10.0), -200);
+    }
+
+    #[test]
+    fn test_earth_frame_setpoint_level_vehicle() {
+        let target = EarthTarget {
+            bearing: 90.0,
+            elevation: -30.0,
+        };
+        let vehicle = VehicleAttitude {
+            roll: 0.0,
+            pitch: 0.0,
+            yaw: 45.0,
+        };
+        assert_eq!(earth_frame_setpoint(target, vehicle), (450, -300));
+    }
+
+    #[test]
+    fn test_earth_frame_setpoint_pitched_vehicle() {
+        let target = EarthTarget {
+            bearing: 0.0,
+            elevation: 0.0,
+        };
+        let vehicle = VehicleAttitude {
+            roll: 0.0,
+            pitch: 10.0,
+            yaw: 0.0,
+        };
+        assert_eq!(earth_frame_setpoint(target, vehicle), (0, -100));
+    }
+
+    #[test]
+    fn test_earth_frame_setpoint_rolled_vehicle() {
+        let target = EarthTarget {
+            bearing: 90.0,
+            elevation: 0.0,
+        };
+        let vehicle = VehicleAttitude {
+            roll: 20.0,
+            pitch: 0.0,
+            yaw: 0.0,
+        };
+        assert_eq!(earth_frame_setpoint(target, vehicle), (900, 200));
+    }
+}
@@ -0,0 +1,35 @@
+use crate::constants;
+use std::time::Duration;
+
+/// Timeouts, retries and buffer sizes of a client, given to `A8Mini::connect_to`. The default
+/// matches the fixed behavior: one attempt per command with a `RECV_TIMEOUT` wait for the ack,
+/// and `HTTP_QUERY_ATTEMPTS` attempts per HTTP query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientConfig {
+    /// How long an acknowledged command waits for its response. Adaptive timeouts take
+    /// precedence once enabled.
+    pub ack_timeout: Duration,
+    /// How many times an acknowledged command is resent after its response timed out. Commands
+    /// that did reach the camera, such as `TakePicture`, may run twice when only the ack was
+    /// lost; see `set_response_grace`.
+    pub retries: u32,
+    /// How many times an HTTP query is resent after a timeout or transport failure.
+    pub http_retries: u32,
+    /// Wait between attempts, for both commands and HTTP queries.
+    pub retry_delay: Duration,
+    /// Size of the buffer frames are received into. Never smaller than `RECV_BUFF_SIZE`;
+    /// responses returned by `send_command` are cut to that size.
+    pub recv_buffer_size: usize,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            ack_timeout: constants::RECV_TIMEOUT,
+            retries: 0,
+            http_retries: constants::HTTP_QUERY_ATTEMPTS - 1,
+            retry_delay: constants::RETRY_DELAY,
+            recv_buffer_size: constants::RECV_BUFF_SIZE,
+        }
+    }
+}
@@ -1,10 +1,73 @@
 use std::time::Duration;
 pub const RECV_BUFF_SIZE: usize = 64;
 pub const RECV_TIMEOUT: Duration = Duration::from_millis(500);
+pub const RTT_TIMEOUT_MULTIPLIER: u32 = 4;
+pub const RTT_TIMEOUT_FLOOR: Duration = Duration::from_millis(50);
+pub const RTT_TIMEOUT_CEILING: Duration = Duration::from_secs(2);
+pub const EVENT_CHANNEL_CAPACITY: usize = 64;
+pub const ATTITUDE_CHANNEL_CAPACITY: usize = 256;
+pub const PROGRESS_CHANNEL_CAPACITY: usize = 64;
+pub const TELEMETRY_CHANNEL_CAPACITY: usize = 64;
+pub const FEEDBACK_TAP_CAPACITY: usize = 16;
+pub const PUSHED_FRAME_CAPACITY: usize = 64;
+pub const JITTER_WINDOW: usize = 50;
+pub const SEGMENT_RESTART_GAP: Duration = Duration::from_millis(1000);
+pub const OFFLINE_QUEUE_TTL: Duration = Duration::from_secs(30);
+pub const OFFLINE_QUEUE_CAPACITY: usize = 32;
+pub const AUDIT_LOG_CAPACITY: usize = 256;
+/// Tick of the rate-hold loop, and a starting point for `A8Mini::set_setpoint_spacing`. Not a
+/// documented SDK limit; measure the unit before relying on it.
+pub const ANGLE_SETPOINT_MIN_INTERVAL: Duration = Duration::from_millis(100);
+pub const BREAKER_WINDOW: usize = 10;
+pub const BREAKER_TRIP_RATIO: f32 = 0.5;
+pub const CONNECTION_DEGRADED_AFTER: u32 = 2;
+pub const CONNECTION_LOST_AFTER: u32 = 5;
+pub const CONNECTION_PROBE_INTERVAL: Duration = Duration::from_secs(1);
+pub const RECONNECT_BACKOFF_MIN: Duration = Duration::from_millis(250);
+pub const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(8);
+pub const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(1);
+pub const KEEPALIVE_MISSES: u32 = 3;
+pub const OPERATOR_HOLD: Duration = Duration::from_secs(5);
+/// Mechanical yaw range of the A8 mini in degrees. Yaw setpoints are clamped to it on encoding.
+pub const GIMBAL_YAW_RANGE: (f32, f32) = (-135.0, 135.0);
+/// Pitch range of the A8 mini in degrees for a normally mounted gimbal.
+pub const GIMBAL_PITCH_RANGE: (f32, f32) = (-90.0, 25.0);
+/// Attitude change in decidegrees that counts as the gimbal starting to move.
+pub const LATENCY_ONSET_THRESHOLD: i16 = 5;
+pub const LATENCY_STEP_TIMEOUT: Duration = Duration::from_secs(2);
+/// Assumed gimbal rate in degrees per second per unit of speed command (-100..100), used by the
+/// attitude estimate. Calibrate for the unit if the estimate matters.
+pub const RATE_COMMAND_DEGREES_PER_UNIT: f32 = 0.9;
+/// How long after the last attitude sample the estimate keeps extrapolating.
+pub const ESTIMATE_HORIZON: Duration = Duration::from_millis(500);
+/// How far from center, in decidegrees, yaw and pitch may be for `center` to count as done.
+pub const CENTER_TOLERANCE: u16 = 10;
+pub const CENTER_POLL_INTERVAL: Duration = Duration::from_millis(100);
+pub const CENTER_TIMEOUT: Duration = Duration::from_secs(5);
+pub const ZOOM_STEP_INTERVAL: Duration = Duration::from_millis(100);
+pub const ZOOM_TOLERANCE: f32 = 0.1;
+pub const MIN_ZOOM: f32 = 1.0;
+pub const MAX_ZOOM: f32 = 6.0;
+pub const STREAM_RESOLUTIONS: [(u16, u16); 2] = [(1920, 1080), (1280, 720)];
+pub const BOOT_SILENCE: Duration = Duration::from_secs(5);
+pub const IP_CHANGE_DELAY: Duration = Duration::from_secs(3);
+pub const CAPTURE_FEEDBACK_TIMEOUT: Duration = Duration::from_millis(500);
+pub const BURST_SHOT_TIMEOUT: Duration = Duration::from_secs(2);
+pub const HTTP_QUERY_TIMEOUT: Duration = Duration::from_secs(2);
+pub const HTTP_QUERY_ATTEMPTS: u32 = 3;
+pub const RETRY_DELAY: Duration = Duration::ZERO;
+pub const DOWNLOAD_RESUME_ATTEMPTS: u32 = 5;
+pub const STREAM_BITRATE_KBPS: std::ops::RangeInclusive<u16> = 1000..=4000;
+
+pub const HEADER_LEN: usize = 8;
+pub const ATTITUDE_CMD_ID: u8 = 0x0d;
+pub const FUNCTION_FEEDBACK_CMD_ID: u8 = 0x0b;
 
 pub const CAMERA_IP: &str = "192.168.144.25";
 pub const CAMERA_COMMAND_PORT: &str = "37260";
 pub const CAMERA_HTTP_PORT: &str = "82";
+pub const CAMERA_RTSP_PORT: u16 = 8554;
+pub const BROKER_SOCKET_PATH: &str = "/tmp/a8mini.sock";
 
 pub const API_BASE_URL: &str = "//cgi-bin/media.cgi";
 
@@ -37,7 +100,7 @@ pub const API_BASE_URL: &str = "//cgi-bin/media.cgi";
 // +---------+----+---------+---------+----+------- ... --+---------+
 // |   STX   |CTRL| DATALEN |   SEQ   | CMD|  DATA  ...   |  CRC16  |
 // +---------+----+---------+---------+----+------- ... --+---------+
-pub const NUM_COMMANDS: usize = 29; // update this if more commands are added
+pub const NUM_COMMANDS: usize = 35; // update this if more commands are added
 pub const HARDCODED_COMMANDS: [&[u8]; NUM_COMMANDS] = [
     &[
         0x55, 0x66, 0x01, 0x01, 0x00, 0x00, 0x00, 0x08, 0x01, 0xd1, 0x12,
@@ -136,6 +199,18 @@ pub const HARDCODED_COMMANDS: [&[u8]; NUM_COMMANDS] = [
     &[
         0x55, 0x66, 0x01, 0x02, 0x00, 0x00, 0x00, 0x80, 0x00, 0x01, 0x1a, 0x8a,
     ], // Reboot Gimbal
+    &[0x55, 0x66, 0x01, 0x00, 0x00, 0x00, 0x00, 0x18, 0x7c, 0x47], // Acquire Current Zoom Value
+    &[0x55, 0x66, 0x01, 0x00, 0x00, 0x00, 0x00, 0x81, 0xec, 0x55], // Acquire IP Configuration
+    &[
+        0x55, 0x66, 0x01, 0x01, 0x00, 0x00, 0x00, 0x0c, 0x01, 0x15, 0xde,
+    ], // Toggle HDR
+    &[0x55, 0x66, 0x01, 0x00, 0x00, 0x00, 0x00, 0x10, 0x74, 0xc6], // Acquire Image Mode
+    &[
+        0x55, 0x66, 0x01, 0x01, 0x00, 0x00, 0x00, 0x05, 0x00, 0xac, 0x74,
+    ], // Zoom Stop
+    &[
+        0x55, 0x66, 0x01, 0x01, 0x00, 0x00, 0x00, 0x06, 0x00, 0xff, 0x21,
+    ], // Manual Focus Stop
 ];
 
 pub const CRC16_TAB: [u16; 256] = [
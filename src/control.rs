@@ -1,15 +1,56 @@
-use crate::{checksum, constants};
+use crate::capture::CaptureKind;
+use crate::decideg::DeciDeg;
+use crate::urls::{self, MediaUrls};
+use crate::{checksum, constants, A8MiniError};
 use serde::{Deserialize, Serialize};
-
+use std::error::Error;
+use std::net::Ipv4Addr;
 
 /// Trait for camera commands
 pub trait Command {
     fn to_bytes(&self) -> Vec<u8>;
+
+    /// Absolute yaw/pitch setpoint carried by the command, if any. Used for soft limit checks.
+    fn angle_setpoint(&self) -> Option<(i16, i16)> {
+        None
+    }
+
+    /// Yaw/pitch speed setpoint carried by the command, if any.
+    fn rate_setpoint(&self) -> Option<(i8, i8)> {
+        None
+    }
+
+    /// Whether the command moves the gimbal.
+    fn is_motion(&self) -> bool {
+        false
+    }
+
+    /// Whether the command halts gimbal motion. Stop commands are never blocked by the motion interlock.
+    fn is_stop(&self) -> bool {
+        false
+    }
+
+    /// Whether the command is destructive (reboot, format, firmware upgrade). Destructive commands
+    /// are refused by `RestrictedA8Mini`.
+    fn is_destructive(&self) -> bool {
+        false
+    }
+
+    /// CMD_ID of the frame the camera answers this command with.
+    fn ack_cmd_id(&self) -> u8 {
+        self.to_bytes()[7]
+    }
 }
 
 /// Trait for HTTP API queries
 pub trait HTTPQuery {
     fn to_string(&self) -> String;
+
+    /// URL of the query on the camera served at `urls`. Defaults to `to_string`, for queries
+    /// that carry their own full URL.
+    fn url(&self, _urls: &MediaUrls) -> String {
+        self.to_string()
+    }
 }
 
 /// Enums for hardcoded simple commands.
@@ -44,19 +85,72 @@ pub enum A8MiniSimpleCommand {
     LaserRangefinderInformation = 26,
     RebootCamera = 27,
     RebootGimbal = 28,
+    CurrentZoomInformation = 29,
+    NetworkInformation = 30,
+    ToggleHDR = 31,
+    ImageModeInformation = 32,
+    ZoomStop = 33,  // handled ACK (sta)
+    FocusStop = 34, // handled ACK (sta)
 }
 
 impl Command for A8MiniSimpleCommand {
     fn to_bytes(&self) -> Vec<u8> {
         constants::HARDCODED_COMMANDS[*self as usize].to_vec()
     }
+
+    fn is_motion(&self) -> bool {
+        matches!(
+            *self,
+            A8MiniSimpleCommand::AutoCenter
+                | A8MiniSimpleCommand::RotateUp
+                | A8MiniSimpleCommand::RotateDown
+                | A8MiniSimpleCommand::RotateRight
+                | A8MiniSimpleCommand::RotateLeft
+                | A8MiniSimpleCommand::StopRotation
+                | A8MiniSimpleCommand::Rotate100100
+        )
+    }
+
+    fn is_stop(&self) -> bool {
+        *self == A8MiniSimpleCommand::StopRotation
+    }
+
+    fn ack_cmd_id(&self) -> u8 {
+        match *self {
+            // Captures and HDR changes are confirmed by a function feedback frame rather than an ACK.
+            A8MiniSimpleCommand::TakePicture
+            | A8MiniSimpleCommand::RecordVideo
+            | A8MiniSimpleCommand::ToggleHDR => constants::FUNCTION_FEEDBACK_CMD_ID,
+            _ => self.to_bytes()[7],
+        }
+    }
+
+    fn is_destructive(&self) -> bool {
+        matches!(
+            *self,
+            A8MiniSimpleCommand::RebootCamera | A8MiniSimpleCommand::RebootGimbal
+        )
+    }
 }
 
 /// Enums for commands that require continuous values for data field.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum A8MiniComplexCommand {
     SetYawPitchSpeed(i8, i8),
-    SetYawPitchAngle(i16, i16),
+    /// Absolute yaw and pitch, clamped to the mechanical range on encoding.
+    SetYawPitchAngle(DeciDeg, DeciDeg),
+    /// Absolute zoom as integer and tenths parts, e.g. (4, 5) for 4.5x.
+    SetAbsoluteZoom(u8, u8),
+    GetEncodingParams(StreamType),
+    SetEncodingParams(EncodingParams),
+    SetNetworkConfig(NetworkConfig),
+    /// Asks the gimbal to push attitude frames (CMD 0x0D) at the given rate.
+    RequestAttitudeStream(AttitudeStreamRate),
+    /// Selects which sensors feed the main stream and picture-in-picture (multi-sensor payloads).
+    SetImageMode(ImageMode),
+    /// Sets the camera clock, used for media file times and photo EXIF, to a UNIX time in
+    /// microseconds.
+    SetUtcTime(u64),
 }
 
 impl Command for A8MiniComplexCommand {
@@ -72,11 +166,81 @@ impl Command for A8MiniComplexCommand {
 
                 byte_arr
             }
-            A8MiniComplexCommand::SetYawPitchAngle(theta_yaw, theta_pitch) => {
+            A8MiniComplexCommand::SetYawPitchAngle(DeciDeg(theta_yaw), DeciDeg(theta_pitch)) => {
                 let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x04, 0x00, 0x00, 0x00, 0x0e];
 
-                byte_arr.extend_from_slice(&theta_yaw.clamp(-1350, 1350).to_be_bytes());
-                byte_arr.extend_from_slice(&theta_pitch.clamp(-900, 250).to_be_bytes());
+                byte_arr.extend_from_slice(&theta_yaw.clamp(-1350, 1350).to_le_bytes());
+                byte_arr.extend_from_slice(&theta_pitch.clamp(-900, 250).to_le_bytes());
+
+                byte_arr.extend_from_slice(&checksum::crc16_calc(&byte_arr, 0));
+
+                byte_arr
+            }
+            A8MiniComplexCommand::SetAbsoluteZoom(zoom_int, zoom_float) => {
+                let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x02, 0x00, 0x00, 0x00, 0x0f];
+
+                byte_arr.push(zoom_int);
+                byte_arr.push(zoom_float.min(9));
+
+                byte_arr.extend_from_slice(&checksum::crc16_calc(&byte_arr, 0));
+
+                byte_arr
+            }
+            A8MiniComplexCommand::GetEncodingParams(stream_type) => {
+                let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x01, 0x00, 0x00, 0x00, 0x20];
+
+                byte_arr.push(stream_type as u8);
+
+                byte_arr.extend_from_slice(&checksum::crc16_calc(&byte_arr, 0));
+
+                byte_arr
+            }
+            A8MiniComplexCommand::SetEncodingParams(params) => {
+                let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x09, 0x00, 0x00, 0x00, 0x21];
+
+                byte_arr.push(params.stream_type as u8);
+                byte_arr.push(params.codec as u8);
+                byte_arr.extend_from_slice(&params.width.to_le_bytes());
+                byte_arr.extend_from_slice(&params.height.to_le_bytes());
+                byte_arr.extend_from_slice(&params.bitrate_kbps.to_le_bytes());
+                byte_arr.push(0x00); // reserved
+
+                byte_arr.extend_from_slice(&checksum::crc16_calc(&byte_arr, 0));
+
+                byte_arr
+            }
+            A8MiniComplexCommand::RequestAttitudeStream(rate) => {
+                let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x02, 0x00, 0x00, 0x00, 0x25];
+
+                byte_arr.push(0x01); // data_type: attitude
+                byte_arr.push(rate as u8);
+
+                byte_arr.extend_from_slice(&checksum::crc16_calc(&byte_arr, 0));
+
+                byte_arr
+            }
+            A8MiniComplexCommand::SetNetworkConfig(config) => {
+                let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x0c, 0x00, 0x00, 0x00, 0x82];
+
+                byte_arr.extend_from_slice(&config.to_payload());
+
+                byte_arr.extend_from_slice(&checksum::crc16_calc(&byte_arr, 0));
+
+                byte_arr
+            }
+            A8MiniComplexCommand::SetImageMode(mode) => {
+                let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x01, 0x00, 0x00, 0x00, 0x11];
+
+                byte_arr.push(mode as u8);
+
+                byte_arr.extend_from_slice(&checksum::crc16_calc(&byte_arr, 0));
+
+                byte_arr
+            }
+            A8MiniComplexCommand::SetUtcTime(unix_us) => {
+                let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x08, 0x00, 0x00, 0x00, 0x30];
+
+                byte_arr.extend_from_slice(&unix_us.to_le_bytes());
 
                 byte_arr.extend_from_slice(&checksum::crc16_calc(&byte_arr, 0));
 
@@ -84,6 +248,37 @@ impl Command for A8MiniComplexCommand {
             }
         }
     }
+
+    fn angle_setpoint(&self) -> Option<(i16, i16)> {
+        match *self {
+            A8MiniComplexCommand::SetYawPitchAngle(DeciDeg(theta_yaw), DeciDeg(theta_pitch)) => {
+                Some((theta_yaw, theta_pitch))
+            }
+            _ => None,
+        }
+    }
+
+    fn rate_setpoint(&self) -> Option<(i8, i8)> {
+        match *self {
+            A8MiniComplexCommand::SetYawPitchSpeed(v_yaw, v_pitch) => Some((v_yaw, v_pitch)),
+            _ => None,
+        }
+    }
+
+    fn is_motion(&self) -> bool {
+        matches!(
+            *self,
+            A8MiniComplexCommand::SetYawPitchSpeed(..) | A8MiniComplexCommand::SetYawPitchAngle(..)
+        )
+    }
+
+    fn is_stop(&self) -> bool {
+        *self == A8MiniComplexCommand::SetYawPitchSpeed(0, 0)
+    }
+
+    fn is_destructive(&self) -> bool {
+        matches!(*self, A8MiniComplexCommand::SetNetworkConfig(_))
+    }
 }
 
 /// Enums for simple HTTP queries
@@ -97,11 +292,15 @@ pub enum A8MiniSimpleHTTPQuery {
 
 impl HTTPQuery for A8MiniSimpleHTTPQuery {
     fn to_string(&self) -> String {
+        self.url(&MediaUrls::default())
+    }
+
+    fn url(&self, urls: &MediaUrls) -> String {
         match *self {
-            A8MiniSimpleHTTPQuery::GetDirectoriesPhotos => "http://192.168.144.25:82/cgi-bin/media.cgi/api/v1/getdirectories?media_type=0".to_string(),
-            A8MiniSimpleHTTPQuery::GetDirectoriesVideos => "http://192.168.144.25:82/cgi-bin/media.cgi/api/v1/getdirectories?media_type=1".to_string(),
-            A8MiniSimpleHTTPQuery::GetMediaCountPhotos => "http://192.168.144.25:82/cgi-bin/media.cgi/api/v1/getmediacount?media_type=0&path=101SIYI_IMG".to_string(),
-            A8MiniSimpleHTTPQuery::GetMediaCountVideos => "http://192.168.144.25:82/cgi-bin/media.cgi/api/v1/getmediacount?media_type=1&path=101SIYI_VID".to_string(),
+            A8MiniSimpleHTTPQuery::GetDirectoriesPhotos => urls.directories(CaptureKind::Photo),
+            A8MiniSimpleHTTPQuery::GetDirectoriesVideos => urls.directories(CaptureKind::Video),
+            A8MiniSimpleHTTPQuery::GetMediaCountPhotos => urls.media_count(CaptureKind::Photo),
+            A8MiniSimpleHTTPQuery::GetMediaCountVideos => urls.media_count(CaptureKind::Video),
         }
     }
 }
@@ -113,19 +312,36 @@ pub enum A8MiniComplexHTTPQuery {
     GetVideo(u8),
 }
 
-impl HTTPQuery for A8MiniComplexHTTPQuery {
-    fn to_string(&self) -> String {
+impl A8MiniComplexHTTPQuery {
+    pub fn kind(&self) -> CaptureKind {
         match *self {
-            A8MiniComplexHTTPQuery::GetPhoto(photo_ind) => format!(
-                "http://192.168.144.25:82/photo/101SIYI_IMG/IMG_{:0>4}.jpg",
-                photo_ind
-            ),
-            A8MiniComplexHTTPQuery::GetVideo(video_ind) => format!(
-                "http://192.168.144.25:82/video/101SIYI_VID/VID_{:0>4}.mpeg",
-                video_ind
-            ),
+            A8MiniComplexHTTPQuery::GetPhoto(_) => CaptureKind::Photo,
+            A8MiniComplexHTTPQuery::GetVideo(_) => CaptureKind::Video,
         }
     }
+
+    pub fn index(&self) -> u8 {
+        match *self {
+            A8MiniComplexHTTPQuery::GetPhoto(index) | A8MiniComplexHTTPQuery::GetVideo(index) => {
+                index
+            }
+        }
+    }
+
+    /// Name of the file on the camera, e.g. `IMG_0007.jpg`.
+    pub fn file_name(&self) -> String {
+        urls::media_file_name(self.kind(), self.index())
+    }
+}
+
+impl HTTPQuery for A8MiniComplexHTTPQuery {
+    fn to_string(&self) -> String {
+        self.url(&MediaUrls::default())
+    }
+
+    fn url(&self, urls: &MediaUrls) -> String {
+        urls.media_file(self.kind(), self.index())
+    }
 }
 
 /// Response json format
@@ -154,7 +370,7 @@ pub struct HTTPResponseData {
 }
 
 /// Camera attitude information
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct A8MiniAtittude {
     pub theta_yaw: i16,
     pub theta_pitch: i16,
@@ -164,15 +380,458 @@ pub struct A8MiniAtittude {
     pub v_roll: i16,
 }
 
+/// Recording state reported in the gimbal configuration information (CMD 0x0A).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingStatus {
+    NotRecording,
+    Recording,
+    NoCard,
+    DataLoss,
+}
+
+impl RecordingStatus {
+    pub fn from_u8(record_sta: u8) -> Option<Self> {
+        match record_sta {
+            0 => Some(RecordingStatus::NotRecording),
+            1 => Some(RecordingStatus::Recording),
+            2 => Some(RecordingStatus::NoCard),
+            3 => Some(RecordingStatus::DataLoss),
+            _ => None,
+        }
+    }
+}
+
+/// Gimbal motion mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MotionMode {
+    Lock,
+    Follow,
+    FPV,
+}
+
+impl MotionMode {
+    pub fn from_u8(gimbal_motion_mode: u8) -> Option<Self> {
+        match gimbal_motion_mode {
+            0 => Some(MotionMode::Lock),
+            1 => Some(MotionMode::Follow),
+            2 => Some(MotionMode::FPV),
+            _ => None,
+        }
+    }
+
+    /// Command that switches the gimbal into this mode.
+    pub fn command(&self) -> A8MiniSimpleCommand {
+        match *self {
+            MotionMode::Lock => A8MiniSimpleCommand::SetLockMode,
+            MotionMode::Follow => A8MiniSimpleCommand::SetFollowMode,
+            MotionMode::FPV => A8MiniSimpleCommand::SetFPVMode,
+        }
+    }
+}
+
+/// Alias of [`MotionMode`] under the name used by the gimbal's documentation.
+pub type GimbalMode = MotionMode;
+
+/// Gimbal mounting direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MountingDirection {
+    Normal,
+    UpsideDown,
+}
+
+impl MountingDirection {
+    pub fn from_u8(gimbal_mounting_dir: u8) -> Option<Self> {
+        match gimbal_mounting_dir {
+            1 => Some(MountingDirection::Normal),
+            2 => Some(MountingDirection::UpsideDown),
+            _ => None,
+        }
+    }
+
+    /// Valid pitch range (degrees * 10) for this mounting direction.
+    pub fn pitch_range(&self) -> (i16, i16) {
+        match *self {
+            MountingDirection::Normal => (-900, 250),
+            MountingDirection::UpsideDown => (-250, 900),
+        }
+    }
+
+    /// Pitch (degrees * 10) that points the camera fully up, away from the ground. Upside down
+    /// the pitch axis is mirrored, so this is the other end of the range.
+    pub fn park_pitch(&self) -> i16 {
+        match self {
+            MountingDirection::Normal => self.pitch_range().1,
+            MountingDirection::UpsideDown => self.pitch_range().0,
+        }
+    }
+}
+
+/// Active analog/digital video output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoOutput {
+    HDMI,
+    CVBS,
+}
+
+impl VideoOutput {
+    pub fn from_u8(video_hdmi_or_cvbs: u8) -> Option<Self> {
+        match video_hdmi_or_cvbs {
+            0 => Some(VideoOutput::HDMI),
+            1 => Some(VideoOutput::CVBS),
+            _ => None,
+        }
+    }
+}
+
+/// Decoded gimbal configuration information (CMD 0x0A).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GimbalInfo {
+    pub hdr: bool,
+    pub recording: RecordingStatus,
+    pub motion_mode: MotionMode,
+    /// `None` when the firmware reports the reserved value.
+    pub mounting: Option<MountingDirection>,
+    /// `None` when the firmware omits the field.
+    pub video_output: Option<VideoOutput>,
+}
+
+impl GimbalInfo {
+    /// Decodes the DATA field of the gimbal configuration ACK.
+    pub fn from_payload(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        if data.len() < 6 {
+            return Err(
+                A8MiniError::MalformedAck("Gimbal configuration ACK too short.".into()).into(),
+            );
+        }
+
+        Ok(GimbalInfo {
+            hdr: data[1] != 0,
+            recording: RecordingStatus::from_u8(data[3])
+                .ok_or_else(|| A8MiniError::MalformedAck("Unknown recording status.".into()))?,
+            motion_mode: MotionMode::from_u8(data[4])
+                .ok_or_else(|| A8MiniError::MalformedAck("Unknown motion mode.".into()))?,
+            mounting: MountingDirection::from_u8(data[5]),
+            video_output: data.get(6).copied().and_then(VideoOutput::from_u8),
+        })
+    }
+}
+
+/// Firmware version in major.minor.patch form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Version {
+    pub major: u8,
+    pub minor: u8,
+    pub patch: u8,
+}
+
+impl Version {
+    /// Decodes a little-endian version word. The top byte is unused.
+    pub fn from_bytes(bytes: [u8; 4]) -> Self {
+        Version {
+            major: bytes[2],
+            minor: bytes[1],
+            patch: bytes[0],
+        }
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "v{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Decoded firmware version information (CMD 0x01).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FirmwareVersion {
+    pub camera: Version,
+    pub gimbal: Version,
+    /// `None` when the payload has no zoom module or the firmware omits the field.
+    pub zoom: Option<Version>,
+}
+
+impl FirmwareVersion {
+    /// Decodes the DATA field of the firmware version ACK.
+    pub fn from_payload(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        if data.len() < 8 {
+            return Err(A8MiniError::MalformedAck("Firmware version ACK too short.".into()).into());
+        }
+
+        let word = |at: usize| [data[at], data[at + 1], data[at + 2], data[at + 3]];
+        Ok(FirmwareVersion {
+            camera: Version::from_bytes(word(0)),
+            gimbal: Version::from_bytes(word(4)),
+            zoom: (data.len() >= 12 && data[8..12] != [0; 4]).then(|| Version::from_bytes(word(8))),
+        })
+    }
+}
+
+/// Video stream selected by the encoding parameter commands (CMD 0x20/0x21).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamType {
+    Recording = 0,
+    Main = 1,
+    Sub = 2,
+}
+
+impl StreamType {
+    pub fn from_u8(stream_type: u8) -> Option<Self> {
+        match stream_type {
+            0 => Some(StreamType::Recording),
+            1 => Some(StreamType::Main),
+            2 => Some(StreamType::Sub),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoCodec {
+    H264 = 1,
+    H265 = 2,
+}
+
+impl VideoCodec {
+    pub fn from_u8(video_enc_type: u8) -> Option<Self> {
+        match video_enc_type {
+            1 => Some(VideoCodec::H264),
+            2 => Some(VideoCodec::H265),
+            _ => None,
+        }
+    }
+}
+
+/// Encoding parameters of one video stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodingParams {
+    pub stream_type: StreamType,
+    pub codec: VideoCodec,
+    pub width: u16,
+    pub height: u16,
+    pub bitrate_kbps: u16,
+}
+
+impl EncodingParams {
+    /// Decodes the DATA field of the encoding parameter ACK (CMD 0x20).
+    pub fn from_payload(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        if data.len() < 8 {
+            return Err(
+                A8MiniError::MalformedAck("Encoding parameter ACK too short.".into()).into(),
+            );
+        }
+
+        Ok(EncodingParams {
+            stream_type: StreamType::from_u8(data[0])
+                .ok_or_else(|| A8MiniError::MalformedAck("Unknown stream type.".into()))?,
+            codec: VideoCodec::from_u8(data[1])
+                .ok_or_else(|| A8MiniError::MalformedAck("Unknown video codec.".into()))?,
+            width: u16::from_le_bytes([data[2], data[3]]),
+            height: u16::from_le_bytes([data[4], data[5]]),
+            bitrate_kbps: u16::from_le_bytes([data[6], data[7]]),
+        })
+    }
+
+    /// Checks the resolution and bitrate against the ranges supported by the A8 mini firmware.
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        if !constants::STREAM_RESOLUTIONS.contains(&(self.width, self.height)) {
+            return Err(A8MiniError::InvalidArgument(format!(
+                "Unsupported resolution {}x{}.",
+                self.width, self.height
+            ))
+            .into());
+        }
+        if !constants::STREAM_BITRATE_KBPS.contains(&self.bitrate_kbps) {
+            return Err(A8MiniError::InvalidArgument(format!(
+                "Bitrate {} kbps outside {:?}.",
+                self.bitrate_kbps,
+                constants::STREAM_BITRATE_KBPS
+            ))
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Camera network configuration (CMD 0x81/0x82). Addresses are sent as little-endian `u32`s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkConfig {
+    pub ip: Ipv4Addr,
+    pub netmask: Ipv4Addr,
+    pub gateway: Ipv4Addr,
+}
+
+impl NetworkConfig {
+    /// Decodes the DATA field of the IP configuration ACK.
+    pub fn from_payload(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let address = |offset: usize| -> Result<Ipv4Addr, Box<dyn Error>> {
+            let bytes = data.get(offset..offset + 4).ok_or_else(|| {
+                A8MiniError::MalformedAck("IP configuration ACK too short.".into())
+            })?;
+            Ok(Ipv4Addr::from(u32::from_le_bytes([
+                bytes[0], bytes[1], bytes[2], bytes[3],
+            ])))
+        };
+
+        Ok(NetworkConfig {
+            ip: address(0)?,
+            netmask: address(4)?,
+            gateway: address(8)?,
+        })
+    }
+
+    pub fn to_payload(&self) -> [u8; 12] {
+        let mut payload = [0; 12];
+        payload[0..4].copy_from_slice(&u32::from(self.ip).to_le_bytes());
+        payload[4..8].copy_from_slice(&u32::from(self.netmask).to_le_bytes());
+        payload[8..12].copy_from_slice(&u32::from(self.gateway).to_le_bytes());
+        payload
+    }
+}
+
+/// Push rate for the gimbal attitude data stream (CMD 0x25).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttitudeStreamRate {
+    Off = 0,
+    Hz2 = 1,
+    Hz4 = 2,
+    Hz5 = 3,
+    Hz10 = 4,
+    Hz20 = 5,
+    Hz50 = 6,
+    Hz100 = 7,
+}
+
+/// Image sensor of a multi-sensor payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sensor {
+    Zoom,
+    Wide,
+    Thermal,
+}
+
+/// Sensor layout of the video stream (CMD 0x10/0x11). Split modes show two sensors side by
+/// side on the main stream; single modes show one, with the other on the sub stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ImageMode {
+    /// Main: zoom and thermal, sub: wide.
+    SplitZoomThermal = 0,
+    /// Main: wide and thermal, sub: zoom.
+    SplitWideThermal = 1,
+    /// Main: zoom and wide, sub: thermal.
+    SplitZoomWide = 2,
+    /// Main: zoom, sub: thermal.
+    ZoomWithThermal = 3,
+    /// Main: zoom, sub: wide.
+    ZoomWithWide = 4,
+    /// Main: wide, sub: thermal.
+    WideWithThermal = 5,
+    /// Main: wide, sub: zoom.
+    WideWithZoom = 6,
+    /// Main: thermal, sub: zoom.
+    ThermalWithZoom = 7,
+    /// Main: thermal, sub: wide.
+    ThermalWithWide = 8,
+}
+
+impl ImageMode {
+    pub const ALL: [ImageMode; 9] = [
+        ImageMode::SplitZoomThermal,
+        ImageMode::SplitWideThermal,
+        ImageMode::SplitZoomWide,
+        ImageMode::ZoomWithThermal,
+        ImageMode::ZoomWithWide,
+        ImageMode::WideWithThermal,
+        ImageMode::WideWithZoom,
+        ImageMode::ThermalWithZoom,
+        ImageMode::ThermalWithWide,
+    ];
+
+    /// The mode showing `main` on the main stream (one sensor, or two side by side) and `sub`
+    /// on the sub stream, if the protocol has one.
+    pub fn from_layout(main: &[Sensor], sub: Sensor) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.main_sensors() == main && mode.sub_sensor() == sub)
+    }
+
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(ImageMode::SplitZoomThermal),
+            1 => Some(ImageMode::SplitWideThermal),
+            2 => Some(ImageMode::SplitZoomWide),
+            3 => Some(ImageMode::ZoomWithThermal),
+            4 => Some(ImageMode::ZoomWithWide),
+            5 => Some(ImageMode::WideWithThermal),
+            6 => Some(ImageMode::WideWithZoom),
+            7 => Some(ImageMode::ThermalWithZoom),
+            8 => Some(ImageMode::ThermalWithWide),
+            _ => None,
+        }
+    }
+
+    /// Every sensor the mode shows on either stream.
+    pub fn sensors(&self) -> &'static [Sensor] {
+        use Sensor::*;
+        match *self {
+            ImageMode::SplitZoomThermal => &[Zoom, Thermal, Wide],
+            ImageMode::SplitWideThermal => &[Wide, Thermal, Zoom],
+            ImageMode::SplitZoomWide => &[Zoom, Wide, Thermal],
+            ImageMode::ZoomWithThermal => &[Zoom, Thermal],
+            ImageMode::ZoomWithWide => &[Zoom, Wide],
+            ImageMode::WideWithThermal => &[Wide, Thermal],
+            ImageMode::WideWithZoom => &[Wide, Zoom],
+            ImageMode::ThermalWithZoom => &[Thermal, Zoom],
+            ImageMode::ThermalWithWide => &[Thermal, Wide],
+        }
+    }
+
+    /// Sensors on the main stream, left to right in split modes.
+    pub fn main_sensors(&self) -> &'static [Sensor] {
+        let sensors = self.sensors();
+        &sensors[..sensors.len() - 1]
+    }
+
+    pub fn sub_sensor(&self) -> Sensor {
+        *self.sensors().last().unwrap()
+    }
+
+    /// Whether the main stream shows two sensors side by side.
+    pub fn is_split(&self) -> bool {
+        self.main_sensors().len() > 1
+    }
+}
+
+/// State of the camera's TF card as far as the SDK reports it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StorageStatus {
+    #[default]
+    Unknown,
+    Ready,
+    NoCard,
+    DataLoss,
+}
+
+impl From<RecordingStatus> for StorageStatus {
+    fn from(status: RecordingStatus) -> Self {
+        match status {
+            RecordingStatus::NotRecording | RecordingStatus::Recording => StorageStatus::Ready,
+            RecordingStatus::NoCard => StorageStatus::NoCard,
+            RecordingStatus::DataLoss => StorageStatus::DataLoss,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_complex_command_creation_angle() {
-        let computed_command = A8MiniComplexCommand::SetYawPitchAngle(130, -20).to_bytes();
+        let computed_command =
+            A8MiniComplexCommand::SetYawPitchAngle(DeciDeg(130), DeciDeg(-20)).to_bytes();
         let expected_command: [u8; 14] = [
-            0x55, 0x66, 0x01, 0x04, 0x00, 0x00, 0x00, 0x0e, 0x00, 0x82, 0xff, 0xec, 0x8f, 0xad,
+            0x55, 0x66, 0x01, 0x04, 0x00, 0x00, 0x00, 0x0e, 0x82, 0x00, 0xec, 0xff, 0x97, 0xbc,
         ];
         assert_eq!(computed_command, expected_command);
     }
@@ -186,6 +845,143 @@ mod tests {
         assert_eq!(computed_command, expected_command);
     }
 
+    #[test]
+    fn test_complex_command_creation_zoom() {
+        // Same frame as the hardcoded ZoomMax command apart from SEQ.
+        let computed_command = A8MiniComplexCommand::SetAbsoluteZoom(4, 5).to_bytes();
+        let mut expected_command = A8MiniSimpleCommand::ZoomMax.to_bytes();
+        expected_command[5] = 0x00;
+        assert_eq!(computed_command[..10], expected_command[..10]);
+        assert_eq!(
+            computed_command[10..],
+            checksum::crc16_calc(&computed_command[..10], 0)
+        );
+        assert!(!A8MiniComplexCommand::SetAbsoluteZoom(4, 5).is_motion());
+    }
+
+    #[test]
+    fn test_encoding_params() {
+        let params =
+            EncodingParams::from_payload(&[0x01, 0x02, 0x80, 0x07, 0x38, 0x04, 0xb8, 0x0b, 0x1e])
+                .unwrap();
+        assert_eq!(
+            params,
+            EncodingParams {
+                stream_type: StreamType::Main,
+                codec: VideoCodec::H265,
+                width: 1920,
+                height: 1080,
+                bitrate_kbps: 3000,
+            }
+        );
+        assert!(params.validate().is_ok());
+
+        let computed_command = A8MiniComplexCommand::SetEncodingParams(params).to_bytes();
+        assert_eq!(computed_command[3], 0x09);
+        assert_eq!(
+            computed_command[8..17],
+            [0x01, 0x02, 0x80, 0x07, 0x38, 0x04, 0xb8, 0x0b, 0x00]
+        );
+
+        assert!(EncodingParams {
+            bitrate_kbps: 100,
+            ..params
+        }
+        .validate()
+        .is_err());
+        assert!(EncodingParams {
+            width: 640,
+            ..params
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_network_config_round_trip() {
+        let config = NetworkConfig {
+            ip: Ipv4Addr::new(192, 168, 144, 26),
+            netmask: Ipv4Addr::new(255, 255, 255, 0),
+            gateway: Ipv4Addr::new(192, 168, 144, 1),
+        };
+        let payload = config.to_payload();
+        assert_eq!(payload[0..4], [26, 144, 168, 192]);
+        assert_eq!(NetworkConfig::from_payload(&payload).unwrap(), config);
+        assert!(NetworkConfig::from_payload(&payload[..8]).is_err());
+
+        let computed_command = A8MiniComplexCommand::SetNetworkConfig(config).to_bytes();
+        assert_eq!(computed_command.len(), 22);
+        assert!(A8MiniComplexCommand::SetNetworkConfig(config).is_destructive());
+    }
+
+    #[test]
+    fn test_complex_command_creation_attitude_stream() {
+        let computed_command =
+            A8MiniComplexCommand::RequestAttitudeStream(AttitudeStreamRate::Hz50).to_bytes();
+        assert_eq!(
+            computed_command[..10],
+            [0x55, 0x66, 0x01, 0x02, 0x00, 0x00, 0x00, 0x25, 0x01, 0x06]
+        );
+        assert_eq!(
+            computed_command[10..],
+            checksum::crc16_calc(&computed_command[..10], 0)
+        );
+    }
+
+    #[test]
+    fn test_utc_time() {
+        let computed_command = A8MiniComplexCommand::SetUtcTime(1_700_000_000_123_456).to_bytes();
+        assert_eq!(
+            computed_command[..8],
+            [0x55, 0x66, 0x01, 0x08, 0x00, 0x00, 0x00, 0x30]
+        );
+        assert_eq!(
+            computed_command[8..16],
+            1_700_000_000_123_456u64.to_le_bytes()
+        );
+        assert_eq!(
+            computed_command[16..],
+            checksum::crc16_calc(&computed_command[..16], 0)
+        );
+    }
+
+    #[test]
+    fn test_image_mode() {
+        let computed_command =
+            A8MiniComplexCommand::SetImageMode(ImageMode::ThermalWithWide).to_bytes();
+        assert_eq!(
+            computed_command[..9],
+            [0x55, 0x66, 0x01, 0x01, 0x00, 0x00, 0x00, 0x11, 0x08]
+        );
+        assert_eq!(
+            computed_command[9..],
+            checksum::crc16_calc(&computed_command[..9], 0)
+        );
+
+        assert_eq!(ImageMode::from_u8(5), Some(ImageMode::WideWithThermal));
+        assert_eq!(ImageMode::from_u8(9), None);
+        assert!(!ImageMode::WideWithThermal.sensors().contains(&Sensor::Zoom));
+
+        assert_eq!(
+            ImageMode::SplitWideThermal.main_sensors(),
+            &[Sensor::Wide, Sensor::Thermal]
+        );
+        assert_eq!(ImageMode::SplitWideThermal.sub_sensor(), Sensor::Zoom);
+        assert!(ImageMode::SplitWideThermal.is_split());
+        assert!(!ImageMode::ThermalWithZoom.is_split());
+        for mode in ImageMode::ALL {
+            assert_eq!(
+                ImageMode::from_layout(mode.main_sensors(), mode.sub_sensor()),
+                Some(mode)
+            );
+            assert_eq!(ImageMode::from_u8(mode as u8), Some(mode));
+        }
+        assert_eq!(
+            ImageMode::from_layout(&[Sensor::Thermal, Sensor::Zoom], Sensor::Wide),
+            None
+        );
+    }
+
     #[test]
     fn test_byte_deserialization() {
         let attitude_bytes: &[u8] = &[
@@ -206,4 +1002,86 @@ mod tests {
 
         assert_eq!(computed_attitude_info, expected_attitude_info);
     }
+
+    #[test]
+    fn test_is_motion() {
+        assert!(A8MiniSimpleCommand::RotateUp.is_motion());
+        assert!(A8MiniSimpleCommand::AutoCenter.is_motion());
+        assert!(!A8MiniSimpleCommand::TakePicture.is_motion());
+        assert!(!A8MiniSimpleCommand::AttitudeInformation.is_motion());
+        assert!(A8MiniComplexCommand::SetYawPitchSpeed(0, 0).is_motion());
+        assert!(A8MiniComplexCommand::SetYawPitchSpeed(0, 0).is_stop());
+        assert!(!A8MiniComplexCommand::SetYawPitchSpeed(10, 0).is_stop());
+        assert!(A8MiniSimpleCommand::StopRotation.is_stop());
+        assert!(A8MiniSimpleCommand::RebootCamera.is_destructive());
+        assert!(!A8MiniSimpleCommand::TakePicture.is_destructive());
+    }
+
+    #[test]
+    fn test_gimbal_info_from_payload() {
+        let info = GimbalInfo::from_payload(&[0x00, 0x01, 0x00, 0x01, 0x01, 0x02, 0x00]).unwrap();
+        assert_eq!(
+            info,
+            GimbalInfo {
+                hdr: true,
+                recording: RecordingStatus::Recording,
+                motion_mode: MotionMode::Follow,
+                mounting: Some(MountingDirection::UpsideDown),
+                video_output: Some(VideoOutput::HDMI),
+            }
+        );
+        assert_eq!(info.mounting.unwrap().pitch_range(), (-250, 900));
+
+        let short = GimbalInfo::from_payload(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00]).unwrap();
+        assert_eq!(short.mounting, None);
+        assert_eq!(short.video_output, None);
+        assert!(GimbalInfo::from_payload(&[0x00, 0x00, 0x00, 0x00]).is_err());
+        let unknown_mode = GimbalInfo::from_payload(&[0x00, 0x00, 0x00, 0x00, 0x07, 0x01]);
+        assert!(matches!(
+            unknown_mode.unwrap_err().downcast_ref::<A8MiniError>(),
+            Some(A8MiniError::MalformedAck(_))
+        ));
+    }
+
+    #[test]
+    fn test_firmware_version_from_payload() {
+        let version = FirmwareVersion::from_payload(&[
+            0x03, 0x02, 0x03, 0x6e, 0x05, 0x01, 0x00, 0x00, 0x0a, 0x02, 0x01, 0x00,
+        ])
+        .unwrap();
+        assert_eq!(version.camera.to_string(), "v3.2.3");
+        assert_eq!(
+            version.gimbal,
+            Version {
+                major: 0,
+                minor: 1,
+                patch: 5,
+            }
+        );
+        assert_eq!(version.zoom.unwrap().to_string(), "v1.2.10");
+        assert!(version.camera > version.gimbal);
+
+        let no_zoom =
+            FirmwareVersion::from_payload(&[0x03, 0x02, 0x03, 0x00, 0x05, 0x01, 0x00, 0x00])
+                .unwrap();
+        assert_eq!(no_zoom.zoom, None);
+        assert!(FirmwareVersion::from_payload(&[0x03, 0x02, 0x03]).is_err());
+    }
+
+    #[test]
+    fn test_storage_status_from_recording_status() {
+        assert_eq!(RecordingStatus::from_u8(4), None);
+        assert_eq!(
+            StorageStatus::from(RecordingStatus::from_u8(1).unwrap()),
+            StorageStatus::Ready
+        );
+        assert_eq!(
+            StorageStatus::from(RecordingStatus::from_u8(2).unwrap()),
+            StorageStatus::NoCard
+        );
+        assert_eq!(
+            StorageStatus::from(RecordingStatus::from_u8(3).unwrap()),
+            StorageStatus::DataLoss
+        );
+    }
 }
@@ -0,0 +1,155 @@
+use crate::control::A8MiniAtittude;
+use crate::decideg::DeciDeg;
+
+/// Sign of pitch angles. The gimbal itself reports pitch positive up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PitchSign {
+    #[default]
+    PositiveUp,
+    PositiveDown,
+}
+
+/// Range yaw angles are expressed in. The gimbal itself uses [-180, 180).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum YawRange {
+    #[default]
+    Signed180,
+    Unsigned360,
+}
+
+/// Angle convention of the integrating application. The default matches the gimbal's own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AngleConvention {
+    pub pitch: PitchSign,
+    pub yaw: YawRange,
+}
+
+/// Gimbal attitude in degrees, in an application's angle convention.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Attitude {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub roll: f32,
+}
+
+impl AngleConvention {
+    /// Converts a yaw/pitch setpoint in this convention to the gimbal's, in decidegrees.
+    pub fn setpoint_to_gimbal(&self, theta_yaw: i16, theta_pitch: i16) -> (i16, i16) {
+        (
+            self.yaw_to_gimbal(theta_yaw),
+            self.pitch_to_gimbal(theta_pitch),
+        )
+    }
+
+    /// Converts a gimbal yaw/pitch to this convention, in decidegrees.
+    pub fn setpoint_from_gimbal(&self, theta_yaw: i16, theta_pitch: i16) -> (i16, i16) {
+        (
+            self.yaw_from_gimbal(theta_yaw),
+            self.pitch_from_gimbal(theta_pitch),
+        )
+    }
+
+    /// Converts a yaw/pitch speed command in this convention to the gimbal's.
+    pub fn rate_to_gimbal(&self, v_yaw: i8, v_pitch: i8) -> (i8, i8) {
+        match self.pitch {
+            PitchSign::PositiveUp => (v_yaw, v_pitch),
+            PitchSign::PositiveDown => (v_yaw, v_pitch.saturating_neg()),
+        }
+    }
+
+    pub fn yaw_to_gimbal(&self, theta_yaw: i16) -> i16 {
+        match self.yaw {
+            YawRange::Signed180 => theta_yaw,
+            YawRange::Unsigned360 => (i32::from(theta_yaw) + 1800).rem_euclid(3600) as i16 - 1800,
+        }
+    }
+
+    pub fn yaw_from_gimbal(&self, theta_yaw: i16) -> i16 {
+        match self.yaw {
+            YawRange::Signed180 => theta_yaw,
+            YawRange::Unsigned360 => i32::from(theta_yaw).rem_euclid(3600) as i16,
+        }
+    }
+
+    pub fn pitch_to_gimbal(&self, theta_pitch: i16) -> i16 {
+        match self.pitch {
+            PitchSign::PositiveUp => theta_pitch,
+            PitchSign::PositiveDown => theta_pitch.saturating_neg(),
+        }
+    }
+
+    pub fn pitch_from_gimbal(&self, theta_pitch: i16) -> i16 {
+        self.pitch_to_gimbal(theta_pitch)
+    }
+
+    /// Converts an attitude reported by the gimbal to this convention. Roll is unchanged.
+    pub fn attitude_from_gimbal(&self, attitude: &A8MiniAtittude) -> A8MiniAtittude {
+        A8MiniAtittude {
+            theta_yaw: self.yaw_from_gimbal(attitude.theta_yaw),
+            theta_pitch: self.pitch_from_gimbal(attitude.theta_pitch),
+            v_pitch: self.pitch_from_gimbal(attitude.v_pitch),
+            ..*attitude
+        }
+    }
+}
+
+impl From<&A8MiniAtittude> for Attitude {
+    fn from(attitude: &A8MiniAtittude) -> Self {
+        Self {
+            yaw: DeciDeg(attitude.theta_yaw).to_degrees(),
+            pitch: DeciDeg(attitude.theta_pitch).to_degrees(),
+            roll: DeciDeg(attitude.theta_roll).to_degrees(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_convention() {
+        let convention = AngleConvention::default();
+        assert_eq!(convention.setpoint_to_gimbal(-900, -455), (-900, -455));
+        assert_eq!(convention.setpoint_from_gimbal(-900, -455), (-900, -455));
+        assert_eq!(convention.rate_to_gimbal(-10, 20), (-10, 20));
+    }
+
+    #[test]
+    fn test_converted_convention() {
+        let convention = AngleConvention {
+            pitch: PitchSign::PositiveDown,
+            yaw: YawRange::Unsigned360,
+        };
+        assert_eq!(convention.setpoint_to_gimbal(2700, 450), (-900, -450));
+        assert_eq!(convention.setpoint_to_gimbal(-100, 0), (-100, 0));
+        assert_eq!(convention.setpoint_from_gimbal(-900, -450), (2700, 450));
+        assert_eq!(convention.rate_to_gimbal(-10, 20), (-10, -20));
+
+        let attitude = A8MiniAtittude {
+            theta_yaw: -100,
+            theta_pitch: 200,
+            theta_roll: 5,
+            v_yaw: 3,
+            v_pitch: 4,
+            v_roll: 0,
+        };
+        let converted = convention.attitude_from_gimbal(&attitude);
+        assert_eq!(
+            Attitude::from(&converted),
+            Attitude {
+                yaw: 350.0,
+                pitch: -20.0,
+                roll: 0.5,
+            }
+        );
+        assert_eq!((converted.v_yaw, converted.v_pitch), (3, -4));
+
+        // Round trip through the gimbal representation.
+        let (theta_yaw, theta_pitch) = convention.setpoint_to_gimbal(3500, -200);
+        assert_eq!(
+            convention.setpoint_from_gimbal(theta_yaw, theta_pitch),
+            (3500, -200)
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+tokio::task_local! {
+    static CURRENT: CorrelationId;
+}
+
+/// Identifies one logical operation, such as a command, a zoom ramp or a download, across log
+/// lines, audit entries and events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CorrelationId(u64);
+
+impl CorrelationId {
+    /// Allocates an ID unique within this process.
+    pub fn generate() -> Self {
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "op-{}", self.0)
+    }
+}
+
+/// ID of the operation the current task is running, if any.
+pub fn current() -> Option<CorrelationId> {
+    CURRENT.try_with(|id| *id).ok()
+}
+
+/// Runs `future` as the operation `id`.
+pub async fn scope<F: Future>(id: CorrelationId, future: F) -> F::Output {
+    CURRENT.scope(id, future).await
+}
+
+/// Runs `future` as part of the current operation, or as a new operation if there is none.
+/// Wrap a sequence of client calls in this to have them share one ID.
+pub async fn correlated<F: Future>(future: F) -> F::Output {
+    match current() {
+        Some(_) => future.await,
+        None => scope(CorrelationId::generate(), future).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_correlated_nesting() {
+        assert_eq!(current(), None);
+
+        let (outer, inner) = correlated(async {
+            let outer = current().unwrap();
+            let inner = correlated(async { current().unwrap() }).await;
+            (outer, inner)
+        })
+        .await;
+        assert_eq!(outer, inner);
+
+        let next = correlated(async { current().unwrap() }).await;
+        assert_ne!(outer, next);
+        assert_eq!(current(), None);
+    }
+
+    #[tokio::test]
+    async fn test_explicit_scope() {
+        let id = CorrelationId::generate();
+        assert_eq!(scope(id, async { current() }).await, Some(id));
+        assert_eq!(id.to_string(), format!("op-{}", id.value()));
+    }
+}
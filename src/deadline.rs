@@ -0,0 +1,98 @@
+use crate::A8MiniError;
+use std::error::Error;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Overall time budget for a composite operation. Every internal command's timeout is capped by
+/// the time remaining, so a stuck step cannot overrun the budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deadline {
+    at: Instant,
+}
+
+impl Deadline {
+    pub fn at(at: Instant) -> Self {
+        Self { at }
+    }
+
+    pub fn after(budget: Duration) -> Self {
+        Self::at(crate::clock::now() + budget)
+    }
+
+    pub fn instant(&self) -> Instant {
+        self.at
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.at.saturating_duration_since(crate::clock::now())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    /// Caps a per-step timeout by the time remaining.
+    pub fn cap(&self, timeout: Duration) -> Duration {
+        timeout.min(self.remaining())
+    }
+
+    pub fn check(&self) -> Result<(), Box<dyn Error>> {
+        if self.is_expired() {
+            return Err(A8MiniError::Timeout.into());
+        }
+        Ok(())
+    }
+}
+
+/// Runs `future` to completion, failing once `deadline` passes. `None` runs it unbounded.
+pub async fn within<T, E, F>(deadline: Option<Deadline>, future: F) -> Result<T, Box<dyn Error>>
+where
+    E: Into<Box<dyn Error>>,
+    F: Future<Output = Result<T, E>>,
+{
+    let Some(deadline) = deadline else {
+        return future.await.map_err(Into::into);
+    };
+
+    deadline.check()?;
+    tokio::time::timeout(deadline.remaining(), future)
+        .await
+        .map_err(|_| A8MiniError::Timeout)?
+        .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadline_cap() {
+        let deadline = Deadline::after(Duration::from_secs(60));
+        assert_eq!(
+            deadline.cap(Duration::from_millis(500)),
+            Duration::from_millis(500)
+        );
+        assert!(deadline.cap(Duration::from_secs(120)) <= Duration::from_secs(60));
+
+        let expired = Deadline::at(Instant::now() - Duration::from_secs(1));
+        assert!(expired.is_expired());
+        assert_eq!(expired.cap(Duration::from_millis(500)), Duration::ZERO);
+        assert!(expired.check().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_within_deadline() {
+        let result = within(Some(Deadline::after(Duration::from_millis(10))), async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok::<_, Box<dyn Error>>(())
+        })
+        .await;
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<A8MiniError>(),
+            Some(A8MiniError::Timeout)
+        ));
+
+        let result = within(None, async { Ok::<_, Box<dyn Error>>(1) }).await;
+        assert_eq!(result.unwrap(), 1);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::A8MiniError;
+use std::error::Error;
+use std::fmt;
+
+/// An angle in tenths of a degree, the resolution of every angle field on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct DeciDeg(pub i16);
+
+impl DeciDeg {
+    pub const MIN: DeciDeg = DeciDeg(i16::MIN);
+    pub const MAX: DeciDeg = DeciDeg(i16::MAX);
+
+    /// Converts degrees, rounding to the nearest tenth with halves away from zero.
+    /// Fails on NaN, infinities and angles outside the i16 range.
+    pub fn from_degrees(degrees: f32) -> Result<Self, Box<dyn Error>> {
+        let tenths = (degrees * 10.0).round();
+        if !tenths.is_finite() || tenths < i16::MIN as f32 || tenths > i16::MAX as f32 {
+            return Err(A8MiniError::InvalidArgument(format!(
+                "{} degrees is not representable in decidegrees.",
+                degrees
+            ))
+            .into());
+        }
+        Ok(DeciDeg(tenths as i16))
+    }
+
+    /// Like `from_degrees`, clamping out-of-range angles and mapping NaN to zero.
+    pub fn saturating_from_degrees(degrees: f32) -> Self {
+        DeciDeg((degrees * 10.0).round() as i16)
+    }
+
+    /// Converts to degrees. Every value converts back to itself through `from_degrees`.
+    pub fn to_degrees(self) -> f32 {
+        self.0 as f32 / 10.0
+    }
+}
+
+impl TryFrom<f32> for DeciDeg {
+    type Error = Box<dyn Error>;
+
+    fn try_from(degrees: f32) -> Result<Self, Self::Error> {
+        DeciDeg::from_degrees(degrees)
+    }
+}
+
+impl From<DeciDeg> for f32 {
+    fn from(angle: DeciDeg) -> f32 {
+        angle.to_degrees()
+    }
+}
+
+impl From<DeciDeg> for i16 {
+    fn from(angle: DeciDeg) -> i16 {
+        angle.0
+    }
+}
+
+impl fmt::Display for DeciDeg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.1}°", self.to_degrees())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounding() {
+        assert_eq!(DeciDeg::from_degrees(12.34).unwrap(), DeciDeg(123));
+        assert_eq!(DeciDeg::from_degrees(12.35).unwrap(), DeciDeg(124));
+        assert_eq!(DeciDeg::from_degrees(-12.35).unwrap(), DeciDeg(-124));
+        assert_eq!(DeciDeg::from_degrees(-0.04).unwrap(), DeciDeg(0));
+    }
+
+    #[test]
+    fn test_checked_conversion() {
+        assert!(DeciDeg::from_degrees(f32::NAN).is_err());
+        assert!(DeciDeg::from_degrees(f32::INFINITY).is_err());
+        assert!(DeciDeg::from_degrees(3276.8).is_err());
+        assert_eq!(DeciDeg::from_degrees(3276.7).unwrap(), DeciDeg::MAX);
+        assert_eq!(DeciDeg::saturating_from_degrees(1e9), DeciDeg::MAX);
+        assert_eq!(DeciDeg::saturating_from_degrees(-1e9), DeciDeg::MIN);
+        assert_eq!(DeciDeg::saturating_from_degrees(f32::NAN), DeciDeg(0));
+    }
+
+    #[test]
+    fn test_lossless_round_trip() {
+        for raw in i16::MIN..=i16::MAX {
+            let angle = DeciDeg(raw);
+            assert_eq!(DeciDeg::try_from(f32::from(angle)).unwrap(), angle);
+        }
+        assert_eq!(DeciDeg(-455).to_string(), "-45.5°");
+    }
+}
@@ -0,0 +1,282 @@
+use crate::transport::Transport;
+use crate::{checksum, clock};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+/// A request's interest in one frame.
+#[derive(Debug)]
+struct Waiter {
+    cmd_id: u8,
+    seq: Option<u16>,
+    since: Instant,
+    sender: oneshot::Sender<(Vec<u8>, Instant)>,
+}
+
+impl Waiter {
+    fn matches(&self, frame: &[u8], received: Instant) -> bool {
+        frame[7] == self.cmd_id
+            && self.seq.is_none_or(|seq| checksum::seq(frame) == seq)
+            && received >= self.since
+    }
+}
+
+/// A consumer of every frame with one CMD_ID, such as a pushed data stream.
+#[derive(Debug)]
+struct Tap {
+    cmd_id: u8,
+    sender: mpsc::Sender<(Vec<u8>, Instant)>,
+}
+
+/// An acknowledgement nobody awaits, such as the ACK of a blind command, to be dropped quietly.
+#[derive(Debug)]
+struct Absorb {
+    cmd_id: u8,
+    seq: u16,
+    since: Instant,
+    until: Instant,
+}
+
+impl Absorb {
+    fn matches(&self, frame: &[u8], received: Instant) -> bool {
+        frame[7] == self.cmd_id
+            && checksum::seq(frame) == self.seq
+            && (self.since..=self.until).contains(&received)
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    waiters: Vec<Waiter>,
+    taps: Vec<Tap>,
+    absorbed: Vec<Absorb>,
+    closed: bool,
+}
+
+/// Hands frames read from the command link by `spawn_reader` to the requests and taps waiting
+/// for them, so concurrent requests never consume each other's responses. A request registers
+/// before its frame is sent, so its response cannot arrive unclaimed; frames nobody waits for
+/// are handed back to the reader.
+#[derive(Debug, Default)]
+pub struct Demux {
+    state: Mutex<State>,
+}
+
+impl Demux {
+    /// Waits for the next frame with `cmd_id` (and `seq`, when given) received at or after
+    /// `since`, delivered with its arrival time. Once the demux is closed the receiver fails at
+    /// once.
+    pub fn register(
+        &self,
+        cmd_id: u8,
+        seq: Option<u16>,
+        since: Instant,
+    ) -> oneshot::Receiver<(Vec<u8>, Instant)> {
+        let (sender, receiver) = oneshot::channel();
+        let mut state = self.state.lock().unwrap();
+        if state.closed {
+            return receiver;
+        }
+        state.waiters.retain(|waiter| !waiter.sender.is_closed());
+        state.waiters.push(Waiter {
+            cmd_id,
+            seq,
+            since,
+            sender,
+        });
+        receiver
+    }
+
+    /// Delivers every frame with `cmd_id` that no request waits for until the receiver is
+    /// dropped, dropping frames while it is full. Once the demux is closed the receiver ends.
+    pub fn tap(&self, cmd_id: u8, capacity: usize) -> mpsc::Receiver<(Vec<u8>, Instant)> {
+        let (sender, receiver) = mpsc::channel(capacity);
+        let mut state = self.state.lock().unwrap();
+        if !state.closed {
+            state.taps.retain(|tap| !tap.sender.is_closed());
+            state.taps.push(Tap { cmd_id, sender });
+        }
+        receiver
+    }
+
+    /// Drops the frame with `cmd_id` and `seq` if it arrives between `since` and `until`, so the
+    /// ACK of a command sent without awaiting it is not handed back as an unclaimed frame.
+    pub fn absorb(&self, cmd_id: u8, seq: u16, since: Instant, until: Instant) {
+        let mut state = self.state.lock().unwrap();
+        if state.closed {
+            return;
+        }
+        state.absorbed.retain(|absorb| absorb.until >= since);
+        state.absorbed.push(Absorb {
+            cmd_id,
+            seq,
+            since,
+            until,
+        });
+    }
+
+    /// Delivers a valid frame to the first request waiting for it, or else to the first tap for
+    /// its CMD_ID. ACKs registered with `absorb` are dropped. Returns the frame if nobody took it.
+    pub fn route(&self, frame: Vec<u8>, received: Instant) -> Option<Vec<u8>> {
+        let mut state = self.state.lock().unwrap();
+        state.waiters.retain(|waiter| !waiter.sender.is_closed());
+        let frame = match state
+            .waiters
+            .iter()
+            .position(|waiter| waiter.matches(&frame, received))
+        {
+            // Fails only if the request gave up since the retain above.
+            Some(index) => match state.waiters.remove(index).sender.send((frame, received)) {
+                Ok(()) => return None,
+                Err((frame, _)) => frame,
+            },
+            None => frame,
+        };
+
+        state.absorbed.retain(|absorb| absorb.until >= received);
+        if let Some(index) = state
+            .absorbed
+            .iter()
+            .position(|absorb| absorb.matches(&frame, received))
+        {
+            state.absorbed.remove(index);
+            return None;
+        }
+
+        state.taps.retain(|tap| !tap.sender.is_closed());
+        let Some(tap) = state.taps.iter().find(|tap| tap.cmd_id == frame[7]) else {
+            return Some(frame);
+        };
+        match tap.sender.try_send((frame, received)) {
+            Err(mpsc::error::TrySendError::Closed((frame, _))) => Some(frame),
+            // A full tap drops the frame rather than holding back the reader.
+            _ => None,
+        }
+    }
+
+    /// Drops every waiting request, tap and absorbed ACK, failing the requests at once rather than leaving
+    /// them to time out. Later requests fail at once as well.
+    pub fn close(&self) {
+        *self.state.lock().unwrap() = State {
+            closed: true,
+            ..State::default()
+        };
+    }
+}
+
+/// Reads the command link in a task of its own, routing every valid frame through `demux` and
+/// sending the frames nobody waits for to `pushed`, dropping them when it is full. Invalid
+/// frames are logged and skipped. The task ends, closing `demux`, when the link closes or fails;
+/// refused datagrams, from a camera that is not up yet, are not failures.
+pub fn spawn_reader(
+    transport: Arc<dyn Transport>,
+    demux: Arc<Demux>,
+    pushed: mpsc::Sender<(Vec<u8>, Instant)>,
+    buffer_size: usize,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut recv_buffer = vec![0; buffer_size];
+        loop {
+            let recv_len = match transport.recv(&mut recv_buffer).await {
+                Ok(0) => {
+                    log_line!("[COMMAND] No bytes received, link closed.");
+                    break;
+                }
+                Ok(recv_len) => recv_len,
+                Err(e) if e.kind() == std::io::ErrorKind::ConnectionRefused => continue,
+                Err(e) => {
+                    log_line!("[COMMAND] Link failed: {}", e);
+                    break;
+                }
+            };
+            let received = clock::now();
+            let frame = &recv_buffer[..recv_len];
+            if let Err(e) = checksum::validate_frame(frame) {
+                log_line!("[COMMAND] Invalid frame ({}): {:?}", e, frame);
+                continue;
+            }
+            if let Some(frame) = demux.route(frame.to_vec(), received) {
+                let _ = pushed.try_send((frame, received));
+            }
+        }
+        demux.close();
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn frame(cmd_id: u8, seq: u16) -> Vec<u8> {
+        let mut frame = vec![0x55, 0x66, 0x02, 0x01, 0x00, 0x00, 0x00, cmd_id, 0x01, 0, 0];
+        checksum::set_seq(&mut frame, seq);
+        frame
+    }
+
+    #[test]
+    fn test_routes_by_cmd_id_and_seq() {
+        let demux = Demux::default();
+        let start = Instant::now();
+        let mut zoom = demux.register(0x18, Some(4), start);
+        let mut attitude = demux.register(0x0d, Some(3), start);
+
+        assert_eq!(demux.route(frame(0x0d, 2), start), Some(frame(0x0d, 2)));
+        assert_eq!(demux.route(frame(0x0d, 3), start), None);
+        assert_eq!(demux.route(frame(0x18, 4), start), None);
+        assert_eq!(attitude.try_recv().unwrap().0, frame(0x0d, 3));
+        assert_eq!(zoom.try_recv().unwrap().0, frame(0x18, 4));
+
+        // Abandoned requests no longer claim frames.
+        drop(demux.register(0x04, None, start));
+        assert!(demux.route(frame(0x04, 9), start).is_some());
+
+        let mut feedback = demux.register(0x0b, None, start);
+        demux.close();
+        assert!(feedback.try_recv().is_err());
+        assert_eq!(
+            demux.register(0x0b, None, start).try_recv(),
+            Err(oneshot::error::TryRecvError::Closed)
+        );
+    }
+
+    #[test]
+    fn test_taps_take_unclaimed_frames() {
+        let demux = Demux::default();
+        let start = Instant::now();
+        let mut feedback = demux.tap(0x0b, 1);
+
+        // Requests come first, then taps; a full tap drops the frame.
+        let mut request = demux.register(0x0b, None, start);
+        assert_eq!(demux.route(frame(0x0b, 0), start), None);
+        assert_eq!(request.try_recv().unwrap().0, frame(0x0b, 0));
+        assert_eq!(demux.route(frame(0x0b, 1), start), None);
+        assert_eq!(demux.route(frame(0x0b, 2), start), None);
+        assert_eq!(feedback.try_recv().unwrap().0, frame(0x0b, 1));
+        assert!(feedback.try_recv().is_err());
+
+        // Frames received before the request was registered are not its response.
+        let mut late = demux.register(0x04, Some(7), start + Duration::from_millis(5));
+        assert!(demux.route(frame(0x04, 7), start).is_some());
+        assert!(late.try_recv().is_err());
+
+        drop(feedback);
+        assert!(demux.route(frame(0x0b, 3), start).is_some());
+
+        // Absorbed ACKs are dropped once, and only within their window.
+        let until = start + Duration::from_millis(10);
+        demux.absorb(0x0e, 5, start, until);
+        demux.absorb(0x0e, 6, start, until);
+        assert_eq!(demux.route(frame(0x0e, 5), start), None);
+        assert!(demux.route(frame(0x0e, 5), start).is_some());
+        assert!(demux
+            .route(frame(0x0e, 6), until + Duration::from_millis(1))
+            .is_some());
+
+        let mut attitude = demux.tap(0x0d, 1);
+        demux.close();
+        assert!(attitude.try_recv().is_err());
+        assert!(demux.tap(0x0d, 1).try_recv().is_err());
+    }
+}
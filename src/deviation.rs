@@ -0,0 +1,128 @@
+use std::time::{Duration, Instant};
+
+/// When to alert on the gimbal not following its angle setpoint: the attitude has been more than
+/// `threshold` decidegrees off on either axis for at least `duration`. `duration` should cover the
+/// time the gimbal needs to travel to a new setpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviationPolicy {
+    pub threshold: u16,
+    pub duration: Duration,
+}
+
+impl DeviationPolicy {
+    pub fn new(threshold: u16, duration: Duration) -> Self {
+        Self {
+            threshold,
+            duration,
+        }
+    }
+}
+
+/// A persistent deviation from the setpoint, in the gimbal's decidegrees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deviation {
+    pub setpoint: (i16, i16),
+    pub attitude: (i16, i16),
+    /// How long the attitude has been off the setpoint.
+    pub duration: Duration,
+}
+
+/// Compares telemetry with the last angle setpoint sent, to catch a stalled motor, a collision or
+/// an obstruction. Alerts once per excursion; the attitude coming back within the threshold or a
+/// new motion command re-arms it. Speed commands leave nothing to compare against until the next
+/// angle setpoint.
+#[derive(Debug, Clone)]
+pub struct DeviationMonitor {
+    policy: DeviationPolicy,
+    setpoint: Option<(i16, i16)>,
+    exceeded_since: Option<Instant>,
+    alerted: bool,
+}
+
+impl DeviationMonitor {
+    pub fn new(policy: DeviationPolicy) -> Self {
+        Self {
+            policy,
+            setpoint: None,
+            exceeded_since: None,
+            alerted: false,
+        }
+    }
+
+    pub fn policy(&self) -> DeviationPolicy {
+        self.policy
+    }
+
+    /// Records a motion command, with its angle setpoint if it has one.
+    pub fn command(&mut self, setpoint: Option<(i16, i16)>) {
+        self.setpoint = setpoint;
+        self.exceeded_since = None;
+        self.alerted = false;
+    }
+
+    /// Checks an attitude sample, returning the deviation if it has just lasted long enough.
+    pub fn observe(&mut self, theta_yaw: i16, theta_pitch: i16, now: Instant) -> Option<Deviation> {
+        let (yaw, pitch) = self.setpoint?;
+        let yaw_error = (theta_yaw as i32 - yaw as i32 + 1800).rem_euclid(3600) - 1800;
+        let pitch_error = theta_pitch as i32 - pitch as i32;
+        if yaw_error.unsigned_abs().max(pitch_error.unsigned_abs()) <= self.policy.threshold as u32
+        {
+            self.exceeded_since = None;
+            self.alerted = false;
+            return None;
+        }
+
+        let since = *self.exceeded_since.get_or_insert(now);
+        let duration = now.saturating_duration_since(since);
+        if self.alerted || duration < self.policy.duration {
+            return None;
+        }
+        self.alerted = true;
+        Some(Deviation {
+            setpoint: (yaw, pitch),
+            attitude: (theta_yaw, theta_pitch),
+            duration,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deviation_monitor() {
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let mut monitor =
+            DeviationMonitor::new(DeviationPolicy::new(50, Duration::from_millis(500)));
+        assert_eq!(monitor.observe(900, 0, at(0)), None);
+
+        monitor.command(Some((900, -300)));
+        assert_eq!(monitor.observe(0, 0, at(0)), None);
+        assert_eq!(monitor.observe(400, -100, at(300)), None);
+        assert_eq!(
+            monitor.observe(400, -100, at(600)),
+            Some(Deviation {
+                setpoint: (900, -300),
+                attitude: (400, -100),
+                duration: Duration::from_millis(600),
+            })
+        );
+        // Once per excursion.
+        assert_eq!(monitor.observe(400, -100, at(1200)), None);
+
+        // Back within the threshold re-arms it.
+        assert_eq!(monitor.observe(860, -280, at(1300)), None);
+        assert_eq!(monitor.observe(400, -100, at(1400)), None);
+        assert!(monitor.observe(400, -100, at(1900)).is_some());
+
+        // Yaw error wraps around.
+        monitor.command(Some((1790, 0)));
+        assert_eq!(monitor.observe(-1790, 0, at(2000)), None);
+        assert_eq!(monitor.observe(-1790, 0, at(3000)), None);
+
+        monitor.command(None);
+        assert_eq!(monitor.observe(0, 0, at(4000)), None);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::audit::AuditOutcome;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Errors returned by the command, HTTP and connection APIs.
+#[derive(Debug)]
+pub enum A8MiniError {
+    /// No response arrived in time, or a deadline passed.
+    Timeout,
+    /// The socket sent or received zero bytes.
+    SocketClosed,
+    /// A received frame failed its CRC16 check.
+    ChecksumMismatch {
+        expected: u16,
+        received: u16,
+    },
+    /// A received frame is not a well-formed SDK frame.
+    MalformedAck(String),
+    /// An argument was out of range or otherwise unusable.
+    InvalidArgument(String),
+    /// The command was refused by client-side policy: interlock, arbitration, soft limits,
+    /// circuit breaker, restricted handle or dry run.
+    Rejected(String),
+    /// The command was deliberately not transmitted: a setpoint unchanged since the last
+    /// acknowledged one or superseded by a newer one, a command skipped by middleware or held in
+    /// the offline queue. The audit outcome says which.
+    NotSent(AuditOutcome),
+    Io(io::Error),
+    Http(reqwest::Error),
+    Other(String),
+}
+
+impl fmt::Display for A8MiniError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            A8MiniError::Timeout => write!(f, "Timed out waiting for the camera."),
+            A8MiniError::SocketClosed => write!(f, "Socket closed, no bytes transferred."),
+            A8MiniError::ChecksumMismatch { expected, received } => write!(
+                f,
+                "Checksum mismatch: expected {:#06x}, received {:#06x}.",
+                expected, received
+            ),
+            A8MiniError::MalformedAck(reason) => write!(f, "Malformed ACK: {}", reason),
+            A8MiniError::InvalidArgument(reason) => write!(f, "Invalid argument: {}", reason),
+            A8MiniError::Rejected(reason) => write!(f, "{}", reason),
+            A8MiniError::NotSent(AuditOutcome::Suppressed) => {
+                write!(f, "Setpoint unchanged, command not sent.")
+            }
+            A8MiniError::NotSent(AuditOutcome::Superseded) => {
+                write!(f, "Setpoint superseded by a newer one, command not sent.")
+            }
+            A8MiniError::NotSent(AuditOutcome::Skipped) => {
+                write!(f, "Skipped by middleware, command not sent.")
+            }
+            A8MiniError::NotSent(AuditOutcome::Queued) => {
+                write!(f, "Command queued, not sent yet.")
+            }
+            A8MiniError::NotSent(outcome) => write!(f, "Command not sent: {:?}.", outcome),
+            A8MiniError::Io(e) => write!(f, "I/O error: {}", e),
+            A8MiniError::Http(e) => write!(f, "HTTP error: {}", e),
+            A8MiniError::Other(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl Error for A8MiniError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            A8MiniError::Io(e) => Some(e),
+            A8MiniError::Http(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for A8MiniError {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::TimedOut => A8MiniError::Timeout,
+            _ => A8MiniError::Io(e),
+        }
+    }
+}
+
+impl From<reqwest::Error> for A8MiniError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            return A8MiniError::Timeout;
+        }
+        A8MiniError::Http(e)
+    }
+}
+
+impl From<tokio::time::error::Elapsed> for A8MiniError {
+    fn from(_: tokio::time::error::Elapsed) -> Self {
+        A8MiniError::Timeout
+    }
+}
+
+/// Recovers the typed error from the boxed errors used internally. Untyped errors become `Other`.
+impl From<Box<dyn Error>> for A8MiniError {
+    fn from(e: Box<dyn Error>) -> Self {
+        let e = match e.downcast::<A8MiniError>() {
+            Ok(e) => return *e,
+            Err(e) => e,
+        };
+        let e = match e.downcast::<io::Error>() {
+            Ok(e) => return (*e).into(),
+            Err(e) => e,
+        };
+        let e = match e.downcast::<reqwest::Error>() {
+            Ok(e) => return (*e).into(),
+            Err(e) => e,
+        };
+        if e.is::<tokio::time::error::Elapsed>() {
+            return A8MiniError::Timeout;
+        }
+        A8MiniError::Other(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_boxed() {
+        let typed: Box<dyn Error> = A8MiniError::Rejected("Motion is disarmed.".into()).into();
+        assert!(matches!(
+            A8MiniError::from(typed),
+            A8MiniError::Rejected(reason) if reason == "Motion is disarmed."
+        ));
+
+        let timed_out: Box<dyn Error> = io::Error::from(io::ErrorKind::TimedOut).into();
+        assert!(matches!(A8MiniError::from(timed_out), A8MiniError::Timeout));
+
+        let io: Box<dyn Error> = io::Error::from(io::ErrorKind::AddrInUse).into();
+        assert!(matches!(A8MiniError::from(io), A8MiniError::Io(_)));
+
+        let untyped: Box<dyn Error> = "Deadline exceeded.".into();
+        assert_eq!(A8MiniError::from(untyped).to_string(), "Deadline exceeded.");
+    }
+
+    #[tokio::test]
+    async fn test_from_elapsed() {
+        let elapsed = tokio::time::timeout(std::time::Duration::ZERO, std::future::pending::<()>())
+            .await
+            .unwrap_err();
+        let boxed: Box<dyn Error> = elapsed.into();
+        assert!(matches!(A8MiniError::from(boxed), A8MiniError::Timeout));
+    }
+}
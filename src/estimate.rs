@@ -0,0 +1,166 @@
+use crate::constants;
+use crate::control::A8MiniAtittude;
+use crate::decideg::DeciDeg;
+use std::time::{Duration, Instant};
+
+/// Attitude extrapolated from the last telemetry sample, in degrees, gimbal frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttitudeEstimate {
+    pub yaw: f32,
+    pub pitch: f32,
+    /// Age of the telemetry sample the estimate is based on.
+    pub age: Duration,
+}
+
+/// Dead-reckons the attitude between telemetry updates, so fast control loops have a usable
+/// estimate when attitude arrives at a lower rate. Each sample resets the estimate and moves it
+/// on at the angular velocity the gimbal reported; a speed command sent since takes over that
+/// velocity, and an angle setpoint or stop holds the estimate until the next sample.
+#[derive(Debug, Clone)]
+pub struct AttitudeEstimator {
+    degrees_per_unit: f32,
+    horizon: Duration,
+    yaw: f32,
+    pitch: f32,
+    rates: (f32, f32),
+    last_update: Option<Instant>,
+    sampled_at: Option<Instant>,
+}
+
+impl AttitudeEstimator {
+    /// `degrees_per_unit` converts speed command units (-100..100) to degrees per second.
+    /// Extrapolation stops `horizon` after the last sample.
+    pub fn new(degrees_per_unit: f32, horizon: Duration) -> Self {
+        Self {
+            degrees_per_unit,
+            horizon,
+            yaw: 0.0,
+            pitch: 0.0,
+            rates: (0.0, 0.0),
+            last_update: None,
+            sampled_at: None,
+        }
+    }
+
+    /// Resets the estimate to a telemetry sample.
+    pub fn observe(&mut self, attitude: &A8MiniAtittude, at: Instant) {
+        self.yaw = DeciDeg(attitude.theta_yaw).to_degrees();
+        self.pitch = DeciDeg(attitude.theta_pitch).to_degrees();
+        self.rates = (
+            DeciDeg(attitude.v_yaw).to_degrees(),
+            DeciDeg(attitude.v_pitch).to_degrees(),
+        );
+        self.last_update = Some(at);
+        self.sampled_at = Some(at);
+    }
+
+    /// Records a speed command. Motion up to `at` is extrapolated at the previous rates.
+    pub fn command_rate(&mut self, v_yaw: i8, v_pitch: i8, at: Instant) {
+        self.advance(at);
+        self.rates = (
+            v_yaw as f32 * self.degrees_per_unit,
+            v_pitch as f32 * self.degrees_per_unit,
+        );
+    }
+
+    /// Records an angle setpoint or stop, after which the rate towards the target is unknown.
+    pub fn hold(&mut self, at: Instant) {
+        self.advance(at);
+        self.rates = (0.0, 0.0);
+    }
+
+    /// The estimate at `now`, or `None` before the first sample.
+    pub fn estimate(&self, now: Instant) -> Option<AttitudeEstimate> {
+        let sampled_at = self.sampled_at?;
+        let mut estimator = self.clone();
+        estimator.advance(now);
+        Some(AttitudeEstimate {
+            yaw: estimator.yaw,
+            pitch: estimator.pitch,
+            age: now.saturating_duration_since(sampled_at),
+        })
+    }
+
+    fn advance(&mut self, now: Instant) {
+        let (Some(last_update), Some(sampled_at)) = (self.last_update, self.sampled_at) else {
+            return;
+        };
+        let until = now.min(sampled_at + self.horizon);
+        let elapsed = until.saturating_duration_since(last_update).as_secs_f32();
+        self.yaw += self.rates.0 * elapsed;
+        self.pitch += self.rates.1 * elapsed;
+        let (yaw_min, yaw_max) = constants::GIMBAL_YAW_RANGE;
+        let (pitch_min, pitch_max) = constants::GIMBAL_PITCH_RANGE;
+        self.yaw = self.yaw.clamp(yaw_min, yaw_max);
+        self.pitch = self.pitch.clamp(pitch_min, pitch_max);
+        self.last_update = Some(last_update.max(until));
+    }
+}
+
+impl Default for AttitudeEstimator {
+    fn default() -> Self {
+        Self::new(
+            constants::RATE_COMMAND_DEGREES_PER_UNIT,
+            constants::ESTIMATE_HORIZON,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attitude(theta_yaw: i16, theta_pitch: i16, v_yaw: i16, v_pitch: i16) -> A8MiniAtittude {
+        A8MiniAtittude {
+            theta_yaw,
+            theta_pitch,
+            theta_roll: 0,
+            v_yaw,
+            v_pitch,
+            v_roll: 0,
+        }
+    }
+
+    #[test]
+    fn test_extrapolates_reported_velocity() {
+        let start = Instant::now();
+        let mut estimator = AttitudeEstimator::new(1.0, Duration::from_millis(500));
+        assert_eq!(estimator.estimate(start), None);
+
+        estimator.observe(&attitude(100, -50, 200, -100), start);
+        let estimate = estimator
+            .estimate(start + Duration::from_millis(100))
+            .unwrap();
+        assert_eq!((estimate.yaw, estimate.pitch), (12.0, -6.0));
+        assert_eq!(estimate.age, Duration::from_millis(100));
+
+        // Extrapolation stops at the horizon.
+        let estimate = estimator.estimate(start + Duration::from_secs(5)).unwrap();
+        assert_eq!((estimate.yaw, estimate.pitch), (20.0, -10.0));
+    }
+
+    #[test]
+    fn test_commands_override_velocity() {
+        let start = Instant::now();
+        let mut estimator = AttitudeEstimator::new(0.5, Duration::from_secs(1));
+        estimator.observe(&attitude(0, 0, 0, 0), start);
+        estimator.command_rate(40, -20, start + Duration::from_millis(100));
+        let estimate = estimator
+            .estimate(start + Duration::from_millis(300))
+            .unwrap();
+        assert_eq!((estimate.yaw, estimate.pitch), (4.0, -2.0));
+
+        estimator.hold(start + Duration::from_millis(300));
+        let estimate = estimator
+            .estimate(start + Duration::from_millis(600))
+            .unwrap();
+        assert_eq!((estimate.yaw, estimate.pitch), (4.0, -2.0));
+
+        // A new sample replaces the estimate.
+        estimator.observe(&attitude(30, 0, 0, 0), start + Duration::from_millis(700));
+        let estimate = estimator
+            .estimate(start + Duration::from_millis(700))
+            .unwrap();
+        assert_eq!((estimate.yaw, estimate.age), (3.0, Duration::ZERO));
+    }
+}
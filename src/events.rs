@@ -0,0 +1,47 @@
+use crate::control::StorageStatus;
+use crate::correlation::CorrelationId;
+use crate::deviation::Deviation;
+use crate::recording::RecordingSegment;
+
+/// Events emitted by an `A8Mini` client. Subscribe with `A8Mini::subscribe_events`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum A8MiniEvent {
+    /// An angle setpoint was outside the soft limits and the nearest legal angle was sent instead.
+    LimitClamped {
+        requested: (i16, i16),
+        issued: (i16, i16),
+    },
+    /// The TF card status reported by the camera changed.
+    StorageChanged {
+        previous: StorageStatus,
+        current: StorageStatus,
+    },
+    /// A recording segment was stopped by segment rotation.
+    RecordingSegmentClosed(RecordingSegment),
+    /// The circuit breaker tripped after repeated timeouts. Commands fail fast until a probe succeeds.
+    LinkDown,
+    /// A probe succeeded after the circuit breaker tripped.
+    LinkUp,
+    /// The gimbal answered after a period of silence and the boot defaults were applied.
+    GimbalBooted,
+    /// `emergency_stop` was called. Motion stays disabled until it is re-armed.
+    EmergencyStop,
+    /// The camera sent a frame no request was waiting for, e.g. a pushed data stream or status
+    /// frame. Carries the frame's CMD_ID and DATA.
+    UnsolicitedFrame { cmd_id: u8, data: Vec<u8> },
+    /// Attitude telemetry stayed off the angle setpoint for longer than the deviation policy
+    /// allows. See `A8Mini::set_deviation_policy`.
+    AttitudeDeviation(Deviation),
+    /// `run_keepalive` got no answer to several pings in a row.
+    CameraUnresponsive,
+    /// The camera answered a keepalive again after being unresponsive.
+    CameraResponsive,
+}
+
+/// An event and the operation that caused it. Events raised outside any operation, such as
+/// storage changes seen by a background monitor, have no correlation ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventRecord {
+    pub correlation: Option<CorrelationId>,
+    pub event: A8MiniEvent,
+}
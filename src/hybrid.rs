@@ -0,0 +1,108 @@
+use crate::compensation::to_decidegrees;
+use crate::constants;
+use std::time::Instant;
+
+/// Integrates rate commands (degrees per second, gimbal frame) into an absolute angle setpoint.
+/// The gimbal then holds the setpoint with its own angle loop, so a long manual session does
+/// not drift the way open-loop speed commands do, and returning the stick to the same
+/// accumulated input returns the camera to the same angle.
+#[derive(Debug, Clone)]
+pub struct RateIntegrator {
+    yaw: f32,
+    pitch: f32,
+    rates: (f32, f32),
+    last_update: Instant,
+}
+
+impl RateIntegrator {
+    /// Starts from the given attitude in degrees, at rest.
+    pub fn new(yaw: f32, pitch: f32, now: Instant) -> Self {
+        let mut integrator = Self {
+            yaw,
+            pitch,
+            rates: (0.0, 0.0),
+            last_update: now,
+        };
+        integrator.clamp();
+        integrator
+    }
+
+    /// Changes the commanded rates. Motion up to `now` is integrated at the previous rates.
+    pub fn set_rates(&mut self, yaw_rate: f32, pitch_rate: f32, now: Instant) {
+        self.advance(now);
+        self.rates = (yaw_rate, pitch_rate);
+    }
+
+    pub fn rates(&self) -> (f32, f32) {
+        self.rates
+    }
+
+    /// Integrates the current rates up to `now` and returns the setpoint in degrees, clamped to
+    /// the gimbal's range so holding the stick against a limit does not wind up.
+    pub fn advance(&mut self, now: Instant) -> (f32, f32) {
+        let elapsed = now
+            .saturating_duration_since(self.last_update)
+            .as_secs_f32();
+        self.last_update = now;
+        self.yaw += self.rates.0 * elapsed;
+        self.pitch += self.rates.1 * elapsed;
+        self.clamp();
+        (self.yaw, self.pitch)
+    }
+
+    /// The current setpoint in decidegrees, as sent on the wire.
+    pub fn setpoint(&self) -> (i16, i16) {
+        (to_decidegrees(self.yaw), to_decidegrees(self.pitch))
+    }
+
+    fn clamp(&mut self) {
+        let (yaw_min, yaw_max) = constants::GIMBAL_YAW_RANGE;
+        let (pitch_min, pitch_max) = constants::GIMBAL_PITCH_RANGE;
+        self.yaw = self.yaw.clamp(yaw_min, yaw_max);
+        self.pitch = self.pitch.clamp(pitch_min, pitch_max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_rate_integration() {
+        let start = Instant::now();
+        let mut integrator = RateIntegrator::new(10.0, 0.0, start);
+        assert_eq!(
+            integrator.advance(start + Duration::from_secs(1)),
+            (10.0, 0.0)
+        );
+
+        integrator.set_rates(20.0, -10.0, start + Duration::from_secs(1));
+        integrator.set_rates(-20.0, 10.0, start + Duration::from_millis(1500));
+        assert_eq!(integrator.setpoint(), (200, -50));
+
+        // Reversing the stick for as long returns to the starting angle.
+        assert_eq!(
+            integrator.advance(start + Duration::from_secs(2)),
+            (10.0, 0.0)
+        );
+        assert_eq!(integrator.rates(), (-20.0, 10.0));
+    }
+
+    #[test]
+    fn test_rate_integration_does_not_wind_up() {
+        let start = Instant::now();
+        let mut integrator = RateIntegrator::new(130.0, 20.0, start);
+        integrator.set_rates(50.0, 50.0, start);
+        assert_eq!(
+            integrator.advance(start + Duration::from_secs(10)),
+            (135.0, 25.0)
+        );
+
+        integrator.set_rates(-10.0, -10.0, start + Duration::from_secs(10));
+        assert_eq!(
+            integrator.advance(start + Duration::from_secs(11)),
+            (125.0, 15.0)
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::time::{Duration, Instant};
+
+/// What to do once the gimbal has gone without motion commands for the idle timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleAction {
+    /// Stop any rotation still running from the last speed command.
+    StopRotation,
+    /// Return the gimbal to center.
+    Recenter,
+    /// Move to a fixed yaw and pitch, in decidegrees.
+    Park { yaw: i16, pitch: i16 },
+}
+
+/// Safety net for a controlling application that crashes or hangs mid-motion: after `timeout`
+/// without a motion command, `action` is applied once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdlePolicy {
+    pub timeout: Duration,
+    pub action: IdleAction,
+}
+
+impl IdlePolicy {
+    pub fn new(timeout: Duration, action: IdleAction) -> Self {
+        Self { timeout, action }
+    }
+}
+
+/// Tracks motion activity against an idle policy. The action fires once per idle period; the
+/// next motion command re-arms it.
+#[derive(Debug, Clone)]
+pub struct IdleMonitor {
+    policy: IdlePolicy,
+    last_activity: Instant,
+    fired: bool,
+}
+
+impl IdleMonitor {
+    pub fn new(policy: IdlePolicy, now: Instant) -> Self {
+        Self {
+            policy,
+            last_activity: now,
+            fired: false,
+        }
+    }
+
+    pub fn policy(&self) -> IdlePolicy {
+        self.policy
+    }
+
+    pub fn record_activity(&mut self, now: Instant) {
+        self.last_activity = now;
+        self.fired = false;
+    }
+
+    /// Returns the action if the idle timeout has just elapsed.
+    pub fn expire(&mut self, now: Instant) -> Option<IdleAction> {
+        if self.fired || now.saturating_duration_since(self.last_activity) < self.policy.timeout {
+            return None;
+        }
+        self.fired = true;
+        Some(self.policy.action)
+    }
+
+    /// Marks the current idle period as handled again, after the idle action's own command was
+    /// recorded as activity.
+    pub fn hold(&mut self) {
+        self.fired = true;
+    }
+
+    /// Time until the action is due, or `None` if it already fired for this idle period.
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        (!self.fired)
+            .then(|| (self.last_activity + self.policy.timeout).saturating_duration_since(now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_monitor() {
+        let start = Instant::now();
+        let policy = IdlePolicy::new(Duration::from_secs(5), IdleAction::Recenter);
+        let mut monitor = IdleMonitor::new(policy, start);
+
+        assert_eq!(monitor.expire(start + Duration::from_secs(4)), None);
+        monitor.record_activity(start + Duration::from_secs(4));
+        assert_eq!(
+            monitor.remaining(start + Duration::from_secs(6)),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(monitor.expire(start + Duration::from_secs(8)), None);
+        assert_eq!(
+            monitor.expire(start + Duration::from_secs(9)),
+            Some(IdleAction::Recenter)
+        );
+
+        // Fires once per idle period.
+        assert_eq!(monitor.expire(start + Duration::from_secs(20)), None);
+        assert_eq!(monitor.remaining(start + Duration::from_secs(20)), None);
+
+        monitor.record_activity(start + Duration::from_secs(21));
+        monitor.hold();
+        assert_eq!(monitor.expire(start + Duration::from_secs(30)), None);
+        monitor.record_activity(start + Duration::from_secs(31));
+        assert_eq!(
+            monitor.expire(start + Duration::from_secs(36)),
+            Some(IdleAction::Recenter)
+        );
+    }
+}
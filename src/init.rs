@@ -0,0 +1,111 @@
+use crate::control::{A8MiniComplexCommand, A8MiniSimpleCommand};
+use std::time::Duration;
+
+/// A single step of an initialization sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitStep {
+    Simple(A8MiniSimpleCommand),
+    Complex(A8MiniComplexCommand),
+    Delay(Duration),
+}
+
+/// Ordered list of steps run after connecting (and after every reconnect).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InitSequence {
+    steps: Vec<InitStep>,
+}
+
+impl InitSequence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn then(mut self, step: InitStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    pub fn then_simple(self, command: A8MiniSimpleCommand) -> Self {
+        self.then(InitStep::Simple(command))
+    }
+
+    pub fn then_complex(self, command: A8MiniComplexCommand) -> Self {
+        self.then(InitStep::Complex(command))
+    }
+
+    pub fn then_delay(self, delay: Duration) -> Self {
+        self.then(InitStep::Delay(delay))
+    }
+
+    pub fn steps(&self) -> &[InitStep] {
+        &self.steps
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+/// Outcome of a single init step. Errors are kept as strings so reports can be cloned and logged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitStepResult {
+    pub step: InitStep,
+    pub result: Result<(), String>,
+}
+
+/// Report of which init steps succeeded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InitReport {
+    pub steps: Vec<InitStepResult>,
+}
+
+impl InitReport {
+    pub fn all_succeeded(&self) -> bool {
+        self.steps.iter().all(|step| step.result.is_ok())
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &InitStepResult> {
+        self.steps.iter().filter(|step| step.result.is_err())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_sequence_order() {
+        let sequence = InitSequence::new()
+            .then_simple(A8MiniSimpleCommand::AutoCenter)
+            .then_delay(Duration::from_millis(500))
+            .then_simple(A8MiniSimpleCommand::SetFollowMode);
+
+        assert_eq!(
+            sequence.steps(),
+            &[
+                InitStep::Simple(A8MiniSimpleCommand::AutoCenter),
+                InitStep::Delay(Duration::from_millis(500)),
+                InitStep::Simple(A8MiniSimpleCommand::SetFollowMode),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_init_report_failures() {
+        let report = InitReport {
+            steps: vec![
+                InitStepResult {
+                    step: InitStep::Simple(A8MiniSimpleCommand::AutoCenter),
+                    result: Ok(()),
+                },
+                InitStepResult {
+                    step: InitStep::Simple(A8MiniSimpleCommand::SetFollowMode),
+                    result: Err("No bytes sent.".to_string()),
+                },
+            ],
+        };
+
+        assert!(!report.all_succeeded());
+        assert_eq!(report.failures().count(), 1);
+    }
+}
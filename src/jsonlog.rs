@@ -0,0 +1,249 @@
+use crate::arbitration::CommandSource;
+use crate::audit::{AuditEntry, AuditOutcome};
+use crate::correlation::CorrelationId;
+use crate::events::{A8MiniEvent, EventRecord};
+use serde_json::{json, Value};
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How the wait for a command's response ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResponseOutcome {
+    Response,
+    /// Arrived after the response timeout, within the grace window.
+    LateResponse,
+    Timeout,
+    Failed(String),
+}
+
+/// Writes protocol activity as JSON lines with stable field names, for log aggregation.
+///
+/// Every record has `type` (`"command"`, `"response"` or `"event"`), `time_us` (microseconds
+/// since the Unix epoch) and `correlation` (`"op-N"` or null). Commands add `source`, `cmd_id`,
+/// `seq`, `bytes` (hex), `outcome` and `error`; responses add `cmd_id`, `seq`, `outcome`, `rtt_us`
+/// (null unless answered) and `error`; events add `event` (snake_case name) and `details`.
+pub struct JsonLog {
+    writer: Box<dyn Write + Send>,
+}
+
+impl JsonLog {
+    pub fn new<W: Write + Send + 'static>(writer: W) -> Self {
+        Self {
+            writer: Box::new(writer),
+        }
+    }
+
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+
+    pub fn record_command(&mut self, entry: &AuditEntry) {
+        let (outcome, error) = match &entry.outcome {
+            AuditOutcome::Sent => ("sent", None),
+            AuditOutcome::DryRun => ("dry_run", None),
+            AuditOutcome::Queued => ("queued", None),
+            AuditOutcome::Superseded => ("superseded", None),
+            AuditOutcome::Suppressed => ("suppressed", None),
+            AuditOutcome::Skipped => ("skipped", None),
+            AuditOutcome::Failed(e) => ("failed", Some(e.as_str())),
+        };
+        let bytes = &entry.bytes;
+        self.write(json!({
+            "type": "command",
+            "time_us": unix_us(entry.time),
+            "correlation": correlation(entry.correlation),
+            "source": entry.source.map(|source| match source {
+                CommandSource::Operator => "operator",
+                CommandSource::Autopilot => "autopilot",
+            }),
+            "cmd_id": bytes.get(7),
+            "seq": (bytes.len() > 6).then(|| u16::from_le_bytes([bytes[5], bytes[6]])),
+            "bytes": bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<String>(),
+            "outcome": outcome,
+            "error": error,
+        }));
+    }
+
+    pub fn record_response(
+        &mut self,
+        correlation_id: Option<CorrelationId>,
+        cmd_id: u8,
+        seq: u16,
+        rtt: Duration,
+        outcome: &ResponseOutcome,
+    ) {
+        let rtt_us = rtt.as_micros() as u64;
+        let (outcome, rtt_us, error) = match outcome {
+            ResponseOutcome::Response => ("response", Some(rtt_us), None),
+            ResponseOutcome::LateResponse => ("late_response", Some(rtt_us), None),
+            ResponseOutcome::Timeout => ("timeout", None, None),
+            ResponseOutcome::Failed(e) => ("failed", None, Some(e.as_str())),
+        };
+        self.write(json!({
+            "type": "response",
+            "time_us": unix_us(SystemTime::now()),
+            "correlation": correlation(correlation_id),
+            "cmd_id": cmd_id,
+            "seq": seq,
+            "outcome": outcome,
+            "rtt_us": rtt_us,
+            "error": error,
+        }));
+    }
+
+    pub fn record_event(&mut self, record: &EventRecord) {
+        let (event, details) = match &record.event {
+            A8MiniEvent::LimitClamped { requested, issued } => (
+                "limit_clamped",
+                json!({ "requested": [requested.0, requested.1], "issued": [issued.0, issued.1] }),
+            ),
+            A8MiniEvent::StorageChanged { previous, current } => (
+                "storage_changed",
+                json!({
+                    "previous": format!("{:?}", previous),
+                    "current": format!("{:?}", current),
+                }),
+            ),
+            A8MiniEvent::RecordingSegmentClosed(segment) => (
+                "recording_segment_closed",
+                json!({
+                    "index": segment.index,
+                    "started_us": unix_us(segment.started),
+                    "stopped_us": unix_us(segment.stopped),
+                }),
+            ),
+            A8MiniEvent::LinkDown => ("link_down", Value::Null),
+            A8MiniEvent::LinkUp => ("link_up", Value::Null),
+            A8MiniEvent::GimbalBooted => ("gimbal_booted", Value::Null),
+            A8MiniEvent::EmergencyStop => ("emergency_stop", Value::Null),
+            A8MiniEvent::UnsolicitedFrame { cmd_id, data } => (
+                "unsolicited_frame",
+                json!({
+                    "cmd_id": cmd_id,
+                    "data": data.iter().map(|byte| format!("{:02x}", byte)).collect::<String>(),
+                }),
+            ),
+            A8MiniEvent::CameraUnresponsive => ("camera_unresponsive", Value::Null),
+            A8MiniEvent::CameraResponsive => ("camera_responsive", Value::Null),
+            A8MiniEvent::AttitudeDeviation(deviation) => (
+                "attitude_deviation",
+                json!({
+                    "setpoint": [deviation.setpoint.0, deviation.setpoint.1],
+                    "attitude": [deviation.attitude.0, deviation.attitude.1],
+                    "duration_ms": deviation.duration.as_millis() as u64,
+                }),
+            ),
+        };
+        self.write(json!({
+            "type": "event",
+            "time_us": unix_us(SystemTime::now()),
+            "correlation": correlation(record.correlation),
+            "event": event,
+            "details": details,
+        }));
+    }
+
+    fn write(&mut self, record: Value) {
+        // Logging must never fail a command.
+        let _ = writeln!(self.writer, "{}", record);
+        let _ = self.writer.flush();
+    }
+}
+
+impl fmt::Debug for JsonLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonLog").finish_non_exhaustive()
+    }
+}
+
+fn unix_us(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_micros() as u64)
+}
+
+fn correlation(id: Option<CorrelationId>) -> Option<String> {
+    id.map(|id| id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_records() {
+        let buffer = Buffer::default();
+        let mut log = JsonLog::new(buffer.clone());
+        log.record_command(&AuditEntry {
+            time: UNIX_EPOCH + std::time::Duration::from_micros(1_500),
+            bytes: vec![
+                0x55, 0x66, 0x01, 0x01, 0x00, 0x02, 0x00, 0x04, 0x01, 0xaa, 0xbb,
+            ],
+            correlation: None,
+            source: Some(CommandSource::Operator),
+            outcome: AuditOutcome::Failed("No bytes sent.".into()),
+        });
+        log.record_response(
+            Some(CorrelationId::generate()),
+            0x0d,
+            7,
+            Duration::from_micros(2_500),
+            &ResponseOutcome::LateResponse,
+        );
+        log.record_response(None, 0x0e, 8, Duration::ZERO, &ResponseOutcome::Timeout);
+        log.record_event(&EventRecord {
+            correlation: None,
+            event: A8MiniEvent::LimitClamped {
+                requested: (1500, 0),
+                issued: (1350, 0),
+            },
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 4);
+
+        assert_eq!(lines[0]["type"], "command");
+        assert_eq!(lines[0]["time_us"], 1_500);
+        assert_eq!(lines[0]["source"], "operator");
+        assert_eq!(lines[0]["cmd_id"], 4);
+        assert_eq!(lines[0]["seq"], 2);
+        assert_eq!(lines[0]["bytes"], "556601010002000401aabb");
+        assert_eq!(lines[0]["outcome"], "failed");
+        assert_eq!(lines[0]["error"], "No bytes sent.");
+        assert_eq!(lines[0]["correlation"], Value::Null);
+
+        assert_eq!(lines[1]["type"], "response");
+        assert_eq!(lines[1]["cmd_id"], 0x0d);
+        assert_eq!(lines[1]["seq"], 7);
+        assert_eq!(lines[1]["outcome"], "late_response");
+        assert_eq!(lines[1]["rtt_us"], 2_500);
+        assert!(lines[1]["correlation"].as_str().unwrap().starts_with("op-"));
+
+        assert_eq!(lines[2]["outcome"], "timeout");
+        assert_eq!(lines[2]["rtt_us"], Value::Null);
+        assert_eq!(lines[2]["error"], Value::Null);
+
+        assert_eq!(lines[3]["type"], "event");
+        assert_eq!(lines[3]["event"], "limit_clamped");
+        assert_eq!(lines[3]["details"]["issued"], json!([1350, 0]));
+    }
+}
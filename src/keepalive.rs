@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+/// How often `A8Mini::run_keepalive` pings the camera, and how many pings in a row may go
+/// unanswered before it is reported unresponsive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeepalivePolicy {
+    pub interval: Duration,
+    pub misses: u32,
+}
+
+impl KeepalivePolicy {
+    pub fn new(interval: Duration, misses: u32) -> Self {
+        Self {
+            interval,
+            misses: misses.max(1),
+        }
+    }
+}
+
+impl Default for KeepalivePolicy {
+    fn default() -> Self {
+        Self::new(
+            crate::constants::KEEPALIVE_INTERVAL,
+            crate::constants::KEEPALIVE_MISSES,
+        )
+    }
+}
+
+/// Tracks keepalive answers. The camera starts out responsive.
+#[derive(Debug, Clone)]
+pub struct KeepaliveMonitor {
+    policy: KeepalivePolicy,
+    missed: u32,
+    responsive: bool,
+}
+
+impl KeepaliveMonitor {
+    pub fn new(policy: KeepalivePolicy) -> Self {
+        Self {
+            policy,
+            missed: 0,
+            responsive: true,
+        }
+    }
+
+    pub fn is_responsive(&self) -> bool {
+        self.responsive
+    }
+
+    /// Records whether a keepalive was answered. Returns the new responsiveness if it changed.
+    pub fn record(&mut self, answered: bool) -> Option<bool> {
+        self.missed = if answered { 0 } else { self.missed + 1 };
+        let responsive = self.missed < self.policy.misses;
+        if responsive == self.responsive {
+            return None;
+        }
+        self.responsive = responsive;
+        Some(responsive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keepalive_monitor() {
+        let mut monitor = KeepaliveMonitor::new(KeepalivePolicy::new(Duration::from_secs(1), 2));
+        assert_eq!(monitor.record(true), None);
+        assert_eq!(monitor.record(false), None);
+        assert_eq!(monitor.record(false), Some(false));
+        assert_eq!(monitor.record(false), None);
+        assert!(!monitor.is_responsive());
+        assert_eq!(monitor.record(true), Some(true));
+        assert_eq!(monitor.record(false), None);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::control::A8MiniAtittude;
+use std::time::Duration;
+
+/// Whether `sample` has moved away from `baseline` by at least `threshold` decidegrees on yaw or
+/// pitch, i.e. the gimbal has started responding to a step.
+pub fn motion_onset(baseline: &A8MiniAtittude, sample: &A8MiniAtittude, threshold: i16) -> bool {
+    (sample.theta_yaw as i32 - baseline.theta_yaw as i32).abs() >= threshold as i32
+        || (sample.theta_pitch as i32 - baseline.theta_pitch as i32).abs() >= threshold as i32
+}
+
+/// Summary of measured command-to-motion latencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl LatencyStats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        Some(Self {
+            min: *sorted.first()?,
+            median: sorted[sorted.len() / 2],
+            mean: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+            max: *sorted.last()?,
+        })
+    }
+}
+
+/// Result of a latency profiling run. `stats` is `None` if no step produced motion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatencyReport {
+    pub samples: Vec<Duration>,
+    /// Steps after which no motion was seen within the timeout.
+    pub missed: usize,
+    pub stats: Option<LatencyStats>,
+}
+
+impl LatencyReport {
+    pub fn new(samples: Vec<Duration>, missed: usize) -> Self {
+        Self {
+            stats: LatencyStats::from_samples(&samples),
+            samples,
+            missed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attitude(theta_yaw: i16, theta_pitch: i16) -> A8MiniAtittude {
+        A8MiniAtittude {
+            theta_yaw,
+            theta_pitch,
+            theta_roll: 0,
+            v_yaw: 0,
+            v_pitch: 0,
+            v_roll: 0,
+        }
+    }
+
+    #[test]
+    fn test_motion_onset() {
+        let baseline = attitude(100, -50);
+        assert!(!motion_onset(&baseline, &attitude(104, -47), 5));
+        assert!(motion_onset(&baseline, &attitude(95, -50), 5));
+        assert!(motion_onset(&baseline, &attitude(100, -60), 5));
+        assert!(motion_onset(
+            &attitude(i16::MIN, 0),
+            &attitude(i16::MAX, 0),
+            5
+        ));
+    }
+
+    #[test]
+    fn test_latency_stats() {
+        let report = LatencyReport::new(
+            [40, 10, 30, 20]
+                .into_iter()
+                .map(Duration::from_millis)
+                .collect(),
+            1,
+        );
+        let stats = report.stats.unwrap();
+        assert_eq!(stats.min, Duration::from_millis(10));
+        assert_eq!(stats.median, Duration::from_millis(30));
+        assert_eq!(stats.mean, Duration::from_millis(25));
+        assert_eq!(stats.max, Duration::from_millis(40));
+        assert_eq!(report.missed, 1);
+
+        assert_eq!(LatencyReport::new(vec![], 3).stats, None);
+    }
+}
@@ -1,177 +1,5693 @@
 #![allow(non_snake_case)]
 
 use bincode::deserialize;
+use control::{Command, HTTPQuery};
+use decideg::DeciDeg;
+pub use error::A8MiniError;
 use std::error::Error;
-use tokio::{net::UdpSocket, time::timeout};
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::{
+    io::AsyncWriteExt,
+    net::UdpSocket,
+    sync::{broadcast, mpsc, oneshot, watch},
+    time::timeout,
+};
+use tracing::Instrument;
 
+#[macro_use]
+mod logging;
+
+pub mod arbitration;
+pub mod audit;
+pub mod boot;
+pub mod breaker;
+#[cfg(unix)]
+pub mod broker;
+pub mod builder;
+pub mod calibration;
+pub mod capture;
 pub mod checksum;
+pub mod clock;
+pub mod compensation;
+pub mod config;
 pub mod constants;
 pub mod control;
+pub mod conventions;
+pub mod correlation;
+pub mod deadline;
+pub mod decideg;
+pub mod demux;
+pub mod deviation;
+pub mod error;
+pub mod estimate;
+pub mod events;
+pub mod hybrid;
+pub mod idle;
+pub mod init;
+pub mod jsonlog;
+pub mod keepalive;
+pub mod latency;
+pub mod limits;
+#[cfg(feature = "sqlite")]
+pub mod media_index;
+pub mod metrics;
+pub mod middleware;
+pub mod models;
+pub mod pacing;
+pub mod progress;
+pub mod queue;
+pub mod recording;
+pub mod replay;
+pub mod report;
+pub mod restricted;
+pub mod rtt;
+#[cfg(all(feature = "serial", unix))]
+pub mod serial;
+pub mod settings;
+pub mod sockets;
+pub mod supervisor;
+pub mod telemetry;
+pub mod throttle;
+pub mod trajectory;
+pub mod transport;
+pub mod urls;
+pub mod zoom;
+
+#[derive(Debug)]
+pub struct A8Mini {
+    command_transport: Arc<dyn transport::Transport>,
+    pub http_socket: UdpSocket,
+    client_config: config::ClientConfig,
+    seq: AtomicU16,
+    http_client: reqwest::Client,
+    soft_limits: Option<limits::SoftLimits>,
+    angle_convention: conventions::AngleConvention,
+    calibration: calibration::CalibrationOffsets,
+    events: broadcast::Sender<events::EventRecord>,
+    attitude_samples: broadcast::Sender<telemetry::AttitudeSample>,
+    zoom_samples: broadcast::Sender<telemetry::Timestamped<f32>>,
+    gimbal_info_samples: broadcast::Sender<telemetry::Timestamped<control::GimbalInfo>>,
+    link_samples: broadcast::Sender<telemetry::Timestamped<telemetry::LinkStats>>,
+    attitude_watch: watch::Sender<conventions::Attitude>,
+    progress: broadcast::Sender<progress::MissionProgress>,
+    jitter: Mutex<telemetry::JitterMeter>,
+    telemetry: Mutex<telemetry::TelemetryCache>,
+    attitude_estimator: Mutex<estimate::AttitudeEstimator>,
+    init_sequence: init::InitSequence,
+    storage_status: Mutex<control::StorageStatus>,
+    model: Mutex<Option<models::CameraModel>>,
+    download_rate_limit: Option<throttle::RateLimit>,
+    offline_queue: Option<Mutex<queue::OfflineQueue>>,
+    dry_run: bool,
+    strict_acks: bool,
+    audit_log: Mutex<audit::AuditLog>,
+    motion_interlock: bool,
+    motion_armed: AtomicBool,
+    /// Set by `emergency_stop`; watched by trajectory and replay loops to stop early.
+    emergency_latched: watch::Sender<bool>,
+    parked: AtomicBool,
+    setpoint_pacer: Option<Mutex<pacing::SetpointPacer>>,
+    delta_filter: Option<Mutex<pacing::DeltaFilter>>,
+    circuit_breaker: Option<Mutex<breaker::CircuitBreaker>>,
+    connection: Option<Mutex<supervisor::ConnectionMonitor>>,
+    connection_state: watch::Sender<supervisor::ConnectionState>,
+    rtt: Option<Mutex<rtt::RttEstimator>>,
+    response_grace: Option<std::time::Duration>,
+    arbitration: Option<Mutex<arbitration::Arbitration>>,
+    middleware: middleware::MiddlewareChain,
+    json_log: Option<Mutex<jsonlog::JsonLog>>,
+    /// Held while `json_log` is set.
+    text_to_stderr: Option<logging::TextToStderr>,
+    idle: Option<Mutex<idle::IdleMonitor>>,
+    deviation: Option<Mutex<deviation::DeviationMonitor>>,
+    command_metrics: Mutex<metrics::CommandBreakdown>,
+    demux: Arc<demux::Demux>,
+    reader: tokio::task::JoinHandle<()>,
+    pushed: tokio::sync::Mutex<mpsc::Receiver<(Vec<u8>, Instant)>>,
+}
+
+impl A8Mini {
+    pub async fn connect() -> Result<Self, A8MiniError> {
+        Self::builder().connect().await
+    }
+
+    /// Starts configuring a UDP connection, e.g.
+    /// `A8Mini::builder().camera_addr(ip).retries(2).connect()`.
+    pub fn builder() -> builder::A8MiniBuilder {
+        builder::A8MiniBuilder::default()
+    }
+
+    /// Connects with ports given as strings. `A8Mini::builder()` is the clearer way to set them.
+    pub async fn connect_to(
+        camera_ip: &str,
+        camera_command_port: &str,
+        camera_http_port: &str,
+        local_command_port: &str,
+        local_http_port: &str,
+        client_config: config::ClientConfig,
+    ) -> Result<A8Mini, A8MiniError> {
+        let port = |port: &str| {
+            port.parse::<u16>()
+                .map_err(|_| A8MiniError::InvalidArgument(format!("Invalid port {:?}.", port)))
+        };
+        Self::builder()
+            .camera_addr(camera_ip)
+            .camera_command_port(port(camera_command_port)?)
+            .camera_http_port(port(camera_http_port)?)
+            .local_command_port(port(local_command_port)?)
+            .local_http_port(port(local_http_port)?)
+            .client_config(client_config)
+            .connect()
+            .await
+    }
+
+    /// Connects to the camera's SDK port over TCP, which newer firmware accepts alongside UDP.
+    /// Delivery is reliable, which helps on lossy Wi-Fi links.
+    pub async fn connect_tcp(
+        camera_ip: &str,
+        camera_command_port: &str,
+    ) -> Result<A8Mini, A8MiniError> {
+        let stream =
+            tokio::net::TcpStream::connect(format!("{}:{}", camera_ip, camera_command_port))
+                .await?;
+        stream.set_nodelay(true)?;
+        log_line!(
+            "[NETWORK] Connected over TCP to {}:{}.",
+            camera_ip,
+            camera_command_port
+        );
+        Self::with_transport(transport::StreamTransport::new(stream)).await
+    }
+
+    /// Attaches to a local broker that owns the camera connection, so several processes can
+    /// command the same camera. See [`broker::Broker`].
+    #[cfg(unix)]
+    pub async fn connect_broker(path: impl AsRef<std::path::Path>) -> Result<A8Mini, A8MiniError> {
+        let path = path.as_ref();
+        let stream = tokio::net::UnixStream::connect(path).await?;
+        log_line!("[NETWORK] Attached to broker at {}.", path.display());
+        Self::with_transport(transport::StreamTransport::new(stream)).await
+    }
+
+    /// Builds a client whose SDK commands go over `transport` instead of the default UDP socket,
+    /// e.g. a TCP or serial link, or a mock in tests.
+    pub async fn with_transport<T: transport::Transport + 'static>(
+        transport: T,
+    ) -> Result<A8Mini, A8MiniError> {
+        let http_socket = UdpSocket::bind("0.0.0.0:0").await?;
+        Ok(Self::from_parts(Box::new(transport), http_socket))
+    }
+
+    /// Connects over the gimbal's UART, e.g. `connect_serial("/dev/ttyUSB0", 115200)`.
+    #[cfg(all(feature = "serial", unix))]
+    pub async fn connect_serial(path: &str, baud_rate: u32) -> Result<A8Mini, A8MiniError> {
+        Self::with_transport(serial::open(path, baud_rate)?).await
+    }
+
+    fn from_parts(
+        command_transport: Box<dyn transport::Transport>,
+        http_socket: UdpSocket,
+    ) -> A8Mini {
+        let command_transport: Arc<dyn transport::Transport> = command_transport.into();
+        let client_config = config::ClientConfig::default();
+        let demux = Arc::new(demux::Demux::default());
+        let (reader, pushed) = Self::spawn_reader(&command_transport, &demux, &client_config);
+        A8Mini {
+            command_transport,
+            http_socket,
+            client_config,
+            seq: AtomicU16::new(0),
+            http_client: reqwest::Client::new(),
+            soft_limits: None,
+            angle_convention: conventions::AngleConvention::default(),
+            calibration: calibration::CalibrationOffsets::default(),
+            events: broadcast::channel(constants::EVENT_CHANNEL_CAPACITY).0,
+            attitude_samples: broadcast::channel(constants::ATTITUDE_CHANNEL_CAPACITY).0,
+            zoom_samples: broadcast::channel(constants::TELEMETRY_CHANNEL_CAPACITY).0,
+            gimbal_info_samples: broadcast::channel(constants::TELEMETRY_CHANNEL_CAPACITY).0,
+            link_samples: broadcast::channel(constants::TELEMETRY_CHANNEL_CAPACITY).0,
+            attitude_watch: watch::channel(conventions::Attitude::default()).0,
+            progress: broadcast::channel(constants::PROGRESS_CHANNEL_CAPACITY).0,
+            jitter: Mutex::new(telemetry::JitterMeter::default()),
+            telemetry: Mutex::new(telemetry::TelemetryCache::default()),
+            attitude_estimator: Mutex::new(estimate::AttitudeEstimator::default()),
+            init_sequence: init::InitSequence::new(),
+            storage_status: Mutex::new(control::StorageStatus::Unknown),
+            model: Mutex::new(None),
+            download_rate_limit: None,
+            offline_queue: None,
+            dry_run: false,
+            strict_acks: false,
+            audit_log: Mutex::new(audit::AuditLog::default()),
+            motion_interlock: false,
+            motion_armed: AtomicBool::new(false),
+            emergency_latched: watch::channel(false).0,
+            parked: AtomicBool::new(false),
+            setpoint_pacer: None,
+            delta_filter: None,
+            circuit_breaker: None,
+            connection: None,
+            connection_state: watch::channel(supervisor::ConnectionState::Connected).0,
+            rtt: None,
+            response_grace: None,
+            arbitration: None,
+            middleware: middleware::MiddlewareChain::default(),
+            json_log: None,
+            text_to_stderr: None,
+            idle: None,
+            deviation: None,
+            command_metrics: Mutex::new(metrics::CommandBreakdown::default()),
+            demux,
+            reader,
+            pushed: tokio::sync::Mutex::new(pushed),
+        }
+    }
+
+    /// Starts the task reading the command link, see `demux::spawn_reader`.
+    fn spawn_reader(
+        transport: &Arc<dyn transport::Transport>,
+        demux: &Arc<demux::Demux>,
+        client_config: &config::ClientConfig,
+    ) -> (
+        tokio::task::JoinHandle<()>,
+        mpsc::Receiver<(Vec<u8>, Instant)>,
+    ) {
+        let (sender, pushed) = mpsc::channel(constants::PUSHED_FRAME_CAPACITY);
+        let reader = demux::spawn_reader(
+            transport.clone(),
+            demux.clone(),
+            sender,
+            client_config
+                .recv_buffer_size
+                .max(constants::RECV_BUFF_SIZE),
+        );
+        (reader, pushed)
+    }
+
+    /// The command socket when commands go over UDP.
+    pub fn command_socket(&self) -> Option<&UdpSocket> {
+        self.command_transport.as_udp()
+    }
+
+    fn udp_command_socket(&self) -> Result<&UdpSocket, Box<dyn Error>> {
+        self.command_socket().ok_or_else(|| {
+            A8MiniError::InvalidArgument("Commands do not go over UDP.".into()).into()
+        })
+    }
+
+    /// Applies buffer sizes and DSCP marking to the command socket, so control datagrams can be
+    /// prioritized over bulk video traffic on QoS-aware links.
+    pub fn set_socket_options(
+        &self,
+        options: sockets::SocketOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        options.apply(self.udp_command_socket()?)?;
+        log_line!("[NETWORK] Applied command socket options {:?}.", options);
+        Ok(())
+    }
+
+    /// Returns a handle sharing this client that refuses destructive commands.
+    pub fn restricted(self: &Arc<Self>) -> restricted::RestrictedA8Mini {
+        restricted::RestrictedA8Mini::new(Arc::clone(self))
+    }
+
+    /// Registers an init sequence and runs it immediately. The sequence is kept and re-run
+    /// after every reconnect.
+    pub async fn with_init_sequence(
+        mut self,
+        init_sequence: init::InitSequence,
+    ) -> (Self, init::InitReport) {
+        self.init_sequence = init_sequence;
+        let report = self.run_init_sequence().await;
+        (self, report)
+    }
+
+    /// Runs the registered init sequence, continuing past failed steps.
+    pub async fn run_init_sequence(&self) -> init::InitReport {
+        let mut report = init::InitReport::default();
+
+        for &step in self.init_sequence.steps() {
+            let result = match step {
+                init::InitStep::Simple(command) => self.send_command_blind(command).await,
+                init::InitStep::Complex(command) => self.send_command_blind(command).await,
+                init::InitStep::Delay(delay) => {
+                    tokio::time::sleep(delay).await;
+                    Ok(())
+                }
+            };
+
+            if let Err(e) = &result {
+                log_line!("[INIT] Step {:?} failed: {}", step, e);
+            }
+
+            report.steps.push(init::InitStepResult {
+                step,
+                result: result.map_err(|e| e.to_string()),
+            });
+        }
+
+        report
+    }
+
+    /// Caps the byte rate of media downloads. `None` removes the cap.
+    pub fn set_download_rate_limit(&mut self, download_rate_limit: Option<throttle::RateLimit>) {
+        self.download_rate_limit = download_rate_limit;
+    }
+
+    /// Enables queueing of non-motion commands that fail to send during a link outage. Queued
+    /// commands are flushed after the next successful send. `None` disables queueing and drops
+    /// anything still pending.
+    pub fn set_offline_queue(&mut self, offline_queue: Option<queue::OfflineQueue>) {
+        self.offline_queue = offline_queue.map(Mutex::new);
+    }
+
+    /// Number of commands waiting in the offline queue.
+    pub fn queued_commands(&self) -> usize {
+        self.offline_queue
+            .as_ref()
+            .map_or(0, |queue| queue.lock().unwrap().len())
+    }
+
+    /// Sends every live command in the offline queue, oldest first. Stops at the first failure,
+    /// leaving the failed command at the head of the queue. Returns the number of commands sent.
+    pub async fn flush_offline_queue(&self) -> Result<usize, Box<dyn Error>> {
+        let Some(offline_queue) = &self.offline_queue else {
+            return Ok(0);
+        };
+
+        let mut flushed = 0;
+        loop {
+            let Some(command) = offline_queue.lock().unwrap().pop_live(clock::now()) else {
+                return Ok(flushed);
+            };
+
+            if let Err(e) = self.transmit(&command.bytes).await {
+                offline_queue.lock().unwrap().push_front(command);
+                return Err(e);
+            }
+            log_line!("[QUEUE] Flushed command: {:?}", command.bytes);
+            flushed += 1;
+        }
+    }
+
+    /// In dry-run mode commands are encoded, validated, logged and recorded in the audit log,
+    /// but never transmitted. Commands that expect a response fail.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// In strict mode blind commands also wait for their acknowledgement and fail if it does not
+    /// arrive or answers a different command, instead of being queued or failing silently.
+    /// Intended for certification and pre-delivery checks.
+    pub fn set_strict_acks(&mut self, strict_acks: bool) {
+        self.strict_acks = strict_acks;
+    }
+
+    pub fn is_strict_acks(&self) -> bool {
+        self.strict_acks
+    }
+
+    /// Snapshot of the most recent commands issued by this client, oldest first.
+    pub fn audit_log(&self) -> Vec<audit::AuditEntry> {
+        self.audit_log.lock().unwrap().entries().cloned().collect()
+    }
+
+    pub fn clear_audit_log(&self) {
+        self.audit_log.lock().unwrap().clear();
+    }
+
+    fn audit(
+        &self,
+        command_bytes: &[u8],
+        source: Option<arbitration::CommandSource>,
+        outcome: audit::AuditOutcome,
+    ) {
+        let mut audit_log = self.audit_log.lock().unwrap();
+        audit_log.record(
+            command_bytes.to_vec(),
+            correlation::current(),
+            source,
+            outcome,
+        );
+        if let (Some(json_log), Some(entry)) = (&self.json_log, audit_log.entries().last()) {
+            json_log.lock().unwrap().record_command(entry);
+        }
+    }
+
+    /// Enables arbitration between tagged command sources. Only motion commands sent with
+    /// `send_command_blind_from`/`send_command_from` are arbitrated; untagged sends and stops
+    /// always pass. `None` disables arbitration.
+    pub fn set_arbitration(&mut self, arbitration: Option<arbitration::Arbitration>) {
+        self.arbitration = arbitration.map(Mutex::new);
+    }
+
+    /// Whether operator input currently overrides the autopilot.
+    pub fn operator_override(&self) -> bool {
+        self.arbitration
+            .as_ref()
+            .is_some_and(|arbitration| arbitration.lock().unwrap().operator_override(clock::now()))
+    }
+
+    /// Hands control back to the autopilot before the operator hold expires.
+    pub fn release_operator_override(&self) {
+        if let Some(arbitration) = &self.arbitration {
+            arbitration.lock().unwrap().release();
+        }
+    }
+
+    fn arbitrate<T: control::Command>(
+        &self,
+        command: &T,
+        source: Option<arbitration::CommandSource>,
+    ) -> Result<(), Box<dyn Error>> {
+        let (Some(arbitration), Some(source)) = (&self.arbitration, source) else {
+            return Ok(());
+        };
+        if !command.is_motion() || command.is_stop() {
+            return Ok(());
+        }
+
+        if !arbitration.lock().unwrap().admit(source, clock::now()) {
+            log_line!(
+                "[ARBITRATION] {:?} motion command rejected, operator override active.",
+                source
+            );
+            return Err(A8MiniError::Rejected("Operator override active.".into()).into());
+        }
+        Ok(())
+    }
+
+    /// Sets the minimum interval between angle setpoints. Setpoints issued faster than this are
+    /// coalesced and only the newest is sent; superseded ones are audited as `Superseded`.
+    /// `None`, the default, sends every setpoint immediately.
+    pub fn set_setpoint_spacing(&mut self, min_interval: Option<std::time::Duration>) {
+        self.setpoint_pacer =
+            min_interval.map(|min_interval| Mutex::new(pacing::SetpointPacer::new(min_interval)));
+    }
+
+    /// Enables suppression of angle and speed setpoints that match the last acknowledged one
+    /// within the filter's tolerance. A setpoint sent blind, as the hold and trajectory loops do,
+    /// counts as acknowledged once sent; one sent with `send_command` once its ACK arrives.
+    /// `None` sends every setpoint.
+    pub fn set_delta_filter(&mut self, delta_filter: Option<pacing::DeltaFilter>) {
+        self.delta_filter = delta_filter.map(Mutex::new);
+    }
+
+    fn setpoint_of<T: control::Command>(command: &T) -> Option<pacing::Setpoint> {
+        command
+            .angle_setpoint()
+            .map(|(theta_yaw, theta_pitch)| pacing::Setpoint::Angle(theta_yaw, theta_pitch))
+            .or_else(|| {
+                command
+                    .rate_setpoint()
+                    .map(|(v_yaw, v_pitch)| pacing::Setpoint::Rate(v_yaw, v_pitch))
+            })
+    }
+
+    /// Whether delta suppression drops this command. A suppressed setpoint also cancels any
+    /// setpoint waiting for its pacing slot, since the gimbal is already where it should end up.
+    fn suppress_setpoint<T: control::Command>(&self, command: &T) -> bool {
+        let (Some(delta_filter), Some(setpoint)) = (&self.delta_filter, Self::setpoint_of(command))
+        else {
+            return false;
+        };
+        if command.is_stop() || !delta_filter.lock().unwrap().is_redundant(setpoint) {
+            return false;
+        }
+
+        if let Some(setpoint_pacer) = &self.setpoint_pacer {
+            setpoint_pacer.lock().unwrap().cancel_pending();
+        }
+        true
+    }
+
+    fn record_motion_sent<T: control::Command>(&self, command: &T, reply: Reply) {
+        if let (Some(delta_filter), true) = (&self.delta_filter, command.is_motion()) {
+            let mut delta_filter = delta_filter.lock().unwrap();
+            match reply {
+                Reply::Blind => delta_filter.record(Self::setpoint_of(command)),
+                Reply::Awaited => delta_filter.sent(Self::setpoint_of(command)),
+            }
+        }
+        if let (Some(idle), true) = (&self.idle, command.is_motion()) {
+            idle.lock().unwrap().record_activity(clock::now());
+        }
+        if command.is_motion() {
+            // Decoded from the frame so the fixed-speed simple rotations count as rates too.
+            let rate = replay::RecordedCommand(command.to_bytes()).rate_setpoint();
+            let mut estimator = self.attitude_estimator.lock().unwrap();
+            match rate {
+                Some((v_yaw, v_pitch)) => estimator.command_rate(v_yaw, v_pitch, clock::now()),
+                None => estimator.hold(clock::now()),
+            }
+        }
+    }
+
+    /// Replaces the attitude estimator, e.g. with a calibrated speed command scale.
+    pub fn set_attitude_estimator(&mut self, attitude_estimator: estimate::AttitudeEstimator) {
+        self.attitude_estimator = Mutex::new(attitude_estimator);
+    }
+
+    /// Attitude dead-reckoned from the last attitude sample and the rates commanded since, for
+    /// control loops running faster than telemetry arrives. `None` before the first sample.
+    pub fn attitude_estimate(&self) -> Option<estimate::AttitudeEstimate> {
+        self.attitude_estimator
+            .lock()
+            .unwrap()
+            .estimate(clock::now())
+    }
+
+    /// Sets the ack timeout, retries and receive buffer size used from the next request on.
+    pub fn set_client_config(&mut self, client_config: config::ClientConfig) {
+        if client_config.recv_buffer_size != self.client_config.recv_buffer_size {
+            self.reader.abort();
+            let (reader, pushed) =
+                Self::spawn_reader(&self.command_transport, &self.demux, &client_config);
+            self.reader = reader;
+            self.pushed = tokio::sync::Mutex::new(pushed);
+        }
+        self.client_config = client_config;
+    }
+
+    pub fn client_config(&self) -> config::ClientConfig {
+        self.client_config
+    }
+
+    /// Derives response timeouts from the measured round-trip time instead of the configured
+    /// ack timeout. `None` restores the configured timeout.
+    pub fn set_adaptive_timeout(&mut self, rtt: Option<rtt::RttEstimator>) {
+        self.rtt = rtt.map(Mutex::new);
+    }
+
+    /// Smoothed round-trip time, when adaptive timeouts are enabled and a response was measured.
+    pub fn smoothed_rtt(&self) -> Option<std::time::Duration> {
+        self.rtt
+            .as_ref()
+            .and_then(|rtt| rtt.lock().unwrap().smoothed())
+    }
+
+    /// Keeps listening for `grace` after a response timeout and still matches a response that
+    /// arrives then to its request, so callers do not retry a command that did reach the camera,
+    /// such as `TakePicture`, on a marginal link. `None` disables the grace window.
+    pub fn set_response_grace(&mut self, grace: Option<std::time::Duration>) {
+        self.response_grace = grace;
+    }
+
+    pub fn response_grace(&self) -> Option<std::time::Duration> {
+        self.response_grace
+    }
+
+    fn response_timeout(&self) -> std::time::Duration {
+        self.rtt
+            .as_ref()
+            .map_or(self.client_config.ack_timeout, |rtt| {
+                rtt.lock().unwrap().timeout()
+            })
+    }
+
+    /// Waits for the next angle setpoint slot. Returns `false` if a newer setpoint superseded
+    /// this one while waiting.
+    async fn pace_setpoint(&self) -> bool {
+        let Some(setpoint_pacer) = &self.setpoint_pacer else {
+            return true;
+        };
+
+        let (generation, wait) = {
+            let mut setpoint_pacer = setpoint_pacer.lock().unwrap();
+            let now = clock::now();
+            let (generation, wait) = setpoint_pacer.submit(now);
+            if wait.is_zero() {
+                setpoint_pacer.mark_sent(now);
+                return true;
+            }
+            (generation, wait)
+        };
+        tokio::time::sleep(wait).await;
+
+        let mut setpoint_pacer = setpoint_pacer.lock().unwrap();
+        if !setpoint_pacer.is_current(generation) {
+            return false;
+        }
+        setpoint_pacer.mark_sent(clock::now());
+        true
+    }
+
+    /// Enables a circuit breaker over acknowledged commands. Once tripped, every command except
+    /// stops fails fast, `monitor_storage` and `apply_defaults_on_boot` switch to probing, and a
+    /// `LinkDown` event is emitted until `probe_link` succeeds. `run_keepalive` and
+    /// `supervise_connection` poll with probes already and keep their rate. `None` disables the
+    /// breaker.
+    pub fn set_circuit_breaker(&mut self, circuit_breaker: Option<breaker::CircuitBreaker>) {
+        self.circuit_breaker = circuit_breaker.map(Mutex::new);
+    }
+
+    /// Whether the circuit breaker has tripped.
+    pub fn is_link_down(&self) -> bool {
+        self.circuit_breaker
+            .as_ref()
+            .is_some_and(|circuit_breaker| circuit_breaker.lock().unwrap().is_open())
+    }
+
+    fn record_link_result(&self, success: bool) {
+        self.record_connection_result(success);
+        let Some(circuit_breaker) = &self.circuit_breaker else {
+            return;
+        };
+
+        match circuit_breaker.lock().unwrap().record(success) {
+            Some(breaker::LinkState::Down) => {
+                log_line!("[LINK] Circuit breaker tripped, link is down.");
+                self.emit(events::A8MiniEvent::LinkDown);
+            }
+            Some(breaker::LinkState::Up) => {
+                log_line!("[LINK] Probe succeeded, link is up.");
+                self.emit(events::A8MiniEvent::LinkUp);
+            }
+            None => {}
+        }
+    }
+
+    /// Tracks link health by consecutive request outcomes, published through `connection_state`.
+    /// Reconnection is done by `supervise_connection`. `None` disables it.
+    pub fn set_connection_policy(&mut self, policy: Option<supervisor::ConnectionPolicy>) {
+        self.connection =
+            policy.map(|policy| Mutex::new(supervisor::ConnectionMonitor::new(policy)));
+        self.connection_state
+            .send_replace(supervisor::ConnectionState::Connected);
+    }
+
+    pub fn connection_policy(&self) -> Option<supervisor::ConnectionPolicy> {
+        self.connection
+            .as_ref()
+            .map(|connection| connection.lock().unwrap().policy())
+    }
+
+    /// Returns a receiver of the connection state. It stays `Connected` unless a connection
+    /// policy is set.
+    pub fn connection_state(&self) -> watch::Receiver<supervisor::ConnectionState> {
+        self.connection_state.subscribe()
+    }
+
+    fn record_connection_result(&self, success: bool) {
+        let Some(connection) = &self.connection else {
+            return;
+        };
+        let changed = connection.lock().unwrap().record(success);
+        if let Some(state) = changed {
+            log_line!("[LINK] Connection {:?}.", state);
+            self.connection_state.send_replace(state);
+        }
+    }
+
+    /// Keeps the connection alive forever. The link is probed whenever nothing has been received
+    /// for the policy's probe interval, so a dead link is noticed even when the application is
+    /// idle. Once the link is lost, the sockets are re-connected and the link probed with
+    /// exponential backoff until the camera answers; the init sequence is then re-run. Probes
+    /// bypass the circuit breaker, so they also close it once the camera is back. Returns
+    /// immediately if no connection policy is set.
+    ///
+    /// Run it alongside the client, e.g. in a `tokio::select!` with the code using the camera.
+    pub async fn supervise_connection(&self) {
+        let Some(connection) = &self.connection else {
+            return;
+        };
+        let policy = connection.lock().unwrap().policy();
+        let mut state = self.connection_state.subscribe();
+        let mut last_probe = clock::now();
+        loop {
+            if *state.borrow_and_update() == supervisor::ConnectionState::Disconnected {
+                self.restore_connection(policy).await;
+                continue;
+            }
+
+            let last_response = self.telemetry.lock().unwrap().link.last_response;
+            let last_heard = last_response.map_or(last_probe, |last| last.max(last_probe));
+            let due = tokio::time::Instant::from_std(last_heard + policy.probe_interval);
+            tokio::select! {
+                _ = state.changed() => {}
+                _ = tokio::time::sleep_until(due) => {
+                    if self.telemetry.lock().unwrap().link.last_response == last_response {
+                        last_probe = clock::now();
+                        if let Err(e) = self.probe_link().await {
+                            log_line!("[LINK] Probe failed: {}", e);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pings the camera with a firmware version query every `policy.interval`, forever, and
+    /// emits `CameraUnresponsive` once `policy.misses` pings in a row go unanswered, then
+    /// `CameraResponsive` when it answers again. Pings bypass the circuit breaker like
+    /// `probe_link`, so they also close it once the camera is back.
+    ///
+    /// Run it alongside the client, e.g. in a `tokio::select!` with the code using the camera.
+    pub async fn run_keepalive(&self, policy: keepalive::KeepalivePolicy) {
+        let mut monitor = keepalive::KeepaliveMonitor::new(policy);
+        let mut interval = tokio::time::interval(policy.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let answered = self.probe_link().await.is_ok();
+            match monitor.record(answered) {
+                Some(false) => {
+                    log_line!("[KEEPALIVE] Camera stopped answering.");
+                    self.emit(events::A8MiniEvent::CameraUnresponsive);
+                }
+                Some(true) => {
+                    log_line!("[KEEPALIVE] Camera answering again.");
+                    self.emit(events::A8MiniEvent::CameraResponsive);
+                }
+                None => {}
+            }
+        }
+    }
+
+    async fn restore_connection(&self, policy: supervisor::ConnectionPolicy) {
+        for attempt in 0.. {
+            tokio::time::sleep(policy.backoff(attempt)).await;
+            log_line!("[LINK] Reconnecting, attempt {}.", attempt + 1);
+            if let Err(e) = self.reconnect_sockets().await {
+                log_line!("[LINK] Reconnect failed: {}", e);
+                continue;
+            }
+            match self.probe_link().await {
+                Ok(()) => {
+                    self.run_init_sequence().await;
+                    return;
+                }
+                Err(e) => log_line!("[LINK] Probe failed: {}", e),
+            }
+        }
+    }
+
+    /// Re-connects the UDP sockets to their current peers, so they pick up the route again
+    /// after the interface went down. Other transports are only probed.
+    async fn reconnect_sockets(&self) -> Result<(), Box<dyn Error>> {
+        if let Some(command_socket) = self.command_socket() {
+            command_socket.connect(command_socket.peer_addr()?).await?;
+        }
+        if let Ok(http_peer) = self.http_socket.peer_addr() {
+            self.http_socket.connect(http_peer).await?;
+        }
+        Ok(())
+    }
+
+    /// Sends a firmware version query that bypasses the circuit breaker, closing it on success.
+    /// Commands held in the offline queue are flushed once the link is back up.
+    pub async fn probe_link(&self) -> Result<(), Box<dyn Error>> {
+        if self.dry_run {
+            return Err(A8MiniError::Rejected("Dry run, no response available.".into()).into());
+        }
+
+        let mut probe_bytes = control::A8MiniSimpleCommand::FirmwareVersionInformation.to_bytes();
+        let seq = self.stamp_seq(&mut probe_bytes);
+        let mut ack = self.demux.register(probe_bytes[7], Some(seq), clock::now());
+        let result = match self.transmit(&probe_bytes).await {
+            Ok(_) => self
+                .recv_response(self.response_timeout(), &mut ack)
+                .await
+                .map(|_| ()),
+            Err(e) => Err(e),
+        };
+        self.record_link_result(result.is_ok());
+        result?;
+
+        if self.queued_commands() > 0 {
+            self.flush_offline_queue().await?;
+        }
+        Ok(())
+    }
+
+    /// When enabled, rotation and angle commands are rejected until `arm_motion` is called.
+    /// Stop commands, captures and queries are always allowed. Enabling the interlock disarms motion.
+    pub fn set_motion_interlock(&mut self, enabled: bool) {
+        self.motion_interlock = enabled;
+        self.motion_armed.store(false, Ordering::SeqCst);
+    }
+
+    /// Arms motion, also clearing the latch set by `emergency_stop`.
+    pub fn arm_motion(&self) {
+        log_line!("[INTERLOCK] Motion armed.");
+        self.emergency_latched.send_replace(false);
+        self.motion_armed.store(true, Ordering::SeqCst);
+    }
+
+    pub fn disarm_motion(&self) {
+        log_line!("[INTERLOCK] Motion disarmed.");
+        self.motion_armed.store(false, Ordering::SeqCst);
+    }
+
+    /// Whether motion commands are currently accepted.
+    pub fn is_motion_armed(&self) -> bool {
+        !*self.emergency_latched.borrow()
+            && (!self.motion_interlock || self.motion_armed.load(Ordering::SeqCst))
+    }
+
+    /// Stops the gimbal immediately and latches motion off until `arm_motion` is called, whether
+    /// or not the interlock is enabled. Angle setpoints waiting for their pacing slot are
+    /// dropped, `run_trajectory` and `replay` return at once, and hold loops such as
+    /// `hold_bearing` fail on their next setpoint.
+    pub async fn emergency_stop(&self) -> Result<(), Box<dyn Error>> {
+        correlation::correlated(async {
+            log_line!("[INTERLOCK] Emergency stop.");
+            self.emergency_latched.send_replace(true);
+            self.motion_armed.store(false, Ordering::SeqCst);
+            if let Some(setpoint_pacer) = &self.setpoint_pacer {
+                setpoint_pacer.lock().unwrap().cancel_pending();
+            }
+            if let Some(delta_filter) = &self.delta_filter {
+                delta_filter.lock().unwrap().reset();
+            }
+            self.emit(events::A8MiniEvent::EmergencyStop);
+
+            self.send_command_blind(control::A8MiniSimpleCommand::StopRotation)
+                .await
+        })
+        .await
+    }
+
+    /// Writes every audited command, response outcome and emitted event as a JSON line to
+    /// `json_log`. While any client has one set the human-readable lines go to stderr, so a log
+    /// on stdout stays parseable. `None` disables it.
+    pub fn set_json_log(&mut self, json_log: Option<jsonlog::JsonLog>) {
+        self.text_to_stderr = json_log.as_ref().map(|_| {
+            self.text_to_stderr
+                .take()
+                .unwrap_or_else(logging::TextToStderr::acquire)
+        });
+        self.json_log = json_log.map(Mutex::new);
+    }
+
+    /// Appends a middleware layer run on every outgoing command after the interlock and soft
+    /// limit checks and before pacing, e.g. for logging, extra safety policies or shadowing the
+    /// camera with a simulator.
+    pub fn add_middleware<F>(&mut self, layer: F)
+    where
+        F: Fn(&dyn control::Command, &mut middleware::Context) -> middleware::Decision
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.middleware.push(layer);
+    }
+
+    pub fn clear_middleware(&mut self) {
+        self.middleware.clear();
+    }
+
+    /// Configures client-side soft limits applied to every angle setpoint, in the configured
+    /// angle convention. `None` disables them.
+    pub fn set_soft_limits(&mut self, soft_limits: Option<limits::SoftLimits>) {
+        self.soft_limits = soft_limits;
+    }
+
+    pub fn soft_limits(&self) -> Option<limits::SoftLimits> {
+        self.soft_limits
+    }
+
+    /// Applies the client-side options kept in persisted settings.
+    pub fn apply_settings(&mut self, settings: &settings::Settings) {
+        self.set_soft_limits(settings.soft_limits);
+        self.set_calibration(settings.calibration);
+    }
+
+    /// Sets how the gimbal is mounted on the airframe. Every angle and speed setpoint is then
+    /// taken in the airframe's frame and every attitude reported in it, so the rest of the API
+    /// works as if the gimbal were mounted straight. Soft limits still apply to the gimbal's own
+    /// angles, in the angle convention.
+    pub fn set_calibration(&mut self, calibration: calibration::CalibrationOffsets) {
+        self.calibration = calibration;
+    }
+
+    pub fn calibration(&self) -> calibration::CalibrationOffsets {
+        self.calibration
+    }
+
+    /// Sets the angle convention of every angle the client takes or reports: setpoints, soft
+    /// limits, attitudes, snapshots and subscriptions. It is applied once, where setpoints are
+    /// encoded and attitude frames decoded, on top of the mounting calibration.
+    pub fn set_angle_convention(&mut self, angle_convention: conventions::AngleConvention) {
+        self.angle_convention = angle_convention;
+    }
+
+    pub fn angle_convention(&self) -> conventions::AngleConvention {
+        self.angle_convention
+    }
+
+    /// Points the gimbal at a yaw/pitch in degrees, given in the configured angle convention.
+    /// When the current attitude is known, yaw takes the shortest path within the mechanical range.
+    pub async fn set_attitude_degrees(&self, yaw: f32, pitch: f32) -> Result<(), Box<dyn Error>> {
+        let theta_yaw = self
+            .angle_convention
+            .yaw_from_gimbal(compensation::to_decidegrees(compensation::wrap_degrees(
+                yaw,
+            )));
+        let theta_pitch = compensation::to_decidegrees(pitch);
+        let theta_yaw = self.shortest_yaw_setpoint(theta_yaw, theta_pitch);
+        self.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(theta_yaw),
+            DeciDeg(theta_pitch),
+        ))
+        .await
+    }
+
+    /// Chooses the rotation direction for an absolute yaw setpoint from the last known attitude,
+    /// see `compensation::shortest_yaw`. Both are converted to the gimbal's own frame, as on
+    /// encoding, so the mechanical range applies whatever the calibration. Without a known
+    /// attitude the setpoint is kept.
+    fn shortest_yaw_setpoint(&self, theta_yaw: i16, theta_pitch: i16) -> i16 {
+        let Some(attitude) = self.telemetry.lock().unwrap().attitude else {
+            return theta_yaw;
+        };
+        let (current, _) =
+            self.setpoint_to_gimbal(attitude.value.theta_yaw, attitude.value.theta_pitch);
+        let (target, gimbal_pitch) = self.setpoint_to_gimbal(theta_yaw, theta_pitch);
+        let (yaw_min, yaw_max) = constants::GIMBAL_YAW_RANGE;
+        let yaw = compensation::shortest_yaw(
+            decideg::DeciDeg(current).to_degrees(),
+            decideg::DeciDeg(target).to_degrees(),
+            yaw_min,
+            yaw_max,
+        );
+        self.setpoint_from_gimbal(compensation::to_decidegrees(yaw), gimbal_pitch)
+            .0
+    }
+
+    /// Recenters the gimbal and waits until yaw and pitch are within `CENTER_TOLERANCE` of
+    /// center, polling the attitude every `CENTER_POLL_INTERVAL`. Returns how long the recenter
+    /// took, or a timeout error if the gimbal has not settled after `CENTER_TIMEOUT`.
+    pub async fn center(&self) -> Result<std::time::Duration, Box<dyn Error>> {
+        correlation::correlated(async {
+            let started = clock::now();
+            let ack = self
+                .send_command(control::A8MiniSimpleCommand::AutoCenter)
+                .await?;
+            if ack_payload(&ack)?.first() != Some(&1) {
+                return Err(
+                    A8MiniError::Other("Camera rejected the center command.".into()).into(),
+                );
+            }
+
+            // Centering moves to the gimbal's own zero, whatever the calibration.
+            self.wait_settled(0, 0).await?;
+
+            let elapsed = clock::now().saturating_duration_since(started);
+            log_line!("[CENTER] Centered in {:?}.", elapsed);
+            Ok(elapsed)
+        })
+        .await
+    }
+
+    /// Polls the attitude every `CENTER_POLL_INTERVAL` until it is within `CENTER_TOLERANCE` of
+    /// a yaw/pitch in the gimbal's own frame, failing after `CENTER_TIMEOUT`.
+    async fn wait_settled(&self, theta_yaw: i16, theta_pitch: i16) -> Result<(), Box<dyn Error>> {
+        let settle = async {
+            let mut interval = tokio::time::interval(constants::CENTER_POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                let attitude = self.query_attitude(None).await?;
+                let (yaw, pitch) =
+                    self.setpoint_to_gimbal(attitude.theta_yaw, attitude.theta_pitch);
+                if yaw.abs_diff(theta_yaw) <= constants::CENTER_TOLERANCE
+                    && pitch.abs_diff(theta_pitch) <= constants::CENTER_TOLERANCE
+                {
+                    return Ok::<_, Box<dyn Error>>(());
+                }
+            }
+        };
+        timeout(constants::CENTER_TIMEOUT, settle)
+            .await
+            .map_err(|_| A8MiniError::Timeout)?
+    }
+
+    /// Parks the gimbal before landing or transport: yaw centered and the camera pitched fully
+    /// up, away from the ground, for the mounting direction the gimbal reports. Returns how long
+    /// parking took once the attitude confirms it. The gimbal then stays travel-locked, with
+    /// every motion command but stops rejected, until `unpark`.
+    ///
+    /// The A8 mini cannot switch its motors off over the SDK, so they keep holding the pose.
+    pub async fn park(&self) -> Result<std::time::Duration, Box<dyn Error>> {
+        correlation::correlated(async {
+            let started = clock::now();
+            let mounting = self
+                .get_gimbal_info()
+                .await?
+                .mounting
+                .unwrap_or(control::MountingDirection::Normal);
+            let park_pitch = mounting.park_pitch();
+            let (theta_yaw, theta_pitch) = self.setpoint_from_gimbal(0, park_pitch);
+            self.send_command(control::A8MiniComplexCommand::SetYawPitchAngle(
+                DeciDeg(theta_yaw),
+                DeciDeg(theta_pitch),
+            ))
+            .await?;
+            self.wait_settled(0, park_pitch).await?;
+
+            self.parked.store(true, Ordering::SeqCst);
+            let elapsed = clock::now().saturating_duration_since(started);
+            log_line!("[PARK] Parked in {:?}.", elapsed);
+            Ok(elapsed)
+        })
+        .await
+    }
+
+    /// Releases the travel lock set by `park` and recenters the gimbal, returning how long the
+    /// recenter took.
+    pub async fn unpark(&self) -> Result<std::time::Duration, Box<dyn Error>> {
+        self.parked.store(false, Ordering::SeqCst);
+        log_line!("[PARK] Unparked.");
+        self.center().await
+    }
+
+    /// Whether the gimbal is travel-locked by `park`.
+    pub fn is_parked(&self) -> bool {
+        self.parked.load(Ordering::SeqCst)
+    }
+
+    /// Queries the gimbal attitude in degrees, in the configured angle convention.
+    pub async fn get_attitude_degrees(&self) -> Result<conventions::Attitude, Box<dyn Error>> {
+        let attitude = self.get_attitude_information().await?;
+        Ok(conventions::Attitude::from(&attitude))
+    }
+
+    /// Steps through `keyframes`, sending each pose as an angle setpoint in the configured angle
+    /// convention and holding it for its dwell time. Stops at the first failed setpoint or when
+    /// `emergency_stop` is called. Progress is published after every keyframe and on failure,
+    /// see `subscribe_progress`.
+    pub async fn run_trajectory(
+        &self,
+        keyframes: &[trajectory::Keyframe],
+    ) -> Result<(), Box<dyn Error>> {
+        correlation::correlated(async {
+            log_line!("[TRAJECTORY] Running {} keyframes.", keyframes.len());
+            let started = clock::now();
+            let total = keyframes.len();
+            self.publish_progress(progress::MissionProgress::new(
+                0,
+                total,
+                std::time::Duration::ZERO,
+            ));
+            for (step, keyframe) in keyframes.iter().enumerate() {
+                let held = match self
+                    .set_attitude_degrees(keyframe.yaw, keyframe.pitch)
+                    .await
+                {
+                    Ok(()) => self.sleep_unless_stopped(keyframe.dwell).await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = held {
+                    self.publish_progress(
+                        progress::MissionProgress::new(
+                            step,
+                            total,
+                            clock::now().saturating_duration_since(started),
+                        )
+                        .failed(e.to_string()),
+                    );
+                    return Err(e);
+                }
+                self.publish_progress(progress::MissionProgress::new(
+                    step + 1,
+                    total,
+                    clock::now().saturating_duration_since(started),
+                ));
+            }
+            log_line!("[TRAJECTORY] Done.");
+            Ok(())
+        })
+        .await
+    }
+
+    /// Replays the commands an audit log shows were sent, typically one saved from a field unit,
+    /// with their original spacing scaled by `speed`. Progress is published as for trajectories,
+    /// and `emergency_stop` ends it the same way. Returns the number of commands replayed.
+    pub async fn replay(
+        &self,
+        entries: &[audit::AuditEntry],
+        speed: f32,
+    ) -> Result<usize, Box<dyn Error>> {
+        correlation::correlated(async {
+            let steps = replay::schedule(entries, speed)?;
+            log_line!("[REPLAY] Replaying {} commands at {}x.", steps.len(), speed);
+            let started = clock::now();
+            let total = steps.len();
+            for (step, replay_step) in steps.into_iter().enumerate() {
+                let replayed = match self.sleep_unless_stopped(replay_step.delay).await {
+                    Ok(()) => self.send_command_blind(replay_step.command).await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = replayed {
+                    self.publish_progress(
+                        progress::MissionProgress::new(
+                            step,
+                            total,
+                            clock::now().saturating_duration_since(started),
+                        )
+                        .failed(e.to_string()),
+                    );
+                    return Err(e);
+                }
+                self.publish_progress(progress::MissionProgress::new(
+                    step + 1,
+                    total,
+                    clock::now().saturating_duration_since(started),
+                ));
+            }
+            log_line!("[REPLAY] Done.");
+            Ok(total)
+        })
+        .await
+    }
+
+    /// Sleeps for `duration`, failing as soon as `emergency_stop` latches.
+    async fn sleep_unless_stopped(
+        &self,
+        duration: std::time::Duration,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut latched = self.emergency_latched.subscribe();
+        tokio::select! {
+            _ = tokio::time::sleep(duration) => Ok(()),
+            _ = latched.wait_for(|latched| *latched) => {
+                Err(A8MiniError::Rejected("Emergency stop latched.".into()).into())
+            }
+        }
+    }
+
+    /// Returns a receiver for events emitted by this client.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<events::EventRecord> {
+        self.events.subscribe()
+    }
+
+    /// Returns a receiver for progress of missions run by this client.
+    pub fn subscribe_progress(&self) -> broadcast::Receiver<progress::MissionProgress> {
+        self.progress.subscribe()
+    }
+
+    fn publish_progress(&self, progress: progress::MissionProgress) {
+        // No subscribers is not an error.
+        let _ = self.progress.send(progress);
+    }
+
+    fn emit(&self, event: events::A8MiniEvent) {
+        let record = events::EventRecord {
+            correlation: correlation::current(),
+            event,
+        };
+        if let Some(json_log) = &self.json_log {
+            json_log.lock().unwrap().record_event(&record);
+        }
+        // No subscribers is not an error.
+        let _ = self.events.send(record);
+    }
+
+    /// Encodes a command, enforcing the motion interlock and soft limits on angle setpoints.
+    fn encode_command<T: control::Command>(&self, command: &T) -> Result<Vec<u8>, Box<dyn Error>> {
+        if command.is_motion() && !command.is_stop() && !self.is_motion_armed() {
+            log_line!("[INTERLOCK] Motion command rejected, motion is disarmed.");
+            return Err(A8MiniError::Rejected("Motion is disarmed.".into()).into());
+        }
+        if command.is_motion() && !command.is_stop() && self.is_parked() {
+            log_line!("[PARK] Motion command rejected, gimbal is parked.");
+            return Err(A8MiniError::Rejected("Gimbal is parked.".into()).into());
+        }
+
+        let command = self.calibrate(command);
+        let (Some(soft_limits), Some((theta_yaw, theta_pitch))) =
+            (self.soft_limits, command.angle_setpoint())
+        else {
+            return Ok(command.to_bytes());
+        };
+        // Limits are on the gimbal's own angles, in the angle convention.
+        let (theta_yaw, theta_pitch) = self
+            .angle_convention
+            .setpoint_from_gimbal(theta_yaw, theta_pitch);
+
+        if soft_limits.contains(theta_yaw, theta_pitch) {
+            return Ok(command.to_bytes());
+        }
+
+        match soft_limits.mode {
+            limits::LimitMode::Reject => Err(A8MiniError::Rejected(format!(
+                "Setpoint ({}, {}) is outside soft limits.",
+                theta_yaw, theta_pitch
+            ))
+            .into()),
+            limits::LimitMode::Clamp => {
+                let (clamped_yaw, clamped_pitch) = soft_limits.clamp(theta_yaw, theta_pitch);
+                log_line!(
+                    "[COMMAND] Setpoint ({}, {}) clamped to ({}, {}).",
+                    theta_yaw,
+                    theta_pitch,
+                    clamped_yaw,
+                    clamped_pitch
+                );
+                self.emit(events::A8MiniEvent::LimitClamped {
+                    requested: (theta_yaw, theta_pitch),
+                    issued: (clamped_yaw, clamped_pitch),
+                });
+                let (clamped_yaw, clamped_pitch) = self
+                    .angle_convention
+                    .setpoint_to_gimbal(clamped_yaw, clamped_pitch);
+                Ok(control::A8MiniComplexCommand::SetYawPitchAngle(
+                    DeciDeg(clamped_yaw),
+                    DeciDeg(clamped_pitch),
+                )
+                .to_bytes())
+            }
+        }
+    }
+
+    /// Re-encodes angle and speed setpoints from the airframe's frame and the angle convention
+    /// into the gimbal's. The setpoint is taken before encoding, which clamps it to the gimbal's
+    /// range.
+    fn calibrate<T: control::Command>(&self, command: &T) -> replay::RecordedCommand {
+        let recorded = replay::RecordedCommand(command.to_bytes());
+        if self.calibration.is_identity()
+            && self.angle_convention == conventions::AngleConvention::default()
+        {
+            return recorded;
+        }
+        if let Some((theta_yaw, theta_pitch)) = command
+            .angle_setpoint()
+            .or_else(|| recorded.angle_setpoint())
+        {
+            let (theta_yaw, theta_pitch) = self.setpoint_to_gimbal(theta_yaw, theta_pitch);
+            return replay::RecordedCommand(
+                control::A8MiniComplexCommand::SetYawPitchAngle(
+                    DeciDeg(theta_yaw),
+                    DeciDeg(theta_pitch),
+                )
+                .to_bytes(),
+            );
+        }
+        if let Some((v_yaw, v_pitch)) = recorded.rate_setpoint() {
+            let (v_yaw, v_pitch) = self.angle_convention.rate_to_gimbal(v_yaw, v_pitch);
+            let (v_yaw, v_pitch) = self.calibration.rate_to_gimbal(v_yaw, v_pitch);
+            return replay::RecordedCommand(
+                control::A8MiniComplexCommand::SetYawPitchSpeed(v_yaw, v_pitch).to_bytes(),
+            );
+        }
+        recorded
+    }
+
+    /// Converts a setpoint in the airframe's frame and the angle convention to the gimbal's own.
+    fn setpoint_to_gimbal(&self, theta_yaw: i16, theta_pitch: i16) -> (i16, i16) {
+        let (theta_yaw, theta_pitch) = self
+            .angle_convention
+            .setpoint_to_gimbal(theta_yaw, theta_pitch);
+        self.calibration.setpoint_to_gimbal(theta_yaw, theta_pitch)
+    }
+
+    /// Converts a yaw/pitch in the gimbal's own frame to the setpoint that reaches it.
+    fn setpoint_from_gimbal(&self, theta_yaw: i16, theta_pitch: i16) -> (i16, i16) {
+        let (theta_yaw, theta_pitch) = self
+            .calibration
+            .setpoint_from_gimbal(theta_yaw, theta_pitch);
+        self.angle_convention
+            .setpoint_from_gimbal(theta_yaw, theta_pitch)
+    }
+
+    /// Stamps the next sequence number of this connection into an encoded frame.
+    fn stamp_seq(&self, command_bytes: &mut [u8]) -> u16 {
+        let seq = self.seq.fetch_add(1, Ordering::Relaxed);
+        checksum::set_seq(command_bytes, seq);
+        seq
+    }
+
+    /// Sends a frame and returns the time it was handed to the socket.
+    async fn transmit(
+        &self,
+        command_bytes: &[u8],
+    ) -> Result<capture::HostTimestamp, Box<dyn Error>> {
+        if self.command_transport.send(command_bytes).await? == 0 {
+            log_line!("[COMMAND] No bytes sent.");
+            return Err(A8MiniError::SocketClosed.into());
+        }
+        Ok(capture::HostTimestamp::now())
+    }
+
+    /// Encodes and sends a command. When the offline queue is enabled, a non-motion command sent
+    /// blind that fails to send is queued instead of returning an error.
+    async fn dispatch<T: control::Command>(
+        &self,
+        command: &T,
+        source: Option<arbitration::CommandSource>,
+        reply: Reply,
+    ) -> Result<Dispatched, Box<dyn Error>> {
+        correlation::correlated(async {
+            self.arbitrate(command, source)?;
+            let mut context = middleware::Context {
+                bytes: self.encode_command(command)?,
+                source,
+                correlation: correlation::current(),
+            };
+            match self.middleware.run(command, &mut context) {
+                middleware::Decision::Continue => {}
+                middleware::Decision::Skip => {
+                    log_line!("[COMMAND] Skipped by middleware, command not sent.");
+                    self.audit(&context.bytes, source, audit::AuditOutcome::Skipped);
+                    return Ok(Dispatched::Withheld(audit::AuditOutcome::Skipped));
+                }
+                middleware::Decision::Reject(reason) => {
+                    log_line!("[COMMAND] Rejected by middleware: {}", reason);
+                    return Err(A8MiniError::Rejected(reason).into());
+                }
+            }
+            let mut command_bytes = context.bytes;
+
+            // Withheld setpoints take no SEQ, so they are audited unstamped.
+            if self.suppress_setpoint(command) {
+                log_line!("[COMMAND] Setpoint unchanged, command not sent.");
+                self.audit(&command_bytes, source, audit::AuditOutcome::Suppressed);
+                return Ok(Dispatched::Withheld(audit::AuditOutcome::Suppressed));
+            }
+
+            if command.angle_setpoint().is_some() && !self.pace_setpoint().await {
+                log_line!("[COMMAND] Setpoint superseded by a newer one, command not sent.");
+                self.audit(&command_bytes, source, audit::AuditOutcome::Superseded);
+                return Ok(Dispatched::Withheld(audit::AuditOutcome::Superseded));
+            }
+
+            let seq = self.stamp_seq(&mut command_bytes);
+            log_line!("[COMMAND] Sending command with bytes: {:?}", command_bytes);
+            log_line!(
+                "[COMMAND] Sending command with DATA_LEN: {:?} | CMD_ID: {:?}",
+                command_bytes[3],
+                command_bytes[7]
+            );
+
+            if self.dry_run {
+                log_line!("[COMMAND] Dry run, command not sent.");
+                self.audit(&command_bytes, source, audit::AuditOutcome::DryRun);
+                self.record_motion_sent(command, reply);
+                return Ok(Dispatched::Withheld(audit::AuditOutcome::DryRun));
+            }
+
+            // Registered before sending, so a fast response cannot arrive unclaimed. Frames that
+            // answer with a different CMD_ID (function feedback) carry their own SEQ.
+            let ack_cmd_id = command.ack_cmd_id();
+            let ack = match reply {
+                Reply::Awaited => {
+                    let ack_seq = (ack_cmd_id == command_bytes[7]).then_some(seq);
+                    Some(self.demux.register(ack_cmd_id, ack_seq, clock::now()))
+                }
+                // The ACK of a blind command is not a pushed frame. Function feedback still
+                // reaches its taps.
+                Reply::Blind => {
+                    if ack_cmd_id == command_bytes[7] {
+                        let now = clock::now();
+                        let window =
+                            self.response_timeout() + self.response_grace.unwrap_or_default();
+                        self.demux.absorb(ack_cmd_id, seq, now, now + window);
+                    }
+                    None
+                }
+            };
+            let sent = if self.is_link_down() && !command.is_stop() {
+                Err(A8MiniError::Rejected("Circuit breaker open, link is down.".into()).into())
+            } else {
+                self.transmit(&command_bytes).await
+            };
+
+            let sent_at = match sent {
+                Ok(sent_at) => sent_at,
+                Err(e) => match &self.offline_queue {
+                    Some(offline_queue) if reply == Reply::Blind && !command.is_motion() => {
+                        log_line!("[QUEUE] Send failed ({}), queueing command.", e);
+                        self.audit(&command_bytes, source, audit::AuditOutcome::Queued);
+                        offline_queue
+                            .lock()
+                            .unwrap()
+                            .push(command_bytes, clock::now());
+                        return Ok(Dispatched::Withheld(audit::AuditOutcome::Queued));
+                    }
+                    _ => {
+                        self.audit(
+                            &command_bytes,
+                            source,
+                            audit::AuditOutcome::Failed(e.to_string()),
+                        );
+                        self.telemetry.lock().unwrap().link.failed += 1;
+                        self.command_metrics
+                            .lock()
+                            .unwrap()
+                            .record_failed(command_bytes[7]);
+                        return Err(e);
+                    }
+                },
+            };
+
+            log_line!("[COMMAND] Command sent successfully.");
+            self.audit(&command_bytes, source, audit::AuditOutcome::Sent);
+            self.telemetry.lock().unwrap().link.sent += 1;
+            self.command_metrics
+                .lock()
+                .unwrap()
+                .record_sent(command_bytes[7]);
+            self.record_motion_sent(command, reply);
+            if let (Some(deviation), true) = (&self.deviation, command.is_motion()) {
+                // Taken from the frame, so it is the clamped setpoint in the gimbal's frame.
+                let setpoint = replay::RecordedCommand(command_bytes.clone()).angle_setpoint();
+                deviation.lock().unwrap().command(setpoint);
+            }
+
+            if self.queued_commands() > 0 {
+                if let Err(e) = self.flush_offline_queue().await {
+                    log_line!("[QUEUE] Flush failed: {}", e);
+                }
+            }
+
+            Ok(Dispatched::Sent(SentFrame {
+                at: sent_at,
+                seq,
+                ack,
+            }))
+        })
+        .await
+    }
+
+    pub async fn send_command_blind<T: control::Command>(
+        &self,
+        command: T,
+    ) -> Result<(), Box<dyn Error>> {
+        self.send_blind(&command, None).await
+    }
+
+    /// Like `send_command_blind`, tagging the command with its source for arbitration.
+    pub async fn send_command_blind_from<T: control::Command>(
+        &self,
+        source: arbitration::CommandSource,
+        command: T,
+    ) -> Result<(), Box<dyn Error>> {
+        self.send_blind(&command, Some(source)).await
+    }
+
+    async fn send_blind<T: control::Command>(
+        &self,
+        command: &T,
+        source: Option<arbitration::CommandSource>,
+    ) -> Result<(), Box<dyn Error>> {
+        if !self.strict_acks || self.dry_run {
+            return correlation::correlated(async {
+                self.dispatch(command, source, Reply::Blind)
+                    .instrument(command_span("send", command.to_bytes()[7]))
+                    .await
+                    .map(|_| ())
+            })
+            .await;
+        }
+
+        // `request` only accepts the acknowledgement matching this command.
+        self.request(command, source, None).await.map(|_| ())
+    }
+
+    pub async fn send_command<T: control::Command>(
+        &self,
+        command: T,
+    ) -> Result<[u8; constants::RECV_BUFF_SIZE], A8MiniError> {
+        self.request(&command, None, None)
+            .await
+            .map_err(A8MiniError::from)
+    }
+
+    /// Like `send_command`, failing once `deadline` passes. The response timeout is capped by the
+    /// time remaining.
+    pub async fn send_command_within<T: control::Command>(
+        &self,
+        command: T,
+        deadline: deadline::Deadline,
+    ) -> Result<[u8; constants::RECV_BUFF_SIZE], A8MiniError> {
+        self.request(&command, None, Some(deadline))
+            .await
+            .map_err(A8MiniError::from)
+    }
+
+    /// Like `send_command`, tagging the command with its source for arbitration.
+    pub async fn send_command_from<T: control::Command>(
+        &self,
+        source: arbitration::CommandSource,
+        command: T,
+    ) -> Result<[u8; constants::RECV_BUFF_SIZE], A8MiniError> {
+        self.request(&command, Some(source), None)
+            .await
+            .map_err(A8MiniError::from)
+    }
+
+    /// Sends `command` and waits for its response, resending it up to `retries` times when the
+    /// response times out.
+    async fn request<T: control::Command>(
+        &self,
+        command: &T,
+        source: Option<arbitration::CommandSource>,
+        deadline: Option<deadline::Deadline>,
+    ) -> Result<[u8; constants::RECV_BUFF_SIZE], Box<dyn Error>> {
+        correlation::correlated(async {
+            let retries = self.client_config.retries;
+            let mut attempt = 0;
+            loop {
+                match self.request_once(command, source, deadline).await {
+                    Err(e) if attempt < retries && e.is::<tokio::time::error::Elapsed>() => {
+                        attempt += 1;
+                        log_line!("[COMMAND] No response, retrying ({}/{}).", attempt, retries);
+                        tokio::time::sleep(self.client_config.retry_delay).await;
+                    }
+                    response => return response,
+                }
+            }
+        })
+        .await
+    }
+
+    async fn request_once<T: control::Command>(
+        &self,
+        command: &T,
+        source: Option<arbitration::CommandSource>,
+        deadline: Option<deadline::Deadline>,
+    ) -> Result<[u8; constants::RECV_BUFF_SIZE], Box<dyn Error>> {
+        correlation::correlated(async {
+            if let Some(deadline) = deadline {
+                deadline.check()?;
+            }
+            let cmd_id = command.to_bytes()[7];
+            let sent = self
+                .dispatch(command, source, Reply::Awaited)
+                .instrument(command_span("send", cmd_id))
+                .await?;
+            if self.dry_run {
+                return Err(A8MiniError::Rejected("Dry run, no response available.".into()).into());
+            }
+
+            let response_timeout = self.response_timeout();
+            let recv_timeout =
+                deadline.map_or(response_timeout, |deadline| deadline.cap(response_timeout));
+            let sent = sent.into_sent()?;
+            let mut ack = sent.ack.ok_or_else(|| {
+                A8MiniError::Other("No response registered for the command.".into())
+            })?;
+            let ack_span = command_span("ack", command.ack_cmd_id());
+            let mut response = self
+                .recv_response(recv_timeout, &mut ack)
+                .instrument(ack_span.clone())
+                .await;
+            let mut late = false;
+            if let (Err(e), Some(grace)) = (&response, self.response_grace) {
+                if e.is::<tokio::time::error::Elapsed>() {
+                    let grace = deadline.map_or(grace, |deadline| deadline.cap(grace));
+                    if let Ok(late_ack) = self
+                        .recv_response(grace, &mut ack)
+                        .instrument(ack_span)
+                        .await
+                    {
+                        log_line!("[COMMAND] Late response accepted within the grace window.");
+                        response = Ok(late_ack);
+                        late = true;
+                    }
+                }
+            }
+            let elapsed = clock::now().saturating_duration_since(sent.at.monotonic);
+            if let Some(json_log) = &self.json_log {
+                let outcome = match &response {
+                    Ok(_) if late => jsonlog::ResponseOutcome::LateResponse,
+                    Ok(_) => jsonlog::ResponseOutcome::Response,
+                    Err(e) if e.is::<tokio::time::error::Elapsed>() => {
+                        jsonlog::ResponseOutcome::Timeout
+                    }
+                    Err(e) => jsonlog::ResponseOutcome::Failed(e.to_string()),
+                };
+                json_log.lock().unwrap().record_response(
+                    correlation::current(),
+                    cmd_id,
+                    sent.seq,
+                    elapsed,
+                    &outcome,
+                );
+            }
+            if let (Some(delta_filter), Ok(_)) = (&self.delta_filter, &response) {
+                if let Some(setpoint) = Self::setpoint_of(command) {
+                    delta_filter.lock().unwrap().acknowledged(setpoint);
+                }
+            }
+            if let Some(rtt) = &self.rtt {
+                let mut rtt = rtt.lock().unwrap();
+                match response {
+                    Ok(_) => rtt.observe(elapsed),
+                    Err(_) => rtt.on_timeout(),
+                }
+            }
+            {
+                let mut command_metrics = self.command_metrics.lock().unwrap();
+                match response {
+                    Ok(_) => command_metrics.record_response(cmd_id, elapsed),
+                    Err(_) => command_metrics.record_timeout(cmd_id),
+                }
+            }
+            let link = {
+                let link = &mut self.telemetry.lock().unwrap().link;
+                if response.is_ok() {
+                    link.responses += 1;
+                    link.late_responses += late as u64;
+                    link.last_response = Some(clock::now());
+                } else {
+                    link.timeouts += 1;
+                }
+                *link
+            };
+            let _ = self
+                .link_samples
+                .send(telemetry::Timestamped::new(link, clock::now()));
+            self.record_link_result(response.is_ok());
+            response
+        })
+        .await
+    }
+
+    /// Waits for the response registered as `ack`, see `demux::Demux::register`. Stale or
+    /// unrelated frames are routed as unsolicited. The timeout covers the whole wait; `ack` stays
+    /// registered after it, so a late response can still be awaited.
+    async fn recv_response(
+        &self,
+        recv_timeout: std::time::Duration,
+        ack: &mut oneshot::Receiver<(Vec<u8>, Instant)>,
+    ) -> Result<[u8; constants::RECV_BUFF_SIZE], Box<dyn Error>> {
+        log_line!("[COMMAND] Waiting for response.");
+
+        let (frame, _) = timeout(recv_timeout, self.recv_routed(ack)).await??;
+        let mut response = [0; constants::RECV_BUFF_SIZE];
+        let len = frame.len().min(response.len());
+        response[..len].copy_from_slice(&frame[..len]);
+        log_line!(
+            "[COMMAND] Response of size {} received successfully: {:?}",
+            frame.len(),
+            response
+        );
+        Ok(response)
+    }
+
+    /// Waits for the next function feedback frame (CMD 0x0B), reporting photo, recording and HDR
+    /// outcomes.
+    pub async fn get_function_feedback(
+        &self,
+        recv_timeout: std::time::Duration,
+    ) -> Result<capture::FunctionFeedback, Box<dyn Error>> {
+        let mut feedback = self.tap_function_feedback();
+        let (_, feedback) = self
+            .recv_function_feedback(recv_timeout, &mut feedback)
+            .await?;
+        Ok(feedback)
+    }
+
+    /// Takes the function feedback frames until the receiver is dropped. Taken before a capture
+    /// is sent, so its feedback cannot arrive unclaimed.
+    fn tap_function_feedback(&self) -> mpsc::Receiver<(Vec<u8>, Instant)> {
+        self.demux.tap(
+            constants::FUNCTION_FEEDBACK_CMD_ID,
+            constants::FEEDBACK_TAP_CAPACITY,
+        )
+    }
+
+    /// Waits for the next function feedback frame from `feedback` and returns its arrival time
+    /// and content.
+    async fn recv_function_feedback(
+        &self,
+        recv_timeout: std::time::Duration,
+        feedback: &mut mpsc::Receiver<(Vec<u8>, Instant)>,
+    ) -> Result<(Instant, capture::FunctionFeedback), Box<dyn Error>> {
+        let (frame, received) = timeout(recv_timeout, self.recv_tapped(feedback)).await??;
+        let info_type = *ack_payload(&frame)?.first().ok_or_else(|| {
+            A8MiniError::MalformedAck("Function feedback without info type.".into())
+        })?;
+        let feedback = capture::FunctionFeedback::from_u8(info_type);
+        log_line!("[COMMAND] Function feedback {:?} received.", feedback);
+        Ok((received, feedback))
+    }
+
+    /// Waits for the reader task to hand over the frame registered as `routed`, then routes the
+    /// frames pushed meanwhile, unless `run_push_handler` is doing so.
+    async fn recv_routed(
+        &self,
+        routed: &mut oneshot::Receiver<(Vec<u8>, Instant)>,
+    ) -> Result<(Vec<u8>, Instant), Box<dyn Error>> {
+        let routed = routed.await;
+        self.route_pushed();
+        routed.map_err(|_| A8MiniError::SocketClosed.into())
+    }
+
+    /// Like `recv_routed`, for the next frame from a tap, see `demux::Demux::tap`.
+    async fn recv_tapped(
+        &self,
+        tapped: &mut mpsc::Receiver<(Vec<u8>, Instant)>,
+    ) -> Result<(Vec<u8>, Instant), Box<dyn Error>> {
+        let tapped = tapped.recv().await;
+        self.route_pushed();
+        tapped.ok_or_else(|| A8MiniError::SocketClosed.into())
+    }
+
+    fn route_pushed(&self) {
+        let Ok(mut pushed) = self.pushed.try_lock() else {
+            return;
+        };
+        while let Ok((frame, received)) = pushed.try_recv() {
+            self.route_unsolicited(&frame, received);
+        }
+    }
+
+    /// Routes frames nobody requested (pushed attitude, function feedback, other unsolicited
+    /// frames, see `subscribe_events`) as they arrive. Without it they are routed only while a
+    /// request waits on the link, and new ones are dropped once `PUSHED_FRAME_CAPACITY` pile up.
+    ///
+    /// Run it alongside the client, e.g. in a `tokio::select!` with the code using the camera.
+    /// Returns when the link closes or fails.
+    pub async fn run_push_handler(&self) -> Result<(), Box<dyn Error>> {
+        let Ok(mut pushed) = self.pushed.try_lock() else {
+            return Err(A8MiniError::Rejected("Push handler already running.".into()).into());
+        };
+        while let Some((frame, received)) = pushed.recv().await {
+            self.route_unsolicited(&frame, received);
+        }
+        Err(A8MiniError::SocketClosed.into())
+    }
+
+    pub async fn get_attitude_information(
+        &self,
+    ) -> Result<control::A8MiniAtittude, Box<dyn Error>> {
+        self.query_attitude(None).await
+    }
+
+    /// Like `get_attitude_information`, failing once `deadline` passes.
+    pub async fn get_attitude_information_within(
+        &self,
+        deadline: deadline::Deadline,
+    ) -> Result<control::A8MiniAtittude, Box<dyn Error>> {
+        self.query_attitude(Some(deadline)).await
+    }
+
+    async fn query_attitude(
+        &self,
+        deadline: Option<deadline::Deadline>,
+    ) -> Result<control::A8MiniAtittude, Box<dyn Error>> {
+        let attitude_bytes = self
+            .request(
+                &control::A8MiniSimpleCommand::AttitudeInformation,
+                None,
+                deadline,
+            )
+            .await?;
+        let attitude_info = self.decode_attitude(&attitude_bytes)?;
+        let received = clock::now();
+        self.jitter.lock().unwrap().push(&attitude_info);
+        self.attitude_estimator
+            .lock()
+            .unwrap()
+            .observe(&attitude_info, received);
+        self.telemetry.lock().unwrap().attitude =
+            Some(telemetry::Timestamped::new(attitude_info, received));
+        self.check_deviation(&attitude_info, received);
+        self.attitude_watch
+            .send_replace(conventions::Attitude::from(&attitude_info));
+        Ok(attitude_info)
+    }
+
+    /// Queries the current zoom level.
+    pub async fn get_zoom(&self) -> Result<f32, Box<dyn Error>> {
+        Ok(self.get_current_zoom().await?.into())
+    }
+
+    /// Queries the current zoom level as a typed value, e.g. for display in a UI.
+    pub async fn get_current_zoom(&self) -> Result<zoom::ZoomLevel, Box<dyn Error>> {
+        let zoom_bytes = self
+            .send_command(control::A8MiniSimpleCommand::CurrentZoomInformation)
+            .await?;
+        let zoom = match ack_payload(&zoom_bytes)? {
+            [zoom_int, zoom_float, ..] => zoom::ZoomLevel::from_wire(*zoom_int, *zoom_float),
+            _ => return Err(A8MiniError::MalformedAck("Zoom ACK too short.".into()).into()),
+        };
+        self.record_zoom(zoom.value());
+        Ok(zoom)
+    }
+
+    /// Queries the highest zoom level of the camera, for models other than the A8 mini's 6x.
+    pub async fn get_max_zoom(&self) -> Result<f32, Box<dyn Error>> {
+        let max_zoom_bytes = self
+            .send_command(control::A8MiniSimpleCommand::MaxZoomInformation)
+            .await?;
+        match ack_payload(&max_zoom_bytes)? {
+            [zoom_int, zoom_float, ..] => Ok(zoom::from_wire(*zoom_int, *zoom_float)),
+            _ => Err(A8MiniError::MalformedAck("Max zoom ACK too short.".into()).into()),
+        }
+    }
+
+    /// Starts zooming in until `zoom_stop`. Returns the zoom level reported when the zoom started.
+    pub async fn zoom_in(&self) -> Result<f32, Box<dyn Error>> {
+        self.manual_zoom(control::A8MiniSimpleCommand::ZoomIn).await
+    }
+
+    /// Starts zooming out until `zoom_stop`. Returns the zoom level reported when the zoom started.
+    pub async fn zoom_out(&self) -> Result<f32, Box<dyn Error>> {
+        self.manual_zoom(control::A8MiniSimpleCommand::ZoomOut)
+            .await
+    }
+
+    /// Stops a manual zoom. Returns the zoom level the lens stopped at.
+    pub async fn zoom_stop(&self) -> Result<f32, Box<dyn Error>> {
+        self.manual_zoom(control::A8MiniSimpleCommand::ZoomStop)
+            .await
+    }
+
+    async fn manual_zoom(
+        &self,
+        command: control::A8MiniSimpleCommand,
+    ) -> Result<f32, Box<dyn Error>> {
+        let ack = self.send_command(command).await?;
+        let zoom = match ack_payload(&ack)? {
+            [low, high, ..] => zoom::from_tenths([*low, *high]),
+            _ => return Err(A8MiniError::MalformedAck("Manual zoom ACK too short.".into()).into()),
+        };
+        self.record_zoom(zoom);
+        Ok(zoom)
+    }
+
+    fn record_zoom(&self, zoom: f32) {
+        let sample = telemetry::Timestamped::new(zoom, clock::now());
+        self.telemetry.lock().unwrap().zoom = Some(sample);
+        let _ = self.zoom_samples.send(sample);
+    }
+
+    /// Triggers a single autofocus on the zoom camera, e.g. after a large zoom change. Fails if
+    /// the camera reports the focus was not started.
+    pub async fn autofocus(&self) -> Result<(), Box<dyn Error>> {
+        self.focus(control::A8MiniSimpleCommand::AutoFocus, "Autofocus")
+            .await
+    }
+
+    /// Starts moving the focus towards far until `focus_stop`, for targets where autofocus hunts.
+    pub async fn focus_far(&self) -> Result<(), Box<dyn Error>> {
+        self.focus(control::A8MiniSimpleCommand::FocusIn, "Manual focus")
+            .await
+    }
+
+    /// Starts moving the focus towards near until `focus_stop`.
+    pub async fn focus_near(&self) -> Result<(), Box<dyn Error>> {
+        self.focus(control::A8MiniSimpleCommand::FocusOut, "Manual focus")
+            .await
+    }
+
+    pub async fn focus_stop(&self) -> Result<(), Box<dyn Error>> {
+        self.focus(control::A8MiniSimpleCommand::FocusStop, "Manual focus")
+            .await
+    }
+
+    async fn focus(
+        &self,
+        command: control::A8MiniSimpleCommand,
+        name: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let ack = self.send_command(command).await?;
+        match ack_payload(&ack)?.first() {
+            Some(1) => Ok(()),
+            Some(_) => Err(A8MiniError::Other(format!(
+                "Camera reported {} failed.",
+                name.to_lowercase()
+            ))
+            .into()),
+            None => Err(A8MiniError::MalformedAck(format!("{} ACK has no status.", name)).into()),
+        }
+    }
+
+    /// Sets the zoom directly to `level`, rounded to tenths. Levels outside the A8 mini's 1x to 6x
+    /// range are rejected with `InvalidArgument` without sending anything.
+    pub async fn set_zoom(&self, level: f32) -> Result<(), Box<dyn Error>> {
+        let (zoom_int, zoom_float) = zoom::to_wire(zoom::validate(level)?);
+        let ack = self
+            .send_command(control::A8MiniComplexCommand::SetAbsoluteZoom(
+                zoom_int, zoom_float,
+            ))
+            .await?;
+        match ack_payload(&ack)?.first() {
+            Some(1) => Ok(()),
+            Some(_) => Err(A8MiniError::Other(format!("Camera rejected zoom {}x.", level)).into()),
+            None => {
+                Err(A8MiniError::MalformedAck("Absolute zoom ACK has no status.".into()).into())
+            }
+        }
+    }
+
+    /// Ramps the zoom to `target` at no more than `max_rate` zoom levels per second using
+    /// absolute zoom steps, then confirms arrival with a zoom query. Returns the confirmed level.
+    pub async fn zoom_to(&self, target: f32, max_rate: f32) -> Result<f32, Box<dyn Error>> {
+        correlation::correlated(async {
+            let target = target.clamp(constants::MIN_ZOOM, constants::MAX_ZOOM);
+            let current = self.get_zoom().await?;
+
+            let mut interval = tokio::time::interval(constants::ZOOM_STEP_INTERVAL);
+            for level in zoom::ramp(current, target, max_rate, constants::ZOOM_STEP_INTERVAL) {
+                interval.tick().await;
+                let (zoom_int, zoom_float) = zoom::to_wire(level);
+                self.send_command_blind(control::A8MiniComplexCommand::SetAbsoluteZoom(
+                    zoom_int, zoom_float,
+                ))
+                .await?;
+            }
+            interval.tick().await;
+
+            let confirmed = self.get_zoom().await?;
+            if (confirmed - target).abs() > constants::ZOOM_TOLERANCE {
+                return Err(A8MiniError::Other(format!(
+                    "Zoom settled at {} instead of {}.",
+                    confirmed, target
+                ))
+                .into());
+            }
+            log_line!("[ZOOM] Reached {}x.", confirmed);
+            Ok(confirmed)
+        })
+        .await
+    }
+
+    pub async fn get_encoding_params(
+        &self,
+        stream_type: control::StreamType,
+    ) -> Result<control::EncodingParams, Box<dyn Error>> {
+        let params_bytes = self
+            .send_command(control::A8MiniComplexCommand::GetEncodingParams(
+                stream_type,
+            ))
+            .await?;
+        control::EncodingParams::from_payload(ack_payload(&params_bytes)?)
+    }
+
+    /// Validates and applies the encoding parameters of a stream.
+    pub async fn set_encoding_params(
+        &self,
+        params: control::EncodingParams,
+    ) -> Result<(), Box<dyn Error>> {
+        params.validate()?;
+        let ack_bytes = self
+            .send_command(control::A8MiniComplexCommand::SetEncodingParams(params))
+            .await?;
+        match ack_payload(&ack_bytes)? {
+            [_, 1, ..] => Ok(()),
+            [_, _, ..] => {
+                Err(A8MiniError::Other("Camera rejected encoding parameters.".into()).into())
+            }
+            _ => Err(A8MiniError::MalformedAck("Encoding parameter ACK too short.".into()).into()),
+        }
+    }
+
+    /// Changes the bitrate of a stream, keeping its codec and resolution.
+    pub async fn set_stream_bitrate(
+        &self,
+        stream_type: control::StreamType,
+        bitrate_kbps: u16,
+    ) -> Result<(), Box<dyn Error>> {
+        let params = self.get_encoding_params(stream_type).await?;
+        self.set_encoding_params(control::EncodingParams {
+            bitrate_kbps,
+            ..params
+        })
+        .await
+    }
+
+    pub async fn get_network_config(&self) -> Result<control::NetworkConfig, Box<dyn Error>> {
+        let config_bytes = self
+            .send_command(control::A8MiniSimpleCommand::NetworkInformation)
+            .await?;
+        control::NetworkConfig::from_payload(ack_payload(&config_bytes)?)
+    }
+
+    /// Points both sockets at a new camera address, keeping the camera ports, then re-runs the
+    /// init sequence.
+    pub async fn reconnect_to(
+        &self,
+        camera_ip: Ipv4Addr,
+    ) -> Result<init::InitReport, Box<dyn Error>> {
+        let command_socket = self.udp_command_socket()?;
+        let command_port = command_socket.peer_addr()?.port();
+        let http_port = self.http_socket.peer_addr()?.port();
+        command_socket.connect((camera_ip, command_port)).await?;
+        self.http_socket.connect((camera_ip, http_port)).await?;
+        log_line!("[NETWORK] Reconnected to {}.", camera_ip);
+
+        Ok(self.run_init_sequence().await)
+    }
+
+    /// Changes the camera's network configuration, waits for it to apply, reconnects at the new
+    /// address and confirms the change by reading the configuration back.
+    pub async fn change_network_config(
+        &self,
+        config: control::NetworkConfig,
+    ) -> Result<init::InitReport, Box<dyn Error>> {
+        correlation::correlated(async {
+            self.send_command_blind(control::A8MiniComplexCommand::SetNetworkConfig(config))
+                .await?;
+            tokio::time::sleep(constants::IP_CHANGE_DELAY).await;
+
+            let report = self.reconnect_to(config.ip).await?;
+            let applied = self.get_network_config().await?;
+            if applied != config {
+                return Err(A8MiniError::Other(format!(
+                    "Camera reports {:?} instead of {:?}.",
+                    applied, config
+                ))
+                .into());
+            }
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Queries and decodes the gimbal configuration information.
+    pub async fn get_gimbal_info(&self) -> Result<control::GimbalInfo, Box<dyn Error>> {
+        let config_bytes = self
+            .send_command(control::A8MiniSimpleCommand::CameraInformation)
+            .await?;
+        let gimbal_info = control::GimbalInfo::from_payload(ack_payload(&config_bytes)?)?;
+        let sample = telemetry::Timestamped::new(gimbal_info, clock::now());
+        self.telemetry.lock().unwrap().gimbal_info = Some(sample);
+        let _ = self.gimbal_info_samples.send(sample);
+        Ok(gimbal_info)
+    }
+
+    /// Sets the camera clock to `time`, so recorded media and photo EXIF timestamps match the
+    /// host clock for post-flight alignment.
+    pub async fn set_utc_time(&self, time: std::time::SystemTime) -> Result<(), Box<dyn Error>> {
+        let unix_us = time
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|_| A8MiniError::InvalidArgument("Time is before the UNIX epoch.".into()))?
+            .as_micros() as u64;
+        let ack = self
+            .send_command(control::A8MiniComplexCommand::SetUtcTime(unix_us))
+            .await?;
+        match ack_payload(&ack)?.first() {
+            Some(1) => Ok(()),
+            Some(_) => Err(A8MiniError::Other("Camera rejected the UTC time.".into()).into()),
+            None => Err(A8MiniError::MalformedAck("UTC time ACK has no status.".into()).into()),
+        }
+    }
+
+    /// Queries whether the camera is recording, so mission software can verify video is rolling
+    /// before takeoff. A missing TF card is reported as `RecordingStatus::NoCard`.
+    pub async fn is_recording(&self) -> Result<control::RecordingStatus, Box<dyn Error>> {
+        Ok(self.get_gimbal_info().await?.recording)
+    }
+
+    /// Queries the current gimbal motion mode from the gimbal configuration information.
+    pub async fn get_motion_mode(&self) -> Result<control::GimbalMode, Box<dyn Error>> {
+        Ok(self.get_gimbal_info().await?.motion_mode)
+    }
+
+    /// Switches the gimbal to lock, follow or FPV mode and confirms the change by reading the
+    /// gimbal configuration back, since the photo function command is not acknowledged.
+    pub async fn set_motion_mode(&self, mode: control::GimbalMode) -> Result<(), Box<dyn Error>> {
+        correlation::correlated(async {
+            self.send_command_blind(mode.command()).await?;
+            let applied = self.get_motion_mode().await?;
+            if applied != mode {
+                return Err(A8MiniError::Other(format!(
+                    "Gimbal reports {:?} mode instead of {:?}.",
+                    applied, mode
+                ))
+                .into());
+            }
+            log_line!("[MODE] Gimbal in {:?} mode.", mode);
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn get_firmware_version(&self) -> Result<control::FirmwareVersion, Box<dyn Error>> {
+        let version_bytes = self
+            .send_command(control::A8MiniSimpleCommand::FirmwareVersionInformation)
+            .await?;
+        control::FirmwareVersion::from_payload(ack_payload(&version_bytes)?)
+    }
+
+    /// Detects the camera model from its hardware ID and returns the features it supports, so
+    /// unsupported actions can be hidden. The model is queried once and cached.
+    pub async fn capabilities(&self) -> Result<models::Capabilities, Box<dyn Error>> {
+        if let Some(model) = *self.model.lock().unwrap() {
+            return Ok(model.capabilities());
+        }
+
+        let model = self.get_hardware_id().await?.model;
+        log_line!("[INIT] Detected camera model {:?}.", model);
+        Ok(model.capabilities())
+    }
+
+    /// Queries the hardware ID, which identifies both the model and the individual unit.
+    pub async fn get_hardware_id(&self) -> Result<models::HardwareId, Box<dyn Error>> {
+        let hardware_id_bytes = self
+            .send_command(control::A8MiniSimpleCommand::HardwareIDInformation)
+            .await?;
+        let hardware_id = models::HardwareId::from_payload(ack_payload(&hardware_id_bytes)?)?;
+        *self.model.lock().unwrap() = Some(hardware_id.model);
+        Ok(hardware_id)
+    }
+
+    /// Queries firmware and hardware ID, detects the model and probes the features it may have,
+    /// in one report for inventory. Fails only when the camera cannot be identified; probes that
+    /// go unanswered are recorded as such.
+    pub async fn camera_report(&self) -> Result<report::CameraReport, Box<dyn Error>> {
+        correlation::correlated(async {
+            let hardware_id = self.get_hardware_id().await?;
+            let firmware = self.get_firmware_version().await?;
+            let capabilities = hardware_id.model.capabilities();
+
+            let max_zoom = self.get_max_zoom().await.ok();
+            let probes = [
+                ("max_zoom", max_zoom.is_some()),
+                ("image_mode", self.get_image_mode().await.is_ok()),
+                (
+                    "laser_rangefinder",
+                    self.send_command(control::A8MiniSimpleCommand::LaserRangefinderInformation)
+                        .await
+                        .is_ok(),
+                ),
+                ("gimbal_info", self.get_gimbal_info().await.is_ok()),
+            ];
+            log_line!(
+                "[INIT] {:?} {} reported, firmware {}.",
+                hardware_id.model,
+                hardware_id,
+                firmware.camera
+            );
+            Ok(report::CameraReport {
+                hardware_id: hardware_id.id,
+                model: hardware_id.model,
+                firmware,
+                capabilities,
+                image_modes: capabilities.image_modes(),
+                probes: probes.into_iter().collect(),
+                max_zoom,
+            })
+        })
+        .await
+    }
+
+    /// Switches HDR on or off. The SDK only offers an HDR toggle, so the current state is
+    /// queried first and the change is confirmed by querying again. Returns the confirmed
+    /// gimbal configuration, which also refreshes the cached state.
+    pub async fn set_hdr(&self, enabled: bool) -> Result<control::GimbalInfo, Box<dyn Error>> {
+        let gimbal_info = self.get_gimbal_info().await?;
+        if gimbal_info.hdr == enabled {
+            return Ok(gimbal_info);
+        }
+
+        self.send_command_blind(control::A8MiniSimpleCommand::ToggleHDR)
+            .await?;
+        let gimbal_info = self.get_gimbal_info().await?;
+        if gimbal_info.hdr != enabled {
+            return Err(A8MiniError::Other(format!(
+                "HDR is still {}.",
+                if enabled { "off" } else { "on" }
+            ))
+            .into());
+        }
+        log_line!("[COMMAND] HDR {}.", if enabled { "on" } else { "off" });
+        Ok(gimbal_info)
+    }
+
+    /// Queries which sensors currently feed the main and sub streams.
+    pub async fn get_image_mode(&self) -> Result<control::ImageMode, Box<dyn Error>> {
+        let mode_bytes = self
+            .send_command(control::A8MiniSimpleCommand::ImageModeInformation)
+            .await?;
+        let mode = *ack_payload(&mode_bytes)?
+            .first()
+            .ok_or_else(|| A8MiniError::MalformedAck("Image mode ACK too short.".into()))?;
+        Ok(control::ImageMode::from_u8(mode)
+            .ok_or_else(|| A8MiniError::MalformedAck("Unknown image mode.".into()))?)
+    }
+
+    /// Selects which sensors feed the main and sub streams, refusing modes the detected model
+    /// has no sensors for.
+    pub async fn set_image_mode(&self, mode: control::ImageMode) -> Result<(), Box<dyn Error>> {
+        let capabilities = self.capabilities().await?;
+        if !capabilities.supports_image_mode(mode) {
+            return Err(A8MiniError::InvalidArgument(format!(
+                "{:?} does not support image mode {:?}.",
+                capabilities.model, mode
+            ))
+            .into());
+        }
+        self.send_command_blind(control::A8MiniComplexCommand::SetImageMode(mode))
+            .await
+    }
+
+    /// Arranges the video streams: `main` on the main stream (one sensor, or two side by side)
+    /// and `sub` on the sub stream. Fails with `InvalidArgument` for arrangements the protocol
+    /// has no image mode for.
+    pub async fn set_stream_layout(
+        &self,
+        main: &[control::Sensor],
+        sub: control::Sensor,
+    ) -> Result<control::ImageMode, Box<dyn Error>> {
+        let mode = control::ImageMode::from_layout(main, sub).ok_or_else(|| {
+            A8MiniError::InvalidArgument(format!(
+                "No image mode shows {:?} with {:?} on the sub stream.",
+                main, sub
+            ))
+        })?;
+        self.set_image_mode(mode).await?;
+        Ok(mode)
+    }
+
+    /// Traffic counters and response times broken down by CMD_ID, e.g. to show that attitude
+    /// queries are answered while captures time out. `render_prometheus` exports them.
+    pub fn command_metrics(&self) -> metrics::CommandBreakdown {
+        self.command_metrics.lock().unwrap().clone()
+    }
+
+    /// Returns the latest cached attitude, zoom, gimbal configuration, storage and link state
+    /// without querying the camera. Each value carries the time it was received; values never
+    /// received are `None`.
+    pub fn snapshot(&self) -> telemetry::TelemetrySnapshot {
+        let cache = *self.telemetry.lock().unwrap();
+        let taken = clock::now();
+        telemetry::TelemetrySnapshot {
+            taken,
+            attitude: cache.attitude,
+            zoom: cache.zoom,
+            gimbal_info: cache.gimbal_info,
+            storage: self.storage_status(),
+            link_state: if self.is_link_down() {
+                breaker::LinkState::Down
+            } else {
+                breaker::LinkState::Up
+            },
+            link: cache.link,
+            jitter: self.jitter_score(),
+            estimate: self.attitude_estimator.lock().unwrap().estimate(taken),
+        }
+    }
+
+    /// Returns a receiver holding the latest attitude in degrees, in the configured angle
+    /// convention. It is updated by every attitude the client receives, pushed (see
+    /// `run_push_handler` and `stream_attitude`) or queried, so a control loop can read it each
+    /// iteration without a request. It reads zero until the first attitude arrives.
+    pub fn attitude_watch(&self) -> watch::Receiver<conventions::Attitude> {
+        self.attitude_watch.subscribe()
+    }
+
+    /// Subscribes to attitude frames published by `stream_attitude`, receiving at most one sample
+    /// per `period`. A zero period receives every sample.
+    pub fn subscribe_attitude(
+        &self,
+        period: std::time::Duration,
+    ) -> telemetry::AttitudeSubscription {
+        telemetry::AttitudeSubscription::new(self.attitude_samples.subscribe(), period)
+    }
+
+    /// Subscribes to zoom levels reported by zoom queries and manual zoom commands, receiving
+    /// at most one per `period`.
+    pub fn subscribe_zoom(
+        &self,
+        period: std::time::Duration,
+    ) -> telemetry::Subscription<telemetry::Timestamped<f32>> {
+        telemetry::Subscription::new(self.zoom_samples.subscribe(), period)
+    }
+
+    /// Subscribes to gimbal state (motion mode, recording, mounting) from `get_gimbal_info`,
+    /// receiving at most one per `period`.
+    pub fn subscribe_gimbal_info(
+        &self,
+        period: std::time::Duration,
+    ) -> telemetry::Subscription<telemetry::Timestamped<control::GimbalInfo>> {
+        telemetry::Subscription::new(self.gimbal_info_samples.subscribe(), period)
+    }
+
+    /// Subscribes to the link counters, updated after every request, receiving at most one per
+    /// `period`.
+    pub fn subscribe_link_stats(
+        &self,
+        period: std::time::Duration,
+    ) -> telemetry::Subscription<telemetry::Timestamped<telemetry::LinkStats>> {
+        telemetry::Subscription::new(self.link_samples.subscribe(), period)
+    }
+
+    /// Asks the gimbal to push attitude at `rate` and publishes every pushed frame to attitude
+    /// subscribers, feeding the jitter meter as well. Runs until the link fails. Requests issued
+    /// while it runs are answered as usual.
+    pub async fn stream_attitude(
+        &self,
+        rate: control::AttitudeStreamRate,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut frames = self.tap_attitude();
+        self.send_command_blind(control::A8MiniComplexCommand::RequestAttitudeStream(rate))
+            .await?;
+        loop {
+            let (frame, received) = self.recv_tapped(&mut frames).await?;
+            self.accept_pushed_attitude(&frame, received);
+        }
+    }
+
+    /// Asks the gimbal to push attitude at `rate` and yields every pushed frame as it arrives,
+    /// instead of polling with `get_attitude_information`. Frames are also published to
+    /// attitude subscribers. The stream ends when the link fails.
+    pub async fn attitude_stream(
+        &self,
+        rate: control::AttitudeStreamRate,
+    ) -> Result<telemetry::AttitudeStream<'_>, Box<dyn Error>> {
+        let frames = self.tap_attitude();
+        self.send_command_blind(control::A8MiniComplexCommand::RequestAttitudeStream(rate))
+            .await?;
+        Ok(telemetry::AttitudeStream::new(self, frames))
+    }
+
+    /// Takes the pushed attitude frames until the receiver is dropped. Taken before the stream is
+    /// requested, so no frame of it arrives unclaimed.
+    fn tap_attitude(&self) -> mpsc::Receiver<(Vec<u8>, Instant)> {
+        self.demux.tap(
+            constants::ATTITUDE_CMD_ID,
+            constants::ATTITUDE_CHANNEL_CAPACITY,
+        )
+    }
+
+    /// Decodes and publishes a pushed attitude frame taken from an attitude tap. A malformed
+    /// frame is logged and skipped.
+    fn accept_pushed_attitude(
+        &self,
+        frame: &[u8],
+        received: Instant,
+    ) -> Option<telemetry::AttitudeSample> {
+        match self.decode_attitude(frame) {
+            Ok(attitude) => {
+                self.publish_attitude(attitude, received);
+                Some(telemetry::AttitudeSample { attitude, received })
+            }
+            Err(e) => {
+                log_line!("[ATTITUDE] Malformed attitude frame: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Decodes an attitude frame into the airframe's frame and the angle convention.
+    fn decode_attitude(&self, frame: &[u8]) -> Result<control::A8MiniAtittude, Box<dyn Error>> {
+        let attitude: control::A8MiniAtittude = deserialize(ack_payload(frame)?)?;
+        let attitude = self.calibration.attitude_from_gimbal(&attitude);
+        Ok(self.angle_convention.attitude_from_gimbal(&attitude))
+    }
+
+    /// Records a pushed attitude frame in the telemetry cache and jitter meter and publishes it
+    /// to attitude subscribers. A frame no newer than the cached attitude was already published,
+    /// or is stale, and is ignored.
+    fn publish_attitude(&self, attitude: control::A8MiniAtittude, received: Instant) {
+        {
+            let telemetry = self.telemetry.lock().unwrap();
+            if telemetry
+                .attitude
+                .is_some_and(|cached| cached.at >= received)
+            {
+                return;
+            }
+        }
+        self.jitter.lock().unwrap().push(&attitude);
+        self.attitude_estimator
+            .lock()
+            .unwrap()
+            .observe(&attitude, received);
+        self.telemetry.lock().unwrap().attitude =
+            Some(telemetry::Timestamped::new(attitude, received));
+        self.check_deviation(&attitude, received);
+        self.attitude_watch
+            .send_replace(conventions::Attitude::from(&attitude));
+        // No subscribers is not an error.
+        let _ = self
+            .attitude_samples
+            .send(telemetry::AttitudeSample { attitude, received });
+    }
+
+    /// Handles a valid frame that answers no request, tap or blind command, such as a pushed data
+    /// stream or status frame. Pushed attitude still reaches attitude subscribers; every such
+    /// frame is emitted as an `UnsolicitedFrame` event. Requests register before sending, so a
+    /// frame that arrives here is never returned to one.
+    fn route_unsolicited(&self, frame: &[u8], received: Instant) {
+        let Ok(data) = ack_payload(frame) else {
+            return;
+        };
+        let cmd_id = frame[7];
+        log_line!(
+            "[PUSH] Unsolicited frame CMD_ID {:#04x} SEQ {}.",
+            cmd_id,
+            checksum::seq(frame)
+        );
+        if cmd_id == constants::ATTITUDE_CMD_ID {
+            if let Ok(attitude) = self.decode_attitude(frame) {
+                self.publish_attitude(attitude, received);
+            }
+        }
+        self.emit(events::A8MiniEvent::UnsolicitedFrame {
+            cmd_id,
+            data: data.to_vec(),
+        });
+    }
+
+    /// Measures command-to-motion latency for tuning outer control loops. `steps` yaw steps of
+    /// `step` decidegrees, alternating in direction, are issued from the current attitude, and the
+    /// latency is the time from sending a step to the first pushed attitude that moved by
+    /// `LATENCY_ONSET_THRESHOLD`. Each step is followed by `settle` for the gimbal to come to rest.
+    /// Requires `stream_attitude` to be running; steps without motion within
+    /// `LATENCY_STEP_TIMEOUT` are counted as missed.
+    pub async fn profile_latency(
+        &self,
+        steps: usize,
+        step: i16,
+        settle: std::time::Duration,
+    ) -> Result<latency::LatencyReport, Box<dyn Error>> {
+        correlation::correlated(async {
+            let mut subscription = self.subscribe_attitude(std::time::Duration::ZERO);
+            let mut baseline = self
+                .telemetry
+                .lock()
+                .unwrap()
+                .attitude
+                .ok_or_else(|| {
+                    A8MiniError::Other(
+                        "No attitude telemetry yet, start stream_attitude first.".into(),
+                    )
+                })?
+                .value;
+
+            let mut samples = Vec::new();
+            let mut missed = 0;
+            for index in 0..steps {
+                let direction = if index % 2 == 0 { 1 } else { -1 };
+                let command = control::A8MiniComplexCommand::SetYawPitchAngle(
+                    DeciDeg(baseline.theta_yaw.saturating_add(step * direction)),
+                    DeciDeg(baseline.theta_pitch),
+                );
+                let sent = self
+                    .dispatch(&command, None, Reply::Blind)
+                    .await?
+                    .into_sent()?
+                    .at
+                    .monotonic;
+
+                let onset = tokio::time::timeout(constants::LATENCY_STEP_TIMEOUT, async {
+                    while let Some(sample) = subscription.recv().await {
+                        if sample.received >= sent
+                            && latency::motion_onset(
+                                &baseline,
+                                &sample.attitude,
+                                constants::LATENCY_ONSET_THRESHOLD,
+                            )
+                        {
+                            return Some(sample.received - sent);
+                        }
+                    }
+                    None
+                })
+                .await;
+                match onset {
+                    Ok(Some(latency)) => {
+                        log_line!("[LATENCY] Step {}: motion after {:?}.", index, latency);
+                        samples.push(latency);
+                    }
+                    Ok(None) | Err(_) => {
+                        log_line!("[LATENCY] Step {}: no motion seen.", index);
+                        missed += 1;
+                    }
+                }
+
+                // The last sample before the next step is its baseline.
+                let settled = clock::now() + settle;
+                while let Ok(Some(sample)) =
+                    tokio::time::timeout_at(settled.into(), subscription.recv()).await
+                {
+                    baseline = sample.attitude;
+                }
+            }
+
+            let report = latency::LatencyReport::new(samples, missed);
+            log_line!("[LATENCY] {:?}, {} missed.", report.stats, report.missed);
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Queries the gimbal configuration information and updates the cached TF card status,
+    /// emitting a `StorageChanged` event when it differs from the previous value.
+    pub async fn check_storage(&self) -> Result<control::StorageStatus, Box<dyn Error>> {
+        let current: control::StorageStatus = self.get_gimbal_info().await?.recording.into();
+
+        let previous = std::mem::replace(&mut *self.storage_status.lock().unwrap(), current);
+        if previous != current {
+            log_line!(
+                "[STORAGE] Status changed from {:?} to {:?}.",
+                previous,
+                current
+            );
+            self.emit(events::A8MiniEvent::StorageChanged { previous, current });
+        }
+
+        Ok(current)
+    }
+
+    /// Last TF card status seen by `check_storage`.
+    pub fn storage_status(&self) -> control::StorageStatus {
+        *self.storage_status.lock().unwrap()
+    }
+
+    /// Polls the TF card status forever at the given period. Query failures are logged and skipped.
+    /// While the circuit breaker is tripped the poll is replaced by a link probe.
+    pub async fn monitor_storage(&self, period: std::time::Duration) {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            if self.is_link_down() {
+                if let Err(e) = self.probe_link().await {
+                    log_line!("[LINK] Probe failed: {}", e);
+                }
+                continue;
+            }
+            if let Err(e) = self.check_storage().await {
+                log_line!("[STORAGE] Status query failed: {}", e);
+            }
+        }
+    }
+
+    /// Sets the action applied when no motion command has been sent for the policy's timeout.
+    /// Only motion commands count as activity, so background polling does not keep a stalled
+    /// gimbal moving. The policy is enforced by `enforce_idle_policy`. `None` disables it.
+    pub fn set_idle_policy(&mut self, policy: Option<idle::IdlePolicy>) {
+        self.idle = policy.map(|policy| Mutex::new(idle::IdleMonitor::new(policy, clock::now())));
+    }
+
+    pub fn idle_policy(&self) -> Option<idle::IdlePolicy> {
+        self.idle.as_ref().map(|idle| idle.lock().unwrap().policy())
+    }
+
+    /// Emits `AttitudeDeviation` when attitude telemetry stays off the last angle setpoint for
+    /// longer than the policy allows, e.g. a stalled motor or an obstructed gimbal. Attitude is
+    /// checked as it arrives, from queries or a running attitude stream. `None` disables it.
+    pub fn set_deviation_policy(&mut self, policy: Option<deviation::DeviationPolicy>) {
+        self.deviation = policy.map(|policy| Mutex::new(deviation::DeviationMonitor::new(policy)));
+    }
+
+    pub fn deviation_policy(&self) -> Option<deviation::DeviationPolicy> {
+        self.deviation
+            .as_ref()
+            .map(|deviation| deviation.lock().unwrap().policy())
+    }
+
+    fn check_deviation(&self, attitude: &control::A8MiniAtittude, received: Instant) {
+        let Some(deviation) = &self.deviation else {
+            return;
+        };
+        let (theta_yaw, theta_pitch) =
+            self.setpoint_to_gimbal(attitude.theta_yaw, attitude.theta_pitch);
+        let alert = deviation
+            .lock()
+            .unwrap()
+            .observe(theta_yaw, theta_pitch, received);
+        if let Some(alert) = alert {
+            log_line!(
+                "[DEVIATION] Attitude {:?} off setpoint {:?} for {:?}.",
+                alert.attitude,
+                alert.setpoint,
+                alert.duration
+            );
+            self.emit(events::A8MiniEvent::AttitudeDeviation(alert));
+        }
+    }
+
+    /// Applies the idle policy forever, once per idle period. Returns immediately if no policy is
+    /// set. Failures to apply the action are logged and retried after the next idle period.
+    pub async fn enforce_idle_policy(&self) {
+        let Some(idle) = &self.idle else {
+            return;
+        };
+        let policy = idle.lock().unwrap().policy();
+        loop {
+            let wait = idle
+                .lock()
+                .unwrap()
+                .remaining(clock::now())
+                .unwrap_or(policy.timeout);
+            tokio::time::sleep(wait).await;
+
+            let Some(action) = idle.lock().unwrap().expire(clock::now()) else {
+                continue;
+            };
+            log_line!(
+                "[IDLE] No motion for {:?}, applying {:?}.",
+                policy.timeout,
+                action
+            );
+            let result = match action {
+                idle::IdleAction::StopRotation => {
+                    self.send_command_blind(control::A8MiniSimpleCommand::StopRotation)
+                        .await
+                }
+                idle::IdleAction::Recenter => {
+                    self.send_command_blind(control::A8MiniSimpleCommand::AutoCenter)
+                        .await
+                }
+                idle::IdleAction::Park { yaw, pitch } => {
+                    self.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+                        DeciDeg(yaw),
+                        DeciDeg(pitch),
+                    ))
+                    .await
+                }
+            };
+            match result {
+                Ok(()) => idle.lock().unwrap().hold(),
+                Err(e) => log_line!("[IDLE] Idle action failed: {}", e),
+            }
+        }
+    }
+
+    /// Polls the firmware version forever at the given period. Whenever the gimbal answers after
+    /// `defaults.silence` without a response (or for the first time), it is treated as freshly
+    /// booted: the default mode is applied, the gimbal is optionally centered, and a
+    /// `GimbalBooted` event is emitted. While the circuit breaker is tripped the poll is a link
+    /// probe, as in `monitor_storage`.
+    pub async fn apply_defaults_on_boot(
+        &self,
+        defaults: boot::BootDefaults,
+        period: std::time::Duration,
+    ) {
+        let mut detector = boot::BootDetector::new(defaults.silence);
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            let responded = if self.is_link_down() {
+                self.probe_link().await.is_ok()
+            } else {
+                self.send_command(control::A8MiniSimpleCommand::FirmwareVersionInformation)
+                    .await
+                    .is_ok()
+            };
+            if !detector.observe(responded, clock::now()) {
+                continue;
+            }
+
+            log_line!("[BOOT] Gimbal boot detected, applying {:?}.", defaults);
+            for command in defaults.commands() {
+                if let Err(e) = self.send_command_blind(command).await {
+                    log_line!("[BOOT] {:?} failed: {}", command, e);
+                }
+            }
+            self.emit(events::A8MiniEvent::GimbalBooted);
+        }
+    }
+
+    /// Records in segments, restarting the recording every `rotation.segment_length` until `stop`
+    /// is set to `true` or its sender is dropped. Starts and stops go through `start_recording`
+    /// and `stop_recording`, so a recording already running or a lost toggle does not invert the
+    /// segments. Each finished segment is emitted as a `RecordingSegmentClosed` event and returned
+    /// in order. On error, the recording state is whatever the failed start or stop left it in.
+    pub async fn rotate_recording(
+        &self,
+        rotation: recording::SegmentRotation,
+        mut stop: watch::Receiver<bool>,
+    ) -> Result<Vec<recording::RecordingSegment>, Box<dyn Error>> {
+        let mut segments = Vec::new();
+
+        for index in 0.. {
+            if *stop.borrow() {
+                break;
+            }
+            self.start_recording().await?;
+            let started = std::time::SystemTime::now();
+            log_line!("[RECORDING] Segment {} started.", index);
+
+            let stop_requested = tokio::select! {
+                _ = tokio::time::sleep(rotation.segment_length) => false,
+                _ = stop.wait_for(|stop| *stop) => true,
+            };
+
+            self.stop_recording().await?;
+            let segment = recording::RecordingSegment {
+                index,
+                started,
+                stopped: std::time::SystemTime::now(),
+            };
+            log_line!("[RECORDING] Segment {} stopped.", index);
+            self.emit(events::A8MiniEvent::RecordingSegmentClosed(segment));
+            segments.push(segment);
+
+            if stop_requested {
+                break;
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(rotation.restart_gap) => {}
+                _ = stop.wait_for(|stop| *stop) => break,
+            }
+        }
+
+        Ok(segments)
+    }
+
+    /// Rolling jitter score over the most recent attitude samples.
+    /// Samples are collected by `get_attitude_information`, so poll it at a high rate.
+    pub fn jitter_score(&self) -> Option<telemetry::JitterScore> {
+        self.jitter.lock().unwrap().score()
+    }
+
+    pub fn reset_jitter(&self) {
+        self.jitter.lock().unwrap().clear();
+    }
+
+    /// Holds the camera on an earth-fixed target while the vehicle moves, re-issuing the
+    /// angle setpoint every time a new vehicle attitude is published.
+    /// Returns once the vehicle attitude sender is dropped.
+    pub async fn hold_earth_frame(
+        &self,
+        target: compensation::EarthTarget,
+        mut vehicle_attitude: watch::Receiver<compensation::VehicleAttitude>,
+    ) -> Result<(), Box<dyn Error>> {
+        loop {
+            let vehicle = *vehicle_attitude.borrow_and_update();
+            let (theta_yaw, theta_pitch) = compensation::earth_frame_setpoint(target, vehicle);
+            let (theta_yaw, theta_pitch) = self
+                .angle_convention
+                .setpoint_from_gimbal(theta_yaw, theta_pitch);
+            self.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+                DeciDeg(theta_yaw),
+                DeciDeg(theta_pitch),
+            ))
+            .await?;
+
+            if vehicle_attitude.changed().await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Keeps the camera locked on a ground point, typically the center of an orbit, while the
+    /// vehicle moves around it. Every published vehicle state is turned into a bearing and
+    /// elevation to the point and compensated for the vehicle attitude. Setpoints go through the
+    /// usual pacing and soft limits, so a fast autopilot stream does not flood the link.
+    /// Returns once the vehicle state sender is dropped.
+    pub async fn stare_at(
+        &self,
+        point: compensation::GeoPoint,
+        mut vehicle: watch::Receiver<compensation::VehicleState>,
+    ) -> Result<(), Box<dyn Error>> {
+        correlation::correlated(async {
+            loop {
+                let state = *vehicle.borrow_and_update();
+                let target = compensation::direction_to(state.position, point);
+                let (theta_yaw, theta_pitch) =
+                    compensation::earth_frame_setpoint(target, state.attitude);
+                let (theta_yaw, theta_pitch) = self
+                    .angle_convention
+                    .setpoint_from_gimbal(theta_yaw, theta_pitch);
+                self.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+                    DeciDeg(theta_yaw),
+                    DeciDeg(theta_pitch),
+                ))
+                .await?;
+
+                if vehicle.changed().await.is_err() {
+                    return Ok(());
+                }
+            }
+        })
+        .await
+    }
+
+    /// Joystick-style control without drift: yaw and pitch rates published on `rates` (degrees
+    /// per second, gimbal frame) are integrated into an angle setpoint, starting from the last
+    /// known attitude, and the gimbal holds that setpoint with its own angle loop. The setpoint is
+    /// re-sent at most every `ANGLE_SETPOINT_MIN_INTERVAL`, and only when it moved.
+    /// Returns once the rate sender is dropped.
+    pub async fn run_rate_hybrid(
+        &self,
+        mut rates: watch::Receiver<(f32, f32)>,
+    ) -> Result<(), Box<dyn Error>> {
+        correlation::correlated(async {
+            let cached = self.telemetry.lock().unwrap().attitude;
+            let attitude = match cached {
+                Some(attitude) => attitude.value,
+                None => self.query_attitude(None).await?,
+            };
+            let mut integrator = hybrid::RateIntegrator::new(
+                attitude.theta_yaw as f32 / 10.0,
+                attitude.theta_pitch as f32 / 10.0,
+                clock::now(),
+            );
+            let (yaw_rate, pitch_rate) = *rates.borrow_and_update();
+            integrator.set_rates(yaw_rate, pitch_rate, clock::now());
+
+            let mut last_sent = Some(integrator.setpoint());
+            let mut interval = tokio::time::interval(constants::ANGLE_SETPOINT_MIN_INTERVAL);
+            loop {
+                tokio::select! {
+                    changed = rates.changed() => {
+                        if changed.is_err() {
+                            return Ok(());
+                        }
+                        let (yaw_rate, pitch_rate) = *rates.borrow_and_update();
+                        integrator.set_rates(yaw_rate, pitch_rate, clock::now());
+                        continue;
+                    }
+                    _ = interval.tick() => {}
+                }
+
+                integrator.advance(clock::now());
+                let setpoint = integrator.setpoint();
+                if last_sent == Some(setpoint) {
+                    continue;
+                }
+                self.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+                    DeciDeg(setpoint.0),
+                    DeciDeg(setpoint.1),
+                ))
+                .await?;
+                last_sent = Some(setpoint);
+            }
+        })
+        .await
+    }
+
+    /// Keeps the camera pointed at a fixed compass bearing while the vehicle turns, re-issuing
+    /// the yaw setpoint every time a new vehicle heading (degrees) is published. `theta_pitch`
+    /// is in the configured angle convention. Returns once the heading sender is dropped.
+    pub async fn hold_bearing(
+        &self,
+        bearing: f32,
+        theta_pitch: i16,
+        mut heading: watch::Receiver<f32>,
+    ) -> Result<(), Box<dyn Error>> {
+        loop {
+            let theta_yaw = self
+                .angle_convention
+                .yaw_from_gimbal(compensation::heading_setpoint(
+                    bearing,
+                    *heading.borrow_and_update(),
+                ));
+            self.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+                DeciDeg(theta_yaw),
+                DeciDeg(theta_pitch),
+            ))
+            .await?;
+
+            if heading.changed().await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Triggers a photo or video capture and gathers its provenance. The trigger time is taken
+    /// when the frame is handed to the socket. The camera's function feedback is then awaited
+    /// briefly to timestamp the confirmation, and the gimbal attitude is queried; either is left
+    /// empty if it does not arrive or `deadline` passes.
+    pub async fn capture_with_metadata(
+        &self,
+        kind: capture::CaptureKind,
+        vehicle: Option<compensation::VehicleAttitude>,
+        deadline: Option<deadline::Deadline>,
+    ) -> Result<capture::CaptureMetadata, Box<dyn Error>> {
+        correlation::correlated(async {
+            if let Some(deadline) = deadline {
+                deadline.check()?;
+            }
+            let command = kind.command();
+
+            let mut feedback = self.tap_function_feedback();
+            let sent = self
+                .dispatch(&command, None, Reply::Blind)
+                .instrument(command_span("send", command.to_bytes()[7]))
+                .await?
+                .sent();
+            let timing = match sent {
+                Some(SentFrame { at: sent, .. }) => {
+                    let feedback_timeout = deadline
+                        .map_or(constants::CAPTURE_FEEDBACK_TIMEOUT, |deadline| {
+                            deadline.cap(constants::CAPTURE_FEEDBACK_TIMEOUT)
+                        });
+                    let acked = self
+                        .recv_function_feedback(feedback_timeout, &mut feedback)
+                        .instrument(command_span("ack", constants::FUNCTION_FEEDBACK_CMD_ID))
+                        .await
+                        .ok()
+                        .map(|(received, _)| received);
+                    Some(capture::TriggerTiming { sent, acked })
+                }
+                None => None,
+            };
+            let attitude = self.query_attitude(deadline).await.ok();
+
+            // Not sent in dry-run mode or when queued; fall back to the current time.
+            let trigger = timing.map_or_else(capture::HostTimestamp::now, |timing| timing.sent);
+            Ok(capture::CaptureMetadata {
+                kind,
+                trigger_time_unix_ms: trigger.unix_us / 1000,
+                trigger_time_unix_us: trigger.unix_us,
+                ack_time_unix_us: timing
+                    .and_then(|timing| timing.acked)
+                    .map(|acked| trigger.unix_us_at(acked)),
+                timing,
+                seq: sent.map_or(0, |sent| sent.seq),
+                attitude,
+                zoom: self.telemetry.lock().unwrap().zoom.map(|zoom| zoom.value),
+                vehicle,
+            })
+        })
+        .await
+    }
+
+    /// Takes a photo and waits up to `BURST_SHOT_TIMEOUT` for the camera to report whether it was
+    /// saved, so a full or missing TF card shows up as `ShotOutcome::Failed`.
+    pub async fn take_picture(&self) -> Result<capture::ShotResult, Box<dyn Error>> {
+        correlation::correlated(self.shoot()).await
+    }
+
+    /// Runs a photo or video function. Photos wait for the camera's feedback like `take_picture`
+    /// and fail if the camera reports the photo failed. Starting and stopping recording read the
+    /// recording state from the gimbal configuration first and only toggle when it differs.
+    pub async fn capture(&self, command: capture::CaptureCommand) -> Result<(), Box<dyn Error>> {
+        correlation::correlated(async {
+            if command == capture::CaptureCommand::TakePhoto {
+                return match self.shoot().await?.outcome {
+                    capture::ShotOutcome::Failed => {
+                        Err(A8MiniError::Other("Camera reported the photo failed.".into()).into())
+                    }
+                    _ => Ok(()),
+                };
+            }
+
+            let recording = if command == capture::CaptureCommand::ToggleRecording {
+                self.telemetry
+                    .lock()
+                    .unwrap()
+                    .gimbal_info
+                    .map(|info| info.value.recording == control::RecordingStatus::Recording)
+            } else {
+                match self.get_gimbal_info().await?.recording {
+                    control::RecordingStatus::NoCard => {
+                        return Err(A8MiniError::Other("No TF card, cannot record.".into()).into())
+                    }
+                    status => Some(status == control::RecordingStatus::Recording),
+                }
+            };
+            if recording
+                .is_some_and(|recording| command.recording_after(recording) == Some(recording))
+            {
+                log_line!("[CAPTURE] {:?} skipped, nothing to change.", command);
+                return Ok(());
+            }
+
+            self.send_command_blind(command.command()).await?;
+            if let Some(recording) = recording {
+                let status = match command.recording_after(recording) {
+                    Some(true) => control::RecordingStatus::Recording,
+                    _ => control::RecordingStatus::NotRecording,
+                };
+                if let Some(info) = &mut self.telemetry.lock().unwrap().gimbal_info {
+                    info.value.recording = status;
+                }
+            }
+            log_line!("[CAPTURE] {:?} sent.", command);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Takes a photo, failing if the camera reports it failed. A photo without feedback is not
+    /// an error; use `take_picture` to tell the outcomes apart.
+    pub async fn take_photo(&self) -> Result<(), Box<dyn Error>> {
+        self.capture(capture::CaptureCommand::TakePhoto).await
+    }
+
+    /// Starts recording, doing nothing if the camera is already recording.
+    pub async fn start_recording(&self) -> Result<(), Box<dyn Error>> {
+        self.capture(capture::CaptureCommand::StartRecording).await
+    }
+
+    /// Stops recording, doing nothing if the camera is not recording.
+    pub async fn stop_recording(&self) -> Result<(), Box<dyn Error>> {
+        self.capture(capture::CaptureCommand::StopRecording).await
+    }
+
+    /// Takes `count` photos, triggering each as soon as the camera confirms the previous one
+    /// was saved (or after `BURST_SHOT_TIMEOUT` without feedback). Returns one result per shot.
+    pub async fn burst(&self, count: usize) -> Result<Vec<capture::ShotResult>, Box<dyn Error>> {
+        correlation::correlated(async {
+            let mut shots = Vec::with_capacity(count);
+            for _ in 0..count {
+                let shot = self.shoot().await?;
+                log_line!(
+                    "[CAPTURE] Burst shot {}: {:?}",
+                    shots.len() + 1,
+                    shot.outcome
+                );
+                shots.push(shot);
+            }
+            Ok(shots)
+        })
+        .await
+    }
+
+    async fn shoot(&self) -> Result<capture::ShotResult, Box<dyn Error>> {
+        let mut feedback = self.tap_function_feedback();
+        let Some(SentFrame { at: sent, .. }) = self
+            .dispatch(
+                &control::A8MiniSimpleCommand::TakePicture,
+                None,
+                Reply::Blind,
+            )
+            .await?
+            .sent()
+        else {
+            return Ok(capture::ShotResult {
+                sent: None,
+                outcome: capture::ShotOutcome::NotSent,
+            });
+        };
+
+        let outcome = loop {
+            match self
+                .recv_function_feedback(constants::BURST_SHOT_TIMEOUT, &mut feedback)
+                .await
+            {
+                Ok((received, capture::FunctionFeedback::PhotoSaved)) => {
+                    break capture::ShotOutcome::Saved(
+                        received.saturating_duration_since(sent.monotonic),
+                    )
+                }
+                Ok((_, capture::FunctionFeedback::PhotoFailed)) => {
+                    break capture::ShotOutcome::Failed
+                }
+                // Feedback about something else, keep waiting.
+                Ok(_) => continue,
+                Err(_) => break capture::ShotOutcome::NoFeedback,
+            }
+        };
+        if outcome == capture::ShotOutcome::Failed {
+            log_line!("[CAPTURE] Camera reported the photo failed.");
+        }
+        Ok(capture::ShotResult {
+            sent: Some(sent),
+            outcome,
+        })
+    }
+
+    /// Downloads a photo or video into `directory` under its on-camera file name, writing the
+    /// metadata sidecar next to it when given. Returns the path of the media file.
+    /// The download is abandoned once `deadline` passes.
+    pub async fn download_media(
+        &self,
+        query: control::A8MiniComplexHTTPQuery,
+        directory: &Path,
+        metadata: Option<&capture::CaptureMetadata>,
+        deadline: Option<deadline::Deadline>,
+    ) -> Result<PathBuf, Box<dyn Error>> {
+        correlation::correlated(async {
+            let (media_path, _) =
+                deadline::within(deadline, self.fetch_media(query, directory)).await?;
+
+            if let Some(metadata) = metadata {
+                metadata.write_sidecar(&media_path).await?;
+            }
+
+            Ok(media_path)
+        })
+        .await
+    }
+
+    /// Takes a photo and downloads it into `directory` with its metadata sidecar. Every step,
+    /// including the media count query and the download, shares the same `deadline`.
+    pub async fn take_and_download_photo(
+        &self,
+        directory: &Path,
+        vehicle: Option<compensation::VehicleAttitude>,
+        deadline: Option<deadline::Deadline>,
+    ) -> Result<PathBuf, Box<dyn Error>> {
+        correlation::correlated(async {
+            let metadata = self
+                .capture_with_metadata(capture::CaptureKind::Photo, vehicle, deadline)
+                .await?;
+            let count = deadline::within(
+                deadline,
+                self.send_http_query(control::A8MiniSimpleHTTPQuery::GetMediaCountPhotos),
+            )
+            .await?
+            .data
+            .count
+            .ok_or_else(|| A8MiniError::Other("Media count missing from response.".into()))?;
+
+            self.download_media(
+                control::A8MiniComplexHTTPQuery::GetPhoto(count.clamp(0, u8::MAX as i32) as u8),
+                directory,
+                Some(&metadata),
+                deadline,
+            )
+            .await
+        })
+        .await
+    }
+
+    /// Downloads a media file into `directory` and returns its path and contents. Transfers
+    /// interrupted by a link drop resume where they stopped, see `download_resumable`.
+    async fn fetch_media(
+        &self,
+        query: control::A8MiniComplexHTTPQuery,
+        directory: &Path,
+    ) -> Result<(PathBuf, Vec<u8>), Box<dyn Error>> {
+        let media_path = directory.join(query.file_name());
+
+        self.download_resumable(&query.url(&self.media_urls()), &media_path)
+            .await?;
+        let media_bytes = tokio::fs::read(&media_path).await?;
+        log_line!("[HTTP] Downloaded {}.", media_path.display());
+
+        Ok((media_path, media_bytes))
+    }
+
+    /// Downloads `url` to `path` through a `.part` file next to it. When the transfer breaks
+    /// off, the client waits for the camera to be reachable again (for the supervised connection
+    /// to reconnect, when a connection policy is set) and requests the rest with a ranged
+    /// request. Gives up after `DOWNLOAD_RESUME_ATTEMPTS` attempts in a row without progress.
+    async fn download_resumable(&self, url: &str, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut part_path = path.as_os_str().to_owned();
+        part_path.push(".part");
+        let part_path = PathBuf::from(part_path);
+        let part_len =
+            |part_path: &Path| std::fs::metadata(part_path).map_or(0, |metadata| metadata.len());
+
+        let mut stalled = 0;
+        loop {
+            let offset = part_len(&part_path);
+            let Err(e) = self.download_from(url, &part_path, offset).await else {
+                break;
+            };
+            let received = part_len(&part_path);
+            stalled = if received > offset { 0 } else { stalled + 1 };
+            if stalled >= constants::DOWNLOAD_RESUME_ATTEMPTS {
+                return Err(e);
+            }
+            log_line!(
+                "[HTTP] Download interrupted at {} bytes ({}), resuming.",
+                received,
+                e
+            );
+            self.wait_reachable(stalled).await;
+        }
+        tokio::fs::rename(&part_path, path).await?;
+        Ok(())
+    }
+
+    /// Fetches `url` from byte `offset` on, appending to `part_path`. A server that ignores the
+    /// range sends the whole file, which replaces the partial one.
+    async fn download_from(
+        &self,
+        url: &str,
+        part_path: &Path,
+        offset: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut request = self.http_client.get(url);
+        if offset > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+        }
+        let mut response = request.send().await?;
+        let mut file = tokio::fs::OpenOptions::new();
+        file.create(true);
+        match response.status() {
+            reqwest::StatusCode::PARTIAL_CONTENT => file.append(true),
+            // The partial file already holds everything.
+            reqwest::StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => return Ok(()),
+            status if status.is_success() => file.write(true).truncate(true),
+            status => {
+                return Err(A8MiniError::Other(format!(
+                    "Download failed with HTTP status {}.",
+                    status
+                ))
+                .into())
+            }
+        };
+        let mut file = file.open(part_path).await?;
+
+        let started = tokio::time::Instant::now();
+        let mut received = 0;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+            received += chunk.len() as u64;
+            if let Some(rate_limit) = self.download_rate_limit {
+                tokio::time::sleep(rate_limit.delay(received, started.elapsed())).await;
+            }
+        }
+        file.flush().await?;
+        Ok(())
+    }
+
+    /// Waits before download attempt `attempt` is retried: until the supervised connection is
+    /// back when it is lost, otherwise for the reconnect backoff.
+    async fn wait_reachable(&self, attempt: u32) {
+        let mut state = self.connection_state();
+        if *state.borrow() == supervisor::ConnectionState::Disconnected {
+            log_line!("[HTTP] Waiting for the camera to reconnect.");
+            let _ = state
+                .wait_for(|state| *state != supervisor::ConnectionState::Disconnected)
+                .await;
+            return;
+        }
+        let policy = self.connection_policy().unwrap_or_default();
+        tokio::time::sleep(policy.backoff(attempt)).await;
+    }
+
+    /// Downloads every photo or video on the camera that the index has not seen downloaded
+    /// (or whose local file is missing), recording the outcome of each in the index.
+    #[cfg(feature = "sqlite")]
+    pub async fn offload_media(
+        &self,
+        kind: capture::CaptureKind,
+        directory: &Path,
+        media_index: &media_index::MediaIndex,
+    ) -> Result<media_index::OffloadReport, Box<dyn Error>> {
+        let count_query = match kind {
+            capture::CaptureKind::Photo => control::A8MiniSimpleHTTPQuery::GetMediaCountPhotos,
+            capture::CaptureKind::Video => control::A8MiniSimpleHTTPQuery::GetMediaCountVideos,
+        };
+        let count = self
+            .send_http_query(count_query)
+            .await?
+            .data
+            .count
+            .ok_or_else(|| A8MiniError::Other("Media count missing from response.".into()))?;
+
+        let mut report = media_index::OffloadReport::default();
+        for index in 1..=count.clamp(0, u8::MAX as i32) as u8 {
+            let media_query = match kind {
+                capture::CaptureKind::Photo => control::A8MiniComplexHTTPQuery::GetPhoto(index),
+                capture::CaptureKind::Video => control::A8MiniComplexHTTPQuery::GetVideo(index),
+            };
+            let local_path = directory.join(media_query.file_name());
+
+            media_index.record_seen(kind, index as u32)?;
+            if media_index.is_downloaded(kind, index as u32)? && local_path.exists() {
+                report.skipped.push(index as u32);
+                continue;
+            }
+
+            match self.fetch_media(media_query, directory).await {
+                Ok((_, media_bytes)) => {
+                    media_index.record_downloaded(kind, index as u32, &media_bytes)?;
+                    report.downloaded.push(index as u32);
+                }
+                Err(e) => {
+                    log_line!("[HTTP] Download of {:?} failed: {}", media_query, e);
+                    media_index.record_failed(kind, index as u32)?;
+                    report.failed.push(index as u32);
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// URLs of the camera's media server and video streams, at the address the client is
+    /// connected to, so they follow `reconnect_to`.
+    pub fn media_urls(&self) -> urls::MediaUrls {
+        self.http_socket
+            .peer_addr()
+            .map(urls::MediaUrls::from_addr)
+            .unwrap_or_default()
+    }
+
+    /// RTSP URL of a live video stream, or `None` for the recording stream.
+    pub fn stream_url(&self, stream: control::StreamType) -> Option<String> {
+        self.media_urls().rtsp(stream)
+    }
+
+    /// Sends an HTTP media API query. Each query is its own HTTP request on a shared, pooled
+    /// client, so queries from concurrent tasks may overlap and each gets its own response.
+    /// The whole response body is read, however long. Attempts that time out or fail in
+    /// transport are retried up to the configured `http_retries`; malformed responses are not.
+    pub async fn send_http_query<T: control::HTTPQuery>(
+        &self,
+        query: T,
+    ) -> Result<control::HTTPResponse, A8MiniError> {
+        let url = query.url(&self.media_urls());
+        let mut attempt = 1;
+        loop {
+            log_line!("[HTTP] Waiting for response.");
+            match self.http_attempt(&url).await {
+                Ok(json) => return Ok(json),
+                Err(e) if e.is_decode() || attempt > self.client_config.http_retries => {
+                    return Err(e.into())
+                }
+                Err(e) => log_line!("[HTTP] Attempt {} failed ({}), retrying.", attempt, e),
+            }
+            attempt += 1;
+            tokio::time::sleep(self.client_config.retry_delay).await;
+        }
+    }
+
+    async fn http_attempt(&self, url: &str) -> Result<control::HTTPResponse, reqwest::Error> {
+        self.http_client
+            .get(url)
+            .timeout(constants::HTTP_QUERY_TIMEOUT)
+            .send()
+            .await?
+            .json::<control::HTTPResponse>()
+            .await
+    }
+
+    pub async fn send_http_image_query<T: control::HTTPQuery>(
+        &self,
+        query: T,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut response = self
+            .http_client
+            .get(query.url(&self.media_urls()))
+            .send()
+            .await?;
+        log_line!("[HTTP] Waiting for response.");
+
+        let Some(rate_limit) = self.download_rate_limit else {
+            let image_bytes = response.bytes().await?;
+            return Ok(image_bytes.to_vec());
+        };
+
+        let started = tokio::time::Instant::now();
+        let mut image_bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            image_bytes.extend_from_slice(&chunk);
+            tokio::time::sleep(rate_limit.delay(image_bytes.len() as u64, started.elapsed())).await;
+        }
+        Ok(image_bytes)
+    }
+}
+
+/// A transmitted frame: its sequence number, when it was handed to the socket and, when
+/// dispatched with `Reply::Awaited`, its response.
+#[derive(Debug)]
+struct SentFrame {
+    at: capture::HostTimestamp,
+    seq: u16,
+    ack: Option<oneshot::Receiver<(Vec<u8>, Instant)>>,
+}
+
+/// What `dispatch` did with a command.
+#[derive(Debug)]
+enum Dispatched {
+    Sent(SentFrame),
+    /// Deliberately not transmitted, and audited with this outcome.
+    Withheld(audit::AuditOutcome),
+}
+
+impl Dispatched {
+    fn sent(self) -> Option<SentFrame> {
+        match self {
+            Dispatched::Sent(sent) => Some(sent),
+            Dispatched::Withheld(_) => None,
+        }
+    }
+
+    fn into_sent(self) -> Result<SentFrame, A8MiniError> {
+        match self {
+            Dispatched::Sent(sent) => Ok(sent),
+            Dispatched::Withheld(outcome) => Err(A8MiniError::NotSent(outcome)),
+        }
+    }
+}
+
+/// Whether the caller of `dispatch` awaits the camera's answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reply {
+    /// Fire and forget. A non-motion command that fails to send may be queued offline.
+    Blind,
+    Awaited,
+}
+
+impl Drop for A8Mini {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+/// Span around sending a command (`phase` "send") or awaiting its answer ("ack"), carrying the
+/// correlation ID of the current operation.
+fn command_span(phase: &'static str, cmd_id: u8) -> tracing::Span {
+    match correlation::current() {
+        Some(id) => tracing::info_span!("command", correlation = %id, phase, cmd_id),
+        None => tracing::info_span!("command", phase, cmd_id),
+    }
+}
+
+/// Returns the DATA field of an SDK frame.
+fn ack_payload(frame: &[u8]) -> Result<&[u8], Box<dyn Error>> {
+    if frame.len() < constants::HEADER_LEN {
+        return Err(A8MiniError::MalformedAck("Frame shorter than header.".into()).into());
+    }
+    let data_len = u16::from_le_bytes([frame[3], frame[4]]) as usize;
+    frame
+        .get(constants::HEADER_LEN..constants::HEADER_LEN + data_len)
+        .ok_or_else(|| {
+            A8MiniError::MalformedAck("Frame shorter than declared data length.".into()).into()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::fs::File;
+    use tokio::io::AsyncWriteExt;
+
+    use super::*;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    /// Builds an ACK frame with a valid CRC.
+    fn test_frame(cmd_id: u8, data: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0x55, 0x66, 0x02, data.len() as u8, 0x00, 0x00, 0x00, cmd_id];
+        frame.extend_from_slice(data);
+        frame.extend_from_slice(&checksum::crc16_calc(&frame, 0));
+        frame
+    }
+
+    /// Builds the ACK for `request`: its CMD_ID and SEQ, carrying `data`.
+    fn reply(request: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut ack = test_frame(request[7], data);
+        checksum::set_seq(&mut ack, checksum::seq(request));
+        ack
+    }
+
+    /// Serves a fake camera on a local UDP port. `handler` is called with every request and
+    /// returns the frames to send back, in order.
+    async fn fake_camera<F>(mut handler: F) -> std::net::SocketAddr
+    where
+        F: FnMut(&[u8]) -> Vec<Vec<u8>> + Send + 'static,
+    {
+        let camera = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = camera.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut request = [0; 64];
+            loop {
+                let (len, client) = camera.recv_from(&mut request).await.unwrap();
+                for frame in handler(&request[..len]) {
+                    camera.send_to(&frame, client).await.unwrap();
+                }
+            }
+        });
+        addr
+    }
+
+    /// Connects a client to `camera` through the builder, on free local ports.
+    async fn test_client(camera: std::net::SocketAddr) -> Result<A8Mini, A8MiniError> {
+        A8Mini::builder()
+            .camera_addr(camera.ip().to_string())
+            .camera_command_port(camera.port())
+            .local_command_port(0)
+            .local_http_port(0)
+            .connect()
+            .await
+    }
+
+    /// Waits for the first `count` values a fake camera reported.
+    async fn take<T>(received: &mut mpsc::UnboundedReceiver<T>, count: usize) -> Vec<T> {
+        let mut values = Vec::with_capacity(count);
+        while values.len() < count {
+            values.push(received.recv().await.unwrap());
+        }
+        values
+    }
+
+    #[tokio::test]
+    async fn test_corrupted_response_skipped() -> Result<(), Box<dyn Error>> {
+        let camera = fake_camera(|request| {
+            let ack = reply(request, &[0x01]);
+            let mut corrupted = ack.clone();
+            corrupted[8] ^= 0xff;
+            vec![corrupted, ack]
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        let response = cam
+            .send_command(control::A8MiniSimpleCommand::AutoFocus)
+            .await?;
+        assert_eq!(ack_payload(&response)?, [0x01]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ack_matching_discards_unrelated_frames() -> Result<(), Box<dyn Error>> {
+        let camera = fake_camera(|request| {
+            let seq = checksum::seq(request);
+            let mut stale = test_frame(request[7], &[0x00]);
+            checksum::set_seq(&mut stale, seq.wrapping_sub(1));
+            let mut unrelated = test_frame(0x0d, &[0x00; 12]);
+            checksum::set_seq(&mut unrelated, seq);
+            vec![stale, unrelated, reply(request, &[0x01])]
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        for expected_seq in 0..2 {
+            let response = cam
+                .send_command(control::A8MiniSimpleCommand::AutoFocus)
+                .await?;
+            assert_eq!(checksum::seq(&response), expected_seq);
+            assert_eq!(response[7], 0x04);
+            assert_eq!(response[8], 0x01);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unsolicited_frames_routed_to_events() -> Result<(), Box<dyn Error>> {
+        let camera = fake_camera(|request| {
+            // A pushed attitude frame and a pushed frame with the awaited CMD_ID arrive first.
+            let pushed_attitude = test_frame(0x0d, &[0x64, 0, 0xf6, 0xff, 0, 0, 0, 0, 0, 0, 0, 0]);
+            let mut pushed_zoom = test_frame(0x18, &[0x03, 0x00]);
+            checksum::set_seq(&mut pushed_zoom, checksum::seq(request).wrapping_add(7));
+            vec![pushed_attitude, pushed_zoom, reply(request, &[0x02, 0x05])]
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        let mut events = cam.subscribe_events();
+        assert_eq!(cam.get_zoom().await?, 2.5);
+
+        let mut pushed = Vec::new();
+        while let Ok(record) = events.try_recv() {
+            if let events::A8MiniEvent::UnsolicitedFrame { cmd_id, .. } = record.event {
+                pushed.push(cmd_id);
+            }
+        }
+        assert_eq!(pushed, vec![0x0d, 0x18]);
+        let attitude = cam.snapshot().attitude.unwrap().value;
+        assert_eq!((attitude.theta_yaw, attitude.theta_pitch), (100, -10));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_blind_acks_not_unsolicited() -> Result<(), Box<dyn Error>> {
+        let camera = fake_camera(|request| vec![reply(request, &[0x01])]).await;
+        let cam = test_client(camera).await?;
+        let mut events = cam.subscribe_events();
+        cam.send_command_blind(control::A8MiniSimpleCommand::AutoCenter)
+            .await?;
+        // Answered after the blind command's ACK.
+        cam.send_command(control::A8MiniSimpleCommand::AutoFocus)
+            .await?;
+
+        while let Ok(record) = events.try_recv() {
+            assert!(!matches!(
+                record.event,
+                events::A8MiniEvent::UnsolicitedFrame { .. }
+            ));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_run_trajectory() -> Result<(), Box<dyn Error>> {
+        let (angles, mut received) = mpsc::unbounded_channel();
+        let camera = fake_camera(move |request| {
+            assert_eq!(request[7], 0x0e);
+            angles
+                .send((
+                    i16::from_le_bytes([request[8], request[9]]),
+                    i16::from_le_bytes([request[10], request[11]]),
+                ))
+                .unwrap();
+            Vec::new()
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        let mut progress = cam.subscribe_progress();
+        let spiral = trajectory::Spiral {
+            bearing: 10.0,
+            half_width: 5.0,
+            pitch_from: 0.0,
+            pitch_to: -20.0,
+            steps_per_sweep: 2,
+            sweeps: 1,
+            dwell: Duration::ZERO,
+        };
+        cam.run_trajectory(&spiral.keyframes()).await?;
+        assert_eq!(
+            take(&mut received, 3).await,
+            vec![(50, 0), (100, -100), (150, -200)]
+        );
+
+        let mut steps = Vec::new();
+        while let Ok(update) = progress.try_recv() {
+            assert_eq!(update.total, 3);
+            assert!(update.last_error.is_none());
+            steps.push(update.step);
+        }
+        assert_eq!(steps, vec![0, 1, 2, 3]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_emergency_stop_ends_trajectory() -> Result<(), Box<dyn Error>> {
+        let camera = fake_camera(|_| Vec::new()).await;
+        let cam = test_client(camera).await?;
+        let keyframes = [trajectory::Keyframe {
+            yaw: 10.0,
+            pitch: 0.0,
+            dwell: Duration::from_secs(30),
+        }];
+
+        let started = Instant::now();
+        let (trajectory, stopped) = tokio::join!(cam.run_trajectory(&keyframes), async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            cam.emergency_stop().await
+        });
+        stopped?;
+        assert!(matches!(
+            trajectory.unwrap_err().downcast_ref::<A8MiniError>(),
+            Some(A8MiniError::Rejected(_))
+        ));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        Ok(())
+    }
+
+    /// In-memory transport that acknowledges every frame with the same SEQ and CMD_ID.
+    #[derive(Debug)]
+    struct EchoTransport {
+        sent: Mutex<Vec<Vec<u8>>>,
+        acks: mpsc::UnboundedSender<Vec<u8>>,
+        pending: tokio::sync::Mutex<mpsc::UnboundedReceiver<Vec<u8>>>,
+    }
+
+    impl Default for EchoTransport {
+        fn default() -> Self {
+            let (acks, pending) = mpsc::unbounded_channel();
+            Self {
+                sent: Mutex::default(),
+                acks,
+                pending: tokio::sync::Mutex::new(pending),
+            }
+        }
+    }
+
+    impl transport::Transport for EchoTransport {
+        fn send<'a>(&'a self, frame: &'a [u8]) -> transport::BoxFuture<'a, std::io::Result<usize>> {
+            Box::pin(async move {
+                self.sent.lock().unwrap().push(frame.to_vec());
+                let mut ack = test_frame(frame[7], &[0x01]);
+                checksum::set_seq(&mut ack, checksum::seq(frame));
+                self.acks.send(ack).unwrap();
+                Ok(frame.len())
+            })
+        }
+
+        fn recv<'a>(
+            &'a self,
+            buf: &'a mut [u8],
+        ) -> transport::BoxFuture<'a, std::io::Result<usize>> {
+            Box::pin(async move {
+                // The sender lives as long as the transport.
+                let frame = self.pending.lock().await.recv().await.unwrap();
+                buf[..frame.len()].copy_from_slice(&frame);
+                Ok(frame.len())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tcp_control_channel() -> Result<(), Box<dyn Error>> {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let camera_port = listener.local_addr()?.port().to_string();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0; 11];
+            stream.read_exact(&mut request).await.unwrap();
+            // Noise, then the ACK split across two writes.
+            let mut ack = test_frame(request[7], &[0x01]);
+            checksum::set_seq(&mut ack, checksum::seq(&request));
+            stream.write_all(&[0x00, 0x55]).await.unwrap();
+            stream.write_all(&ack[..6]).await.unwrap();
+            stream.write_all(&ack[6..]).await.unwrap();
+        });
+
+        let cam = A8Mini::connect_tcp("127.0.0.1", &camera_port).await?;
+        let response = cam
+            .send_command(control::A8MiniSimpleCommand::AutoFocus)
+            .await?;
+        assert_eq!(response[7], 0x04);
+        assert_eq!(response[8], 0x01);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_custom_transport() -> Result<(), Box<dyn Error>> {
+        let cam = A8Mini::with_transport(EchoTransport::default()).await?;
+        assert!(cam.command_socket().is_none());
+        assert!(cam
+            .set_socket_options(sockets::SocketOptions::default())
+            .is_err());
+
+        let response = cam
+            .send_command(control::A8MiniSimpleCommand::AutoFocus)
+            .await?;
+        assert_eq!(response[7], 0x04);
+        assert_eq!(response[8], 0x01);
+        cam.send_command_blind(control::A8MiniSimpleCommand::AutoCenter)
+            .await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_fast_ack_reaches_request() -> Result<(), Box<dyn Error>> {
+        // The ACK is readable as soon as the frame is sent, while the reader runs on another
+        // thread.
+        let cam = A8Mini::with_transport(EchoTransport::default()).await?;
+        let mut events = cam.subscribe_events();
+        for _ in 0..100 {
+            let response = cam
+                .send_command(control::A8MiniSimpleCommand::AutoFocus)
+                .await?;
+            assert_eq!(response[8], 0x01);
+        }
+        while let Ok(record) = events.try_recv() {
+            assert!(!matches!(
+                record.event,
+                events::A8MiniEvent::UnsolicitedFrame { .. }
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_ack_payload() {
+        let frame = [
+            0x55, 0x66, 0x02, 0x03, 0x00, 0x00, 0x00, 0x0a, 0x00, 0x01, 0x02, 0xaa, 0xbb,
+        ];
+        assert_eq!(ack_payload(&frame).unwrap(), &[0x00, 0x01, 0x02]);
+        assert!(ack_payload(&frame[..9]).is_err());
+        assert!(ack_payload(&frame[..4]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_records_without_sending() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+
+        cam.send_command_blind(control::A8MiniSimpleCommand::TakePicture)
+            .await?;
+        assert!(cam
+            .send_command(control::A8MiniSimpleCommand::AttitudeInformation)
+            .await
+            .is_err());
+
+        let audit_log = cam.audit_log();
+        assert_eq!(audit_log.len(), 2);
+        assert!(audit_log
+            .iter()
+            .all(|entry| entry.outcome == audit::AuditOutcome::DryRun));
+        assert_eq!(
+            audit_log[0].bytes,
+            control::A8MiniSimpleCommand::TakePicture.to_bytes()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_motion_interlock() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+        cam.set_motion_interlock(true);
+
+        assert!(cam
+            .send_command_blind(control::A8MiniSimpleCommand::RotateUp)
+            .await
+            .is_err());
+        cam.send_command_blind(control::A8MiniSimpleCommand::StopRotation)
+            .await?;
+        cam.send_command_blind(control::A8MiniSimpleCommand::TakePicture)
+            .await?;
+
+        cam.arm_motion();
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(100),
+            DeciDeg(0),
+        ))
+        .await?;
+        cam.disarm_motion();
+        assert!(!cam.is_motion_armed());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_setpoint_coalescing() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+        cam.set_setpoint_spacing(Some(Duration::from_millis(50)));
+
+        let (first, second, third) = tokio::join!(
+            cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+                DeciDeg(10),
+                DeciDeg(0)
+            )),
+            cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+                DeciDeg(20),
+                DeciDeg(0)
+            )),
+            cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+                DeciDeg(30),
+                DeciDeg(0)
+            )),
+        );
+        first?;
+        second?;
+        third?;
+
+        let outcomes: Vec<_> = cam
+            .audit_log()
+            .into_iter()
+            .map(|entry| entry.outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                audit::AuditOutcome::DryRun,
+                audit::AuditOutcome::Superseded,
+                audit::AuditOutcome::DryRun,
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_fails_fast() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_circuit_breaker(Some(breaker::CircuitBreaker::new(1, 1.0)));
+        let mut events = cam.subscribe_events();
+
+        assert!(cam
+            .send_command(control::A8MiniSimpleCommand::FirmwareVersionInformation)
+            .await
+            .is_err());
+        assert!(cam.is_link_down());
+        assert_eq!(events.try_recv()?.event, events::A8MiniEvent::LinkDown);
+
+        assert!(cam
+            .send_command_blind(control::A8MiniSimpleCommand::TakePicture)
+            .await
+            .is_err());
+        assert_eq!(
+            cam.audit_log().last().unwrap().outcome,
+            audit::AuditOutcome::Failed("Circuit breaker open, link is down.".to_string())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_boot_detection_probes_open_breaker() -> Result<(), Box<dyn Error>> {
+        let mut answered = false;
+        // The first query goes unanswered and trips the breaker.
+        let camera = fake_camera(move |request| {
+            let first = !answered;
+            answered = true;
+            if first {
+                Vec::new()
+            } else {
+                vec![reply(request, &[0x01])]
+            }
+        })
+        .await;
+        let mut cam = test_client(camera).await?;
+        cam.set_circuit_breaker(Some(breaker::CircuitBreaker::new(1, 1.0)));
+        assert!(cam
+            .send_command(control::A8MiniSimpleCommand::FirmwareVersionInformation)
+            .await
+            .is_err());
+        assert!(cam.is_link_down());
+
+        let mut events = cam.subscribe_events();
+        let booted = async {
+            while events.recv().await?.event != events::A8MiniEvent::GimbalBooted {}
+            Ok::<_, Box<dyn Error>>(())
+        };
+        let defaults = boot::BootDefaults::new(control::MotionMode::Lock).with_center(false);
+        tokio::select! {
+            _ = cam.apply_defaults_on_boot(defaults, Duration::from_millis(50)) => unreachable!(),
+            booted = timeout(Duration::from_secs(5), booted) => booted??,
+        }
+        assert!(!cam.is_link_down());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_operator_override() -> Result<(), Box<dyn Error>> {
+        use arbitration::CommandSource;
+
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+        cam.set_arbitration(Some(arbitration::Arbitration::new(Duration::from_secs(60))));
+
+        cam.send_command_blind_from(
+            CommandSource::Autopilot,
+            control::A8MiniSimpleCommand::RotateUp,
+        )
+        .await?;
+        cam.send_command_blind_from(
+            CommandSource::Operator,
+            control::A8MiniSimpleCommand::RotateLeft,
+        )
+        .await?;
+        assert!(cam.operator_override());
+        assert!(cam
+            .send_command_blind_from(
+                CommandSource::Autopilot,
+                control::A8MiniSimpleCommand::RotateUp
+            )
+            .await
+            .is_err());
+        cam.send_command_blind_from(
+            CommandSource::Autopilot,
+            control::A8MiniSimpleCommand::TakePicture,
+        )
+        .await?;
+
+        cam.release_operator_override();
+        cam.send_command_blind_from(
+            CommandSource::Autopilot,
+            control::A8MiniSimpleCommand::RotateUp,
+        )
+        .await?;
+        assert_eq!(
+            cam.audit_log().last().unwrap().source,
+            Some(CommandSource::Autopilot)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_restricted_handle() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+        let cam = Arc::new(cam);
+        let restricted = cam.restricted();
+
+        restricted
+            .send_command_blind(control::A8MiniSimpleCommand::TakePicture)
+            .await?;
+        assert!(restricted
+            .send_command_blind(control::A8MiniSimpleCommand::RebootGimbal)
+            .await
+            .is_err());
+        cam.send_command_blind(control::A8MiniSimpleCommand::RebootGimbal)
+            .await?;
+        assert_eq!(cam.audit_log().len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_emergency_stop_latches_motion() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+        cam.set_setpoint_spacing(Some(Duration::from_millis(50)));
+
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(10),
+            DeciDeg(0),
+        ))
+        .await?;
+        let (pending, stopped) = tokio::join!(
+            cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+                DeciDeg(20),
+                DeciDeg(0)
+            )),
+            cam.emergency_stop(),
+        );
+        pending?;
+        stopped?;
+
+        let outcomes: Vec<_> = cam
+            .audit_log()
+            .into_iter()
+            .map(|entry| entry.outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                audit::AuditOutcome::DryRun,
+                audit::AuditOutcome::DryRun,
+                audit::AuditOutcome::Superseded,
+            ]
+        );
+
+        assert!(!cam.is_motion_armed());
+        assert!(cam
+            .send_command_blind(control::A8MiniSimpleCommand::RotateUp)
+            .await
+            .is_err());
+        cam.arm_motion();
+        cam.send_command_blind(control::A8MiniSimpleCommand::RotateUp)
+            .await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_link_stats() -> Result<(), Box<dyn Error>> {
+        let cam = test_client("127.0.0.1:37260".parse()?).await?;
+        assert!(cam
+            .send_command(control::A8MiniSimpleCommand::AttitudeInformation)
+            .await
+            .is_err());
+
+        let snapshot = cam.snapshot();
+        assert_eq!(snapshot.attitude, None);
+        assert_eq!(snapshot.link.timeouts, 1);
+        assert_eq!(snapshot.link.responses, 0);
+        assert_eq!(snapshot.link_state, breaker::LinkState::Up);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_command_metrics_by_cmd_id() -> Result<(), Box<dyn Error>> {
+        let camera = fake_camera(|request| match request[7] {
+            // Zoom queries are answered, captures never confirmed.
+            0x18 => vec![reply(request, &[2, 0])],
+            _ => Vec::new(),
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        cam.get_zoom().await?;
+        cam.get_zoom().await?;
+        assert!(cam
+            .send_command(control::A8MiniSimpleCommand::TakePicture)
+            .await
+            .is_err());
+
+        let breakdown = cam.command_metrics();
+        let zoom = breakdown.get(0x18).unwrap();
+        assert_eq!((zoom.sent, zoom.responses, zoom.timeouts), (2, 2, 0));
+        assert_eq!(zoom.rtt.count, 2);
+        let capture = breakdown.get(0x0c).unwrap();
+        assert_eq!(
+            (capture.sent, capture.responses, capture.timeouts),
+            (1, 0, 1)
+        );
+        assert!(breakdown
+            .render_prometheus()
+            .contains("a8mini_command_timeouts_total{cmd_id=\"0x0c\",command=\"photo_video\"} 1"));
+        Ok(())
+    }
+
+    struct LocalQuery(String);
+
+    impl control::HTTPQuery for LocalQuery {
+        fn to_string(&self) -> String {
+            self.0.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_http_queries() -> Result<(), Box<dyn Error>> {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                tokio::spawn(async move {
+                    let mut request = [0; 1024];
+                    let len = stream.read(&mut request).await.unwrap();
+                    let request = String::from_utf8_lossy(&request[..len]).to_string();
+                    // Answer the photo count late so the responses arrive out of order.
+                    let (media_type, count) = if request.contains("media_type=0") {
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        (0, 12)
+                    } else {
+                        (1, 3)
+                    };
+                    let body = format!(
+                        r#"{{"code":200,"data":{{"media_type":{},"count":{}}},"success":true,"message":""}}"#,
+                        media_type, count
+                    );
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    stream.write_all(response.as_bytes()).await.unwrap();
+                });
+            }
+        });
+
+        let cam = test_client("127.0.0.1:37260".parse()?).await?;
+        let (photos, videos) = tokio::join!(
+            cam.send_http_query(LocalQuery(format!(
+                "http://{}/cgi-bin/media.cgi/api/v1/getmediacount?media_type=0",
+                address
+            ))),
+            cam.send_http_query(LocalQuery(format!(
+                "http://{}/cgi-bin/media.cgi/api/v1/getmediacount?media_type=1",
+                address
+            ))),
+        );
+
+        let (photos, videos) = (photos?, videos?);
+        assert_eq!((photos.data.media_type, photos.data.count), (0, Some(12)));
+        assert_eq!((videos.data.media_type, videos.data.count), (1, Some(3)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_http_queries_follow_camera_address() -> Result<(), Box<dyn Error>> {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let http_port = listener.local_addr()?.port();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0; 1024];
+            let len = stream.read(&mut request).await.unwrap();
+            let body =
+                r#"{"code":200,"data":{"media_type":1,"count":4},"success":true,"message":""}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request[..len]).to_string()
+        });
+
+        let cam = A8Mini::builder()
+            .camera_addr("127.0.0.1")
+            .camera_http_port(http_port)
+            .local_command_port(0)
+            .local_http_port(0)
+            .connect()
+            .await?;
+        assert_eq!(
+            cam.stream_url(control::StreamType::Sub).unwrap(),
+            "rtsp://127.0.0.1:8554/sub.264"
+        );
+        let response = cam
+            .send_http_query(control::A8MiniSimpleHTTPQuery::GetMediaCountVideos)
+            .await?;
+        assert_eq!(response.data.count, Some(4));
+        assert!(server.await?.starts_with(
+            "GET /cgi-bin/media.cgi/api/v1/getmediacount?media_type=1&path=101SIYI_VID "
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_after_drop() -> Result<(), Box<dyn Error>> {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let http_port = listener.local_addr()?.port();
+        let server = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            let mut request = [0; 1024];
+            // The link drops after the first four bytes of ten.
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let len = stream.read(&mut request).await.unwrap();
+            requests.push(String::from_utf8_lossy(&request[..len]).to_lowercase());
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 10\r\n\r\n0123")
+                .await
+                .unwrap();
+            drop(stream);
+
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let len = stream.read(&mut request).await.unwrap();
+            requests.push(String::from_utf8_lossy(&request[..len]).to_lowercase());
+            stream
+                .write_all(
+                    b"HTTP/1.1 206 Partial Content\r\ncontent-range: bytes 4-9/10\r\ncontent-length: 6\r\n\r\n456789",
+                )
+                .await
+                .unwrap();
+            requests
+        });
+
+        let directory = std::env::temp_dir().join("a8mini_test_resume");
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory)?;
+        let cam = A8Mini::builder()
+            .camera_addr("127.0.0.1")
+            .camera_http_port(http_port)
+            .local_command_port(0)
+            .local_http_port(0)
+            .connect()
+            .await?;
+        let path = cam
+            .download_media(
+                control::A8MiniComplexHTTPQuery::GetPhoto(3),
+                &directory,
+                None,
+                None,
+            )
+            .await?;
+        assert_eq!(std::fs::read(&path)?, b"0123456789");
+        assert_eq!(std::fs::read_dir(&directory)?.count(), 1);
+
+        let requests = server.await?;
+        assert!(!requests[0].contains("range:"));
+        assert!(requests[1].contains("range: bytes=4-"));
+        std::fs::remove_dir_all(&directory)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_http_query_retry() -> Result<(), Box<dyn Error>> {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        tokio::spawn(async move {
+            // Drop the first connection without answering.
+            let (stream, _) = listener.accept().await.unwrap();
+            drop(stream);
+
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request).await.unwrap();
+            let body =
+                r#"{"code":200,"data":{"media_type":0,"count":5},"success":true,"message":""}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let cam = test_client("127.0.0.1:37260".parse()?).await?;
+        let response = cam
+            .send_http_query(LocalQuery(format!(
+                "http://{}/cgi-bin/media.cgi/api/v1/getmediacount?media_type=0",
+                address
+            )))
+            .await?;
+        assert_eq!(response.data.count, Some(5));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_strict_acks() -> Result<(), Box<dyn Error>> {
+        // Acknowledge the first command correctly, the second with a different CMD_ID and leave
+        // the third unanswered.
+        let mut cmd_id_offsets = [0, 1].into_iter();
+        let camera = fake_camera(move |request| match cmd_id_offsets.next() {
+            Some(cmd_id_offset) => vec![test_frame(request[7] + cmd_id_offset, &[0x01])],
+            None => Vec::new(),
+        })
+        .await;
+
+        let mut cam = test_client(camera).await?;
+        cam.set_strict_acks(true);
+
+        cam.send_command_blind(control::A8MiniSimpleCommand::AutoFocus)
+            .await?;
+        assert!(cam
+            .send_command_blind(control::A8MiniSimpleCommand::AutoFocus)
+            .await
+            .is_err());
+        assert!(cam
+            .send_command_blind(control::A8MiniSimpleCommand::AutoFocus)
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_paused_clock_drives_pacing() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+        cam.set_setpoint_spacing(Some(constants::ANGLE_SETPOINT_MIN_INTERVAL));
+
+        let start = tokio::time::Instant::now();
+        for theta_yaw in [100, 200, 300] {
+            cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+                DeciDeg(theta_yaw),
+                DeciDeg(0),
+            ))
+            .await?;
+        }
+        assert_eq!(start.elapsed(), constants::ANGLE_SETPOINT_MIN_INTERVAL * 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stare_at_orbit_center() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+
+        let center = compensation::GeoPoint {
+            latitude: 40.0,
+            longitude: -86.0,
+            altitude: 0.0,
+        };
+        // 50 m north of the center, 50 m up, heading east: the center is to the right and below.
+        let offset = (50.0 / 6_371_000.0_f64).to_degrees();
+        let state = compensation::VehicleState {
+            position: compensation::GeoPoint {
+                latitude: 40.0 + offset,
+                altitude: 50.0,
+                ..center
+            },
+            attitude: compensation::VehicleAttitude {
+                roll: 0.0,
+                pitch: 0.0,
+                yaw: 90.0,
+            },
+        };
+        let (sender, receiver) = watch::channel(state);
+        drop(sender);
+        cam.stare_at(center, receiver).await?;
+
+        let entry = cam.audit_log().pop().unwrap();
+        let theta_yaw = i16::from_le_bytes([entry.bytes[8], entry.bytes[9]]);
+        let theta_pitch = i16::from_le_bytes([entry.bytes[10], entry.bytes[11]]);
+        assert_eq!((theta_yaw, theta_pitch), (900, -450));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_middleware_chain() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+        // Rewrites zoom in to zoom out, shadows photos and refuses reboots.
+        cam.add_middleware(|command, context| {
+            if command.to_bytes() == control::A8MiniSimpleCommand::ZoomIn.to_bytes() {
+                context.bytes = control::A8MiniSimpleCommand::ZoomOut.to_bytes();
+            }
+            middleware::Decision::Continue
+        });
+        cam.add_middleware(|command, _| match command.to_bytes()[7] {
+            0x0c => middleware::Decision::Skip,
+            0x80 => middleware::Decision::Reject("No reboots in flight.".into()),
+            _ => middleware::Decision::Continue,
+        });
+
+        cam.send_command_blind(control::A8MiniSimpleCommand::ZoomIn)
+            .await?;
+        cam.send_command_blind(control::A8MiniSimpleCommand::TakePicture)
+            .await?;
+        let result = cam
+            .send_command(control::A8MiniSimpleCommand::RebootCamera)
+            .await;
+        assert!(
+            matches!(result, Err(A8MiniError::Rejected(reason)) if reason == "No reboots in flight.")
+        );
+
+        let audit_log = cam.audit_log();
+        assert_eq!(audit_log.len(), 2);
+        assert_eq!(audit_log[0].outcome, audit::AuditOutcome::DryRun);
+        assert_eq!(audit_log[0].bytes[8], 0xff);
+        assert!(checksum::validate_frame(&audit_log[0].bytes).is_ok());
+        assert_eq!(audit_log[1].outcome, audit::AuditOutcome::Skipped);
+
+        cam.clear_middleware();
+        cam.send_command_blind(control::A8MiniSimpleCommand::TakePicture)
+            .await?;
+        assert_eq!(
+            cam.audit_log().last().unwrap().outcome,
+            audit::AuditOutcome::DryRun
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_shortest_path_yaw() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+
+        // Unknown attitude: the wrapped setpoint is clamped on encoding as before.
+        cam.set_attitude_degrees(-185.0, 0.0).await?;
+
+        cam.telemetry.lock().unwrap().attitude = Some(telemetry::Timestamped::new(
+            control::A8MiniAtittude {
+                theta_yaw: -1300,
+                theta_pitch: 0,
+                theta_roll: 0,
+                v_yaw: 0,
+                v_pitch: 0,
+                v_roll: 0,
+            },
+            clock::now(),
+        ));
+        cam.set_attitude_degrees(-185.0, 0.0).await?;
+
+        let setpoints: Vec<_> = cam
+            .audit_log()
+            .into_iter()
+            .map(|entry| i16::from_le_bytes([entry.bytes[8], entry.bytes[9]]))
+            .collect();
+        assert_eq!(setpoints, vec![1350, -1350]);
+
+        // Mounted at yaw 90.0: the attitude -40.0 is the gimbal's -130.0 and the target 175.0 its
+        // 85.0, reachable directly.
+        cam.clear_audit_log();
+        cam.set_calibration(calibration::CalibrationOffsets {
+            yaw: 900,
+            ..Default::default()
+        });
+        cam.telemetry.lock().unwrap().attitude = Some(telemetry::Timestamped::new(
+            control::A8MiniAtittude {
+                theta_yaw: -400,
+                theta_pitch: 0,
+                theta_roll: 0,
+                v_yaw: 0,
+                v_pitch: 0,
+                v_roll: 0,
+            },
+            clock::now(),
+        ));
+        cam.set_attitude_degrees(175.0, 0.0).await?;
+        let angle = &cam.audit_log()[0].bytes;
+        assert_eq!(i16::from_le_bytes([angle[8], angle[9]]), 850);
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_policy_recenters() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+        cam.set_idle_policy(Some(idle::IdlePolicy::new(
+            Duration::from_secs(2),
+            idle::IdleAction::Recenter,
+        )));
+        let recenters = |cam: &A8Mini| {
+            cam.audit_log()
+                .iter()
+                .filter(|entry| entry.bytes[7] == 0x08)
+                .count()
+        };
+
+        let session = async {
+            cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchSpeed(20, 0))
+                .await?;
+            tokio::time::sleep(Duration::from_millis(1500)).await;
+            // Polling is not motion and does not keep the gimbal from going idle.
+            cam.send_command_blind(control::A8MiniSimpleCommand::CurrentZoomInformation)
+                .await?;
+            tokio::time::sleep(Duration::from_millis(1000)).await;
+            assert_eq!(recenters(&cam), 1);
+
+            // Once per idle period, until the next motion command.
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            assert_eq!(recenters(&cam), 1);
+            cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchSpeed(0, 10))
+                .await?;
+            tokio::time::sleep(Duration::from_millis(2100)).await;
+            assert_eq!(recenters(&cam), 2);
+            Ok::<_, Box<dyn Error>>(())
+        };
+        tokio::select! {
+            _ = cam.enforce_idle_policy() => unreachable!(),
+            result = session => result,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_hybrid_returns_to_start() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+        let start = control::A8MiniAtittude {
+            theta_yaw: 100,
+            theta_pitch: -200,
+            theta_roll: 0,
+            v_yaw: 0,
+            v_pitch: 0,
+            v_roll: 0,
+        };
+        cam.telemetry.lock().unwrap().attitude =
+            Some(telemetry::Timestamped::new(start, clock::now()));
+
+        let (rates, receiver) = watch::channel((0.0, 0.0));
+        let joystick = async {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            rates.send((10.0, 5.0)).unwrap();
+            tokio::time::sleep(Duration::from_secs(2)).await;
+            rates.send((-10.0, -5.0)).unwrap();
+            tokio::time::sleep(Duration::from_secs(2)).await;
+            rates.send((0.0, 0.0)).unwrap();
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            drop(rates);
+        };
+        let (result, ()) = tokio::join!(cam.run_rate_hybrid(receiver), joystick);
+        result?;
+
+        let setpoints: Vec<_> = cam
+            .audit_log()
+            .iter()
+            .map(|entry| {
+                replay::RecordedCommand(entry.bytes.clone())
+                    .angle_setpoint()
+                    .unwrap()
+            })
+            .collect();
+        let peak_yaw = setpoints.iter().map(|setpoint| setpoint.0).max().unwrap();
+        assert!((290..=300).contains(&peak_yaw));
+        assert_eq!(setpoints.last(), Some(&(100, -200)));
+        // Nothing is re-sent while the stick is centered.
+        assert!(setpoints.windows(2).all(|pair| pair[0] != pair[1]));
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_replay_preserves_timing() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+
+        let recorded_at = std::time::SystemTime::now();
+        let entries: Vec<_> = [
+            (
+                0,
+                control::A8MiniSimpleCommand::AutoFocus,
+                audit::AuditOutcome::Sent,
+            ),
+            (
+                500,
+                control::A8MiniSimpleCommand::ZoomIn,
+                audit::AuditOutcome::DryRun,
+            ),
+            (
+                2000,
+                control::A8MiniSimpleCommand::ZoomOut,
+                audit::AuditOutcome::Sent,
+            ),
+        ]
+        .into_iter()
+        .map(|(offset_ms, command, outcome)| audit::AuditEntry {
+            time: recorded_at + Duration::from_millis(offset_ms),
+            bytes: command.to_bytes(),
+            correlation: None,
+            source: None,
+            outcome,
+        })
+        .collect();
+
+        let start = tokio::time::Instant::now();
+        assert_eq!(cam.replay(&entries, 4.0).await?, 2);
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
 
-#[derive(Debug)]
-pub struct A8Mini {
-    pub command_socket: UdpSocket,
-    pub http_socket: UdpSocket,
-}
+        let replayed: Vec<_> = cam
+            .audit_log()
+            .into_iter()
+            .map(|entry| entry.bytes[7])
+            .collect();
+        assert_eq!(replayed, vec![0x04, 0x05]);
+        Ok(())
+    }
 
-impl A8Mini {
-    pub async fn connect() -> Result<Self, Box<dyn Error>> {
-        Ok(Self::connect_to(
-            constants::CAMERA_IP,
-            constants::CAMERA_COMMAND_PORT,
-            constants::CAMERA_HTTP_PORT,
-            "8080",
-            "8088",
-        )
-        .await?)
+    #[tokio::test]
+    async fn test_take_picture_reports_failure() -> Result<(), Box<dyn Error>> {
+        // Unrelated feedback first, then the photo's.
+        let camera = fake_camera(|_| vec![test_frame(0x0b, &[4]), test_frame(0x0b, &[1])]).await;
+
+        let cam = test_client(camera).await?;
+        let shot = cam.take_picture().await?;
+        assert!(shot.sent.is_some());
+        assert_eq!(shot.outcome, capture::ShotOutcome::Failed);
+
+        // Waiting before the capture is sent.
+        let (feedback, sent) = tokio::join!(
+            cam.get_function_feedback(Duration::from_secs(1)),
+            cam.send_command_blind(control::A8MiniSimpleCommand::TakePicture),
+        );
+        sent?;
+        assert_eq!(feedback?, capture::FunctionFeedback::RecordingFailed);
+        Ok(())
     }
 
-    pub async fn connect_to(
-        camera_ip: &str,
-        camera_command_port: &str,
-        camera_http_port: &str,
-        local_command_port: &str,
-        local_http_port: &str,
-    ) -> Result<A8Mini, Box<dyn Error>> {
-        let camera: A8Mini = A8Mini {
-            command_socket: UdpSocket::bind(format!("0.0.0.0:{}", local_command_port)).await?,
-            http_socket: UdpSocket::bind(format!("0.0.0.0:{}", local_http_port)).await?,
-        };
+    #[tokio::test]
+    async fn test_autofocus() -> Result<(), Box<dyn Error>> {
+        let mut stas = [1, 0].into_iter();
+        let camera =
+            fake_camera(move |request| vec![reply(request, &[stas.next().unwrap()])]).await;
+
+        let cam = test_client(camera).await?;
+        cam.autofocus().await?;
+        assert!(cam.autofocus().await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_manual_focus() -> Result<(), Box<dyn Error>> {
+        let (directions, mut received) = mpsc::unbounded_channel();
+        let mut stas = [1, 1, 0].into_iter();
+        let camera = fake_camera(move |request| {
+            assert_eq!(request[7], 0x06);
+            directions.send(request[8] as i8).unwrap();
+            vec![reply(request, &[stas.next().unwrap()])]
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        cam.focus_far().await?;
+        cam.focus_stop().await?;
+        assert!(cam.focus_near().await.is_err());
+        assert_eq!(take(&mut received, 3).await, vec![1, 0, -1]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_manual_zoom() -> Result<(), Box<dyn Error>> {
+        let mut levels = [20u16, 35].into_iter();
+        let camera = fake_camera(move |request| {
+            assert_eq!(request[7], 0x05);
+            vec![reply(request, &levels.next().unwrap().to_le_bytes())]
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        assert_eq!(cam.zoom_in().await?, 2.0);
+        assert_eq!(cam.zoom_stop().await?, 3.5);
+        assert_eq!(cam.snapshot().zoom.map(|zoom| zoom.value), Some(3.5));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_current_zoom() -> Result<(), Box<dyn Error>> {
+        let camera = fake_camera(|request| {
+            assert_eq!(request[7], 0x18);
+            vec![reply(request, &[4, 5])]
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        let level = cam.get_current_zoom().await?;
+        assert_eq!(level, zoom::ZoomLevel(4.5));
+        assert_eq!(level.to_string(), "4.5x");
+        assert_eq!(cam.snapshot().zoom.map(|zoom| zoom.value), Some(4.5));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_max_zoom() -> Result<(), Box<dyn Error>> {
+        let mut payloads = [&[30, 0][..], &[6]].into_iter();
+        let camera = fake_camera(move |request| {
+            assert_eq!(request[7], 0x16);
+            vec![reply(request, payloads.next().unwrap())]
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        assert_eq!(cam.get_max_zoom().await?, 30.0);
+        assert!(cam.get_max_zoom().await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_motion_mode() -> Result<(), Box<dyn Error>> {
+        // The camera switches to follow mode but ignores the switch to lock mode.
+        let mut modes = [(0x04, 1), (0x03, 1)].into_iter();
+        let mut reported_mode = 0;
+        let camera = fake_camera(move |request| match request[7] {
+            0x0c => {
+                let (mode_command, mode) = modes.next().unwrap();
+                assert_eq!(request[8], mode_command);
+                reported_mode = mode;
+                Vec::new()
+            }
+            0x0a => vec![reply(request, &[0, 0, 0, 0, reported_mode, 1])],
+            cmd_id => panic!("Unexpected CMD_ID {:#04x}", cmd_id),
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        cam.set_motion_mode(control::GimbalMode::Follow).await?;
+        assert_eq!(
+            cam.snapshot().motion_mode(),
+            Some(control::MotionMode::Follow)
+        );
+        assert!(cam
+            .set_motion_mode(control::GimbalMode::Lock)
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_center_waits_for_settle() -> Result<(), Box<dyn Error>> {
+        // Yaw 30.0, pitch -20.0, then yaw 0.5, pitch -0.3.
+        let mut attitudes = [[0x2c, 0x01, 0x38, 0xff], [0x05, 0x00, 0xfd, 0xff]].into_iter();
+        let camera = fake_camera(move |request| match request[7] {
+            0x08 => vec![reply(request, &[0x01])],
+            0x0d => {
+                let mut data = attitudes.next().unwrap().to_vec();
+                data.extend_from_slice(&[0; 8]);
+                vec![reply(request, &data)]
+            }
+            cmd_id => panic!("Unexpected CMD_ID {:#04x}", cmd_id),
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        let elapsed = cam.center().await?;
+        assert!(elapsed >= constants::CENTER_POLL_INTERVAL);
+        assert_eq!(cam.snapshot().attitude.unwrap().value.theta_pitch, -3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_attitude_watch() -> Result<(), Box<dyn Error>> {
+        let camera = UdpSocket::bind("127.0.0.1:0").await?;
+        let cam = test_client(camera.local_addr()?).await?;
+        let mut attitude = cam.attitude_watch();
+        assert_eq!(*attitude.borrow(), conventions::Attitude::default());
+
+        // Pushed yaw -10.0, pitch 20.0, roll 0.5.
+        let push = test_frame(0x0d, &[0x9c, 0xff, 0xc8, 0x00, 0x05, 0, 0, 0, 0, 0, 0, 0]);
+        let client_port = cam.command_transport.as_udp().unwrap().local_addr()?.port();
+        camera.send_to(&push, ("127.0.0.1", client_port)).await?;
+        tokio::select! {
+            result = cam.run_push_handler() => panic!("Push handler stopped: {:?}", result),
+            changed = attitude.changed() => changed?,
+        }
+        assert_eq!(
+            *attitude.borrow(),
+            conventions::Attitude {
+                yaw: -10.0,
+                pitch: 20.0,
+                roll: 0.5,
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_keepalive_events() -> Result<(), Box<dyn Error>> {
+        // Answers, drops two pings, then answers again.
+        let mut answers = [true, false, false]
+            .into_iter()
+            .chain(std::iter::repeat(true));
+        let camera = fake_camera(move |request| {
+            assert_eq!(request[7], 0x01);
+            match answers.next().unwrap() {
+                true => vec![reply(request, &[0; 12])],
+                false => Vec::new(),
+            }
+        })
+        .await;
 
-        camera
-            .command_socket
-            .connect(format!("{}:{}", camera_ip, camera_command_port))
+        let cam = test_client(camera).await?;
+        let mut events = cam.subscribe_events();
+        let policy = keepalive::KeepalivePolicy::new(Duration::from_millis(10), 2);
+        let mut link_events = Vec::new();
+        tokio::select! {
+            _ = cam.run_keepalive(policy) => unreachable!(),
+            _ = async {
+                while let Ok(record) = events.recv().await {
+                    link_events.push(record.event.clone());
+                    if record.event == events::A8MiniEvent::CameraResponsive {
+                        break;
+                    }
+                }
+            } => {}
+        }
+        assert_eq!(
+            link_events,
+            vec![
+                events::A8MiniEvent::CameraUnresponsive,
+                events::A8MiniEvent::CameraResponsive
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_supervised_reconnect() -> Result<(), Box<dyn Error>> {
+        // The link is down for the first two requests.
+        let mut answers = [false, false].into_iter().chain(std::iter::repeat(true));
+        let camera = fake_camera(move |request| match answers.next().unwrap() {
+            true => vec![reply(request, &[0x01])],
+            false => Vec::new(),
+        })
+        .await;
+
+        let mut cam = test_client(camera).await?;
+        cam.set_connection_policy(Some(supervisor::ConnectionPolicy {
+            degraded_after: 1,
+            disconnected_after: 2,
+            backoff_min: Duration::from_millis(10),
+            ..Default::default()
+        }));
+        let mut state = cam.connection_state();
+        for expected in [
+            supervisor::ConnectionState::Degraded,
+            supervisor::ConnectionState::Disconnected,
+        ] {
+            assert!(cam
+                .send_command(control::A8MiniSimpleCommand::AutoFocus)
+                .await
+                .is_err());
+            assert_eq!(*state.borrow_and_update(), expected);
+        }
+
+        tokio::select! {
+            _ = cam.supervise_connection() => unreachable!(),
+            connected = state.wait_for(|state| *state == supervisor::ConnectionState::Connected) => {
+                connected?;
+            }
+        }
+        cam.send_command(control::A8MiniSimpleCommand::AutoFocus)
             .await?;
-        camera
-            .http_socket
-            .connect(format!("{}:{}", camera_ip, camera_http_port))
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_telemetry_subscriptions() -> Result<(), Box<dyn Error>> {
+        let mut zooms = [2, 3].into_iter();
+        let camera =
+            fake_camera(move |request| vec![reply(request, &[zooms.next().unwrap(), 0])]).await;
+
+        let cam = test_client(camera).await?;
+        let mut every_zoom = cam.subscribe_zoom(Duration::ZERO);
+        let mut slow_zoom = cam.subscribe_zoom(Duration::from_secs(3600));
+        let mut link = cam.subscribe_link_stats(Duration::ZERO);
+        cam.get_zoom().await?;
+        cam.get_zoom().await?;
+        drop(cam);
+
+        let mut zooms = Vec::new();
+        while let Some(sample) = every_zoom.recv().await {
+            zooms.push(sample.value);
+        }
+        assert_eq!(zooms, vec![2.0, 3.0]);
+        assert_eq!(slow_zoom.recv().await.map(|sample| sample.value), Some(2.0));
+        assert!(slow_zoom.recv().await.is_none());
+        let mut responses = Vec::new();
+        while let Some(sample) = link.recv().await {
+            responses.push(sample.value.responses);
+        }
+        assert_eq!(responses, vec![1, 2]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_deviation_alert() -> Result<(), Box<dyn Error>> {
+        let camera = fake_camera(|request| match request[7] {
+            // Stuck at yaw 30.0, pitch 0.
+            0x0d => vec![reply(request, &[0x2c, 0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])],
+            _ => vec![reply(request, &[0x01])],
+        })
+        .await;
+
+        let mut cam = test_client(camera).await?;
+        cam.set_deviation_policy(Some(deviation::DeviationPolicy::new(
+            50,
+            Duration::from_millis(100),
+        )));
+        let mut events = cam.subscribe_events();
+        cam.send_command(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(900),
+            DeciDeg(0),
+        ))
+        .await?;
+        for _ in 0..3 {
+            cam.get_attitude_information().await?;
+            tokio::time::sleep(Duration::from_millis(60)).await;
+        }
+
+        let mut alerts = Vec::new();
+        while let Ok(record) = events.try_recv() {
+            if let events::A8MiniEvent::AttitudeDeviation(deviation) = record.event {
+                alerts.push((deviation.setpoint, deviation.attitude));
+            }
+        }
+        assert_eq!(alerts, vec![((900, 0), (300, 0))]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_calibration_applied() -> Result<(), Box<dyn Error>> {
+        let camera = fake_camera(|request| match request[7] {
+            // Yaw 30.0, pitch -20.0.
+            0x0d => vec![reply(
+                request,
+                &[0x2c, 0x01, 0x38, 0xff, 0, 0, 0, 0, 0, 0, 0, 0],
+            )],
+            _ => vec![reply(request, &[0x01])],
+        })
+        .await;
+
+        let mut cam = test_client(camera).await?;
+        // Mounted facing backwards, pitch axis reversed.
+        cam.set_calibration(calibration::CalibrationOffsets {
+            yaw: 1800,
+            invert_pitch: true,
+            ..Default::default()
+        });
+        cam.send_command(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(1700),
+            DeciDeg(300),
+        ))
+        .await?;
+        cam.send_command(control::A8MiniComplexCommand::SetYawPitchSpeed(30, 20))
             .await?;
-        Ok(camera)
+        let attitude = cam.get_attitude_information().await?;
+        assert_eq!((attitude.theta_yaw, attitude.theta_pitch), (-1500, 200));
+
+        let entries = cam.audit_log();
+        let angle = &entries[0].bytes;
+        assert_eq!(
+            (
+                i16::from_le_bytes([angle[8], angle[9]]),
+                i16::from_le_bytes([angle[10], angle[11]])
+            ),
+            (-100, -300)
+        );
+        assert_eq!(entries[1].bytes[8..10], [30, (-20i8) as u8]);
+        Ok(())
     }
 
-    pub async fn send_command_blind<T: control::Command>(
-        &self,
-        command: T,
-    ) -> Result<(), Box<dyn Error>> {
-        println!(
-            "[COMMAND] Sending command with bytes: {:?}",
-            command.to_bytes()
+    #[tokio::test]
+    async fn test_angle_convention_applied() -> Result<(), Box<dyn Error>> {
+        let camera = fake_camera(|request| match request[7] {
+            // Yaw -10.0, pitch -20.0.
+            0x0d => vec![reply(
+                request,
+                &[0x9c, 0xff, 0x38, 0xff, 0, 0, 0, 0, 0, 0, 0, 0],
+            )],
+            _ => vec![reply(request, &[0x01])],
+        })
+        .await;
+
+        let mut cam = test_client(camera).await?;
+        cam.set_angle_convention(conventions::AngleConvention {
+            pitch: conventions::PitchSign::PositiveDown,
+            yaw: conventions::YawRange::Unsigned360,
+        });
+        cam.set_soft_limits(Some(
+            limits::SoftLimits::new((0, 3599), (0, 450)).with_mode(limits::LimitMode::Clamp),
+        ));
+        let mut events = cam.subscribe_events();
+
+        cam.send_command(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(3500),
+            DeciDeg(600),
+        ))
+        .await?;
+        let angle = &cam.audit_log()[0].bytes;
+        assert_eq!(
+            (
+                i16::from_le_bytes([angle[8], angle[9]]),
+                i16::from_le_bytes([angle[10], angle[11]])
+            ),
+            (-100, -450)
         );
-        println!(
-            "[COMMAND] Sending command with DATA_LEN: {:?} | CMD_ID: {:?}",
-            command.to_bytes()[3],
-            command.to_bytes()[7]
+        assert!(matches!(
+            events.recv().await?.event,
+            events::A8MiniEvent::LimitClamped {
+                requested: (3500, 600),
+                issued: (3500, 450),
+            }
+        ));
+
+        let attitude = cam.get_attitude_information().await?;
+        assert_eq!((attitude.theta_yaw, attitude.theta_pitch), (3500, 200));
+        let cached = cam.snapshot().attitude.unwrap().value;
+        assert_eq!((cached.theta_yaw, cached.theta_pitch), (3500, 200));
+        let degrees = *cam.attitude_watch().borrow();
+        assert_eq!((degrees.yaw, degrees.pitch), (350.0, 20.0));
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_attitude_estimate_follows_rate_commands() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+        cam.set_attitude_estimator(estimate::AttitudeEstimator::new(
+            1.0,
+            Duration::from_secs(1),
+        ));
+        assert_eq!(cam.attitude_estimate(), None);
+
+        cam.publish_attitude(
+            control::A8MiniAtittude {
+                theta_yaw: 100,
+                theta_pitch: 0,
+                theta_roll: 0,
+                v_yaw: 0,
+                v_pitch: 0,
+                v_roll: 0,
+            },
+            clock::now(),
         );
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchSpeed(20, -10))
+            .await?;
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let estimate = cam.snapshot().estimate.unwrap();
+        assert_eq!((estimate.yaw, estimate.pitch), (20.0, -5.0));
 
-        if self
-            .command_socket
-            .send(command.to_bytes().as_slice())
-            .await?
-            == 0
-        {
-            println!("[COMMAND] No bytes sent.");
-            return Err("No bytes sent.".into());
-        }
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchSpeed(0, 0))
+            .await?;
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(cam.attitude_estimate().unwrap().yaw, 20.0);
+        Ok(())
+    }
 
-        println!("[COMMAND] Command sent successfully.");
+    #[tokio::test]
+    async fn test_set_utc_time() -> Result<(), Box<dyn Error>> {
+        let (times, mut sent_time) = mpsc::unbounded_channel();
+        let camera = fake_camera(move |request| {
+            assert_eq!(request[7], 0x30);
+            times
+                .send(u64::from_le_bytes(request[8..16].try_into().unwrap()))
+                .unwrap();
+            vec![reply(request, &[0x01])]
+        })
+        .await;
 
+        let cam = test_client(camera).await?;
+        let time = std::time::UNIX_EPOCH + Duration::from_micros(1_700_000_000_654_321);
+        cam.set_utc_time(time).await?;
+        assert_eq!(take(&mut sent_time, 1).await, [1_700_000_000_654_321]);
         Ok(())
     }
 
-    pub async fn send_command<T: control::Command>(
-        &self,
-        command: T,
-    ) -> Result<[u8; constants::RECV_BUFF_SIZE], Box<dyn Error>> {
-        self.send_command_blind(command).await?;
-        let mut recv_buffer = [0; constants::RECV_BUFF_SIZE];
+    #[tokio::test]
+    async fn test_attitude_stream() -> Result<(), Box<dyn Error>> {
+        use futures_core::Stream;
 
-        println!("[COMMAND] Waiting for response.");
+        let camera = fake_camera(|request| {
+            assert_eq!((request[7], request[8], request[9]), (0x25, 0x01, 0x04));
+            let mut frames = Vec::new();
+            for theta_yaw in [10i16, 20, 30] {
+                let mut data = theta_yaw.to_le_bytes().to_vec();
+                data.extend_from_slice(&[0; 10]);
+                frames.push(test_frame(0x0d, &data));
+                // Something other than attitude in between.
+                frames.push(test_frame(0x0b, &[0x00]));
+            }
+            frames
+        })
+        .await;
 
-        let recv_len = timeout(
-            constants::RECV_TIMEOUT,
-            self.command_socket.recv(&mut recv_buffer),
-        )
-        .await??;
-        if recv_len == 0 {
-            println!("[COMMAND] No bytes received.");
-            return Err("No bytes received.".into());
+        let cam = test_client(camera).await?;
+        let mut stream = std::pin::pin!(
+            cam.attitude_stream(control::AttitudeStreamRate::Hz10)
+                .await?
+        );
+        let mut yaws = Vec::new();
+        while yaws.len() < 3 {
+            let sample = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx))
+                .await
+                .unwrap();
+            yaws.push(sample.attitude.theta_yaw);
         }
+        assert_eq!(yaws, vec![10, 20, 30]);
+        Ok(())
+    }
 
-        println!(
-            "[COMMAND] Response of size {} received successfully: {:?}",
-            recv_len, recv_buffer
+    #[tokio::test]
+    async fn test_is_recording() -> Result<(), Box<dyn Error>> {
+        let mut record_stas = [1, 2].into_iter();
+        let camera = fake_camera(move |request| {
+            assert_eq!(request[7], 0x0a);
+            let record_sta = record_stas.next().unwrap();
+            vec![reply(request, &[0, 0, 0, record_sta, 0, 1])]
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        assert_eq!(
+            cam.is_recording().await?,
+            control::RecordingStatus::Recording
         );
-        Ok(recv_buffer)
+        assert_eq!(cam.is_recording().await?, control::RecordingStatus::NoCard);
+        Ok(())
     }
 
-    pub async fn get_attitude_information(
-        &self,
-    ) -> Result<control::A8MiniAtittude, Box<dyn Error>> {
-        let attitude_bytes = self
-            .send_command(control::A8MiniSimpleCommand::AttitudeInformation)
+    #[tokio::test]
+    async fn test_park_and_unpark() -> Result<(), Box<dyn Error>> {
+        let mut attitude = [0i16; 2];
+        let camera = fake_camera(move |request| {
+            let data = match request[7] {
+                // Mounted upside down.
+                0x0a => vec![0, 0, 0, 0, 1, 2],
+                0x0e => {
+                    attitude = [
+                        i16::from_le_bytes([request[8], request[9]]),
+                        i16::from_le_bytes([request[10], request[11]]),
+                    ];
+                    vec![1]
+                }
+                0x08 => {
+                    attitude = [0, 0];
+                    vec![1]
+                }
+                0x0d => {
+                    let mut data = attitude[0].to_le_bytes().to_vec();
+                    data.extend_from_slice(&attitude[1].to_le_bytes());
+                    data.extend_from_slice(&[0; 8]);
+                    data
+                }
+                _ => vec![1],
+            };
+            vec![reply(request, &data)]
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        cam.park().await?;
+        assert!(cam.is_parked());
+        assert_eq!(cam.snapshot().attitude.unwrap().value.theta_pitch, -250);
+        assert!(cam
+            .send_command(control::A8MiniComplexCommand::SetYawPitchSpeed(10, 0))
+            .await
+            .is_err());
+        cam.send_command(control::A8MiniComplexCommand::SetYawPitchSpeed(0, 0))
             .await?;
-        let attitude_info: control::A8MiniAtittude = deserialize(&attitude_bytes)?;
-        Ok(attitude_info)
+
+        cam.unpark().await?;
+        assert!(!cam.is_parked());
+        assert_eq!(cam.snapshot().attitude.unwrap().value.theta_pitch, 0);
+        cam.send_command(control::A8MiniComplexCommand::SetYawPitchSpeed(10, 0))
+            .await?;
+        Ok(())
     }
 
-    pub async fn send_http_query<T: control::HTTPQuery>(
-        &self,
-        query: T,
-    ) -> Result<control::HTTPResponse, Box<dyn Error>> {
-        let response = reqwest::get(query.to_string()).await?;
-        println!("[HTTP] Waiting for response.");
+    #[tokio::test]
+    async fn test_start_and_stop_recording() -> Result<(), Box<dyn Error>> {
+        // start (toggles), start again (no-op), stop (toggles)
+        let (toggled, mut toggles) = mpsc::unbounded_channel();
+        let mut recording = 0;
+        let camera = fake_camera(move |request| match request[7] {
+            0x0a => vec![reply(request, &[0, 0, 0, recording, 1, 1])],
+            0x0c => {
+                assert_eq!(request[8], 0x02);
+                recording ^= 1;
+                toggled.send(()).unwrap();
+                Vec::new()
+            }
+            cmd_id => panic!("Unexpected CMD_ID {:#04x}", cmd_id),
+        })
+        .await;
 
-        let json = response.json::<control::HTTPResponse>().await?;
-        Ok(json)
+        let cam = test_client(camera).await?;
+        cam.start_recording().await?;
+        assert_eq!(
+            cam.snapshot().recording(),
+            Some(control::RecordingStatus::Recording)
+        );
+        cam.start_recording().await?;
+        cam.stop_recording().await?;
+        assert_eq!(
+            cam.snapshot().recording(),
+            Some(control::RecordingStatus::NotRecording)
+        );
+        take(&mut toggles, 2).await;
+        assert!(toggles.try_recv().is_err());
+        Ok(())
     }
 
-    pub async fn send_http_image_query<T: control::HTTPQuery>(
-        &self,
-        query: T,
-    ) -> Result<Vec<u8>, Box<dyn Error>> {
-        let response = reqwest::get(query.to_string()).await?;
-        println!("[HTTP] Waiting for response.");
+    #[tokio::test]
+    async fn test_rotate_recording_while_recording() -> Result<(), Box<dyn Error>> {
+        use std::sync::atomic::AtomicU8;
+
+        // Already recording when the rotation starts.
+        let recording = Arc::new(AtomicU8::new(1));
+        let state = recording.clone();
+        let camera = fake_camera(move |request| match request[7] {
+            0x0a => vec![reply(
+                request,
+                &[0, 0, 0, state.load(Ordering::SeqCst), 1, 1],
+            )],
+            0x0c => {
+                state.fetch_xor(1, Ordering::SeqCst);
+                Vec::new()
+            }
+            cmd_id => panic!("Unexpected CMD_ID {:#04x}", cmd_id),
+        })
+        .await;
 
-        let image_bytes = response.bytes().await?;
-        Ok(image_bytes.to_vec())
+        let cam = test_client(camera).await?;
+        let (stop, stop_rx) = watch::channel(false);
+        let rotation = recording::SegmentRotation::new(Duration::from_millis(100))
+            .with_restart_gap(Duration::from_millis(200));
+        let (segments, _) = tokio::join!(cam.rotate_recording(rotation, stop_rx), async {
+            // Stop during the first restart gap.
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            stop.send_replace(true);
+        });
+        assert_eq!(segments?.len(), 1);
+        assert_eq!(recording.load(Ordering::SeqCst), 0);
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use tokio::fs::File;
-    use tokio::io::AsyncWriteExt;
+    #[tokio::test]
+    async fn test_late_response_within_grace() -> Result<(), Box<dyn Error>> {
+        let camera = UdpSocket::bind("127.0.0.1:0").await?;
+        let camera_addr = camera.local_addr()?;
+        tokio::spawn(async move {
+            let mut request = [0; 64];
+            for delay in [600, 1200] {
+                let (_, client) = camera.recv_from(&mut request).await.unwrap();
+                let mut ack = test_frame(request[7], &[0x01]);
+                checksum::set_seq(&mut ack, checksum::seq(&request));
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                camera.send_to(&ack, client).await.unwrap();
+            }
+        });
 
-    use super::*;
-    use std::thread::sleep;
-    use std::time::Duration;
+        let mut cam = test_client(camera_addr).await?;
+        cam.set_response_grace(Some(Duration::from_millis(300)));
+        // Just past the response timeout, but within the grace window.
+        cam.send_command(control::A8MiniSimpleCommand::AutoFocus)
+            .await?;
+        // Past the grace window as well.
+        assert!(matches!(
+            cam.send_command(control::A8MiniSimpleCommand::AutoFocus)
+                .await,
+            Err(A8MiniError::Timeout)
+        ));
+        let link = cam.snapshot().link;
+        assert_eq!(
+            (link.responses, link.late_responses, link.timeouts),
+            (1, 1, 1)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_command_retries() -> Result<(), Box<dyn Error>> {
+        // The first request goes unanswered, and every one after the second.
+        let mut answers = [false, true].into_iter();
+        let camera = fake_camera(move |request| match answers.next() {
+            Some(true) => vec![reply(request, &[0x01])],
+            _ => Vec::new(),
+        })
+        .await;
+
+        let client_config = config::ClientConfig {
+            ack_timeout: Duration::from_millis(100),
+            retries: 1,
+            retry_delay: Duration::from_millis(10),
+            ..Default::default()
+        };
+        let mut cam = test_client(camera).await?;
+        cam.set_client_config(client_config);
+        cam.send_command(control::A8MiniSimpleCommand::AutoFocus)
+            .await?;
+        let link = cam.snapshot().link;
+        assert_eq!((link.responses, link.timeouts), (1, 1));
+
+        // Out of retries.
+        assert!(matches!(
+            cam.send_command(control::A8MiniSimpleCommand::AutoFocus)
+                .await,
+            Err(A8MiniError::Timeout)
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_builder() -> Result<(), Box<dyn Error>> {
+        let mut answers = [false, true].into_iter();
+        let camera = fake_camera(move |request| match answers.next() {
+            Some(true) => vec![reply(request, &[0x01])],
+            _ => Vec::new(),
+        })
+        .await;
+
+        let cam = A8Mini::builder()
+            .camera_addr("127.0.0.1")
+            .camera_command_port(camera.port())
+            .local_command_port(0)
+            .local_http_port(0)
+            .ack_timeout(Duration::from_millis(100))
+            .retries(1)
+            .log_target(jsonlog::JsonLog::new(std::io::sink()))
+            .connect()
+            .await?;
+        assert_eq!(cam.client_config().retries, 1);
+        cam.send_command(control::A8MiniSimpleCommand::AutoFocus)
+            .await?;
+
+        assert!(matches!(
+            A8Mini::connect_to("127.0.0.1", "37260", "http", "0", "0", Default::default()).await,
+            Err(A8MiniError::InvalidArgument(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_camera_report() -> Result<(), Box<dyn Error>> {
+        let camera = fake_camera(|request| {
+            // Only identification and max zoom are answered.
+            let data: &[u8] = match request[7] {
+                0x01 => &[1, 2, 0, 0, 3, 4, 0, 0],
+                0x02 => b"7315A8000000",
+                0x16 => &[6, 0],
+                _ => return Vec::new(),
+            };
+            vec![reply(request, data)]
+        })
+        .await;
+
+        let client_config = config::ClientConfig {
+            ack_timeout: Duration::from_millis(100),
+            ..Default::default()
+        };
+        let mut cam = test_client(camera).await?;
+        cam.set_client_config(client_config);
+        let report = cam.camera_report().await?;
+        assert_eq!(report.hardware_id, "7315A8000000");
+        assert_eq!(report.model, models::CameraModel::A8Mini);
+        assert_eq!(report.firmware.gimbal.to_string(), "v0.4.3");
+        assert_eq!(report.max_zoom, Some(6.0));
+        assert!(report.probes["max_zoom"]);
+        assert!(!report.probes["gimbal_info"]);
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json())?;
+        assert_eq!(json["capabilities"]["zoom"], true);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receiver_serves_concurrent_requests() -> Result<(), Box<dyn Error>> {
+        let camera = UdpSocket::bind("127.0.0.1:0").await?;
+        let camera_addr = camera.local_addr()?;
+        tokio::spawn(async move {
+            let mut requests = Vec::new();
+            let mut client = None;
+            for _ in 0..2 {
+                let mut request = [0; 64];
+                let (_, from) = camera.recv_from(&mut request).await.unwrap();
+                requests.push(request);
+                client = Some(from);
+            }
+            let client = client.unwrap();
+            camera
+                .send_to(&test_frame(0x0b, &[0x03]), client)
+                .await
+                .unwrap();
+            for request in requests.iter().rev() {
+                let mut ack = test_frame(request[7], &[0x01]);
+                checksum::set_seq(&mut ack, checksum::seq(request));
+                camera.send_to(&ack, client).await.unwrap();
+            }
+        });
+
+        let cam = test_client(camera_addr).await?;
+        let mut events = cam.subscribe_events();
+        let (first, second) = tokio::join!(
+            cam.send_command(control::A8MiniSimpleCommand::AutoFocus),
+            cam.send_command(control::A8MiniSimpleCommand::AutoFocus),
+        );
+        assert_eq!(checksum::seq(&first?), 0);
+        assert_eq!(checksum::seq(&second?), 1);
+        let mut unsolicited = Vec::new();
+        while let Ok(record) = events.try_recv() {
+            if let events::A8MiniEvent::UnsolicitedFrame { cmd_id, .. } = record.event {
+                unsolicited.push(cmd_id);
+            }
+        }
+        assert_eq!(unsolicited, vec![0x0b]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_zoom_validates_range() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+        let error = cam.set_zoom(7.5).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<A8MiniError>(),
+            Some(A8MiniError::InvalidArgument(_))
+        ));
+        assert!(cam.audit_log().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_profile_latency() -> Result<(), Box<dyn Error>> {
+        let camera = UdpSocket::bind("127.0.0.1:0").await?;
+        let cam = test_client(camera.local_addr()?).await?;
+        let baseline = control::A8MiniAtittude {
+            theta_yaw: 0,
+            theta_pitch: -100,
+            theta_roll: 0,
+            v_yaw: 0,
+            v_pitch: 0,
+            v_roll: 0,
+        };
+        cam.telemetry.lock().unwrap().attitude =
+            Some(telemetry::Timestamped::new(baseline, clock::now()));
+
+        // The gimbal starts moving 20 ms after each step and ignores the third one.
+        let attitude_samples = cam.attitude_samples.clone();
+        tokio::spawn(async move {
+            let mut request = [0; 64];
+            for step in 0..3 {
+                let (len, _) = camera.recv_from(&mut request).await.unwrap();
+                let (theta_yaw, theta_pitch) = replay::RecordedCommand(request[..len].to_vec())
+                    .angle_setpoint()
+                    .unwrap();
+                if step == 2 {
+                    continue;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                let attitude = control::A8MiniAtittude {
+                    theta_yaw,
+                    theta_pitch,
+                    ..baseline
+                };
+                attitude_samples
+                    .send(telemetry::AttitudeSample {
+                        attitude,
+                        received: clock::now(),
+                    })
+                    .unwrap();
+            }
+        });
+
+        let report = cam
+            .profile_latency(3, 50, Duration::from_millis(50))
+            .await?;
+        assert_eq!(report.samples.len(), 2);
+        assert_eq!(report.missed, 1);
+        let stats = report.stats.unwrap();
+        assert!(stats.min >= Duration::from_millis(20));
+        assert!(stats.max < constants::LATENCY_STEP_TIMEOUT);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_burst() -> Result<(), Box<dyn Error>> {
+        // Save the first photo (after unrelated HDR feedback), fail the second and leave the
+        // third unanswered.
+        let mut feedback = [vec![2, 0], vec![1]].into_iter();
+        let camera = fake_camera(move |_| {
+            feedback
+                .next()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|info_type| test_frame(0x0b, &[info_type]))
+                .collect()
+        })
+        .await;
+
+        let cam = test_client(camera).await?;
+        let outcomes: Vec<_> = cam
+            .burst(3)
+            .await?
+            .into_iter()
+            .map(|shot| shot.outcome)
+            .collect();
+
+        assert!(matches!(outcomes[0], capture::ShotOutcome::Saved(_)));
+        assert_eq!(
+            outcomes[1..],
+            [
+                capture::ShotOutcome::Failed,
+                capture::ShotOutcome::NoFeedback
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_correlation_ids() -> Result<(), Box<dyn Error>> {
+        let mut cam = test_client("127.0.0.1:37260".parse()?).await?;
+        cam.set_dry_run(true);
+
+        cam.send_command_blind(control::A8MiniSimpleCommand::AutoCenter)
+            .await?;
+        correlation::correlated(async {
+            cam.send_command_blind(control::A8MiniSimpleCommand::TakePicture)
+                .await?;
+            cam.send_command_blind(control::A8MiniSimpleCommand::RecordVideo)
+                .await
+        })
+        .await?;
+
+        let ids: Vec<_> = cam
+            .audit_log()
+            .into_iter()
+            .map(|entry| entry.correlation.unwrap())
+            .collect();
+        assert_eq!(ids.len(), 3);
+        assert_ne!(ids[0], ids[1]);
+        assert_eq!(ids[1], ids[2]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delta_suppression() -> Result<(), Box<dyn Error>> {
+        // Every setpoint is acknowledged except yaw 11.0.
+        let camera = fake_camera(|request| match request[7] {
+            0x0e if request[8..10] == 110i16.to_le_bytes() => Vec::new(),
+            _ => vec![reply(request, &[0x01])],
+        })
+        .await;
+        let mut cam = test_client(camera).await?;
+        cam.set_client_config(config::ClientConfig {
+            ack_timeout: Duration::from_millis(100),
+            retries: 0,
+            ..Default::default()
+        });
+        cam.set_delta_filter(Some(pacing::DeltaFilter::new(2, 0)));
+
+        let mut acknowledged = Vec::new();
+        for command in [
+            control::A8MiniComplexCommand::SetYawPitchAngle(DeciDeg(100), DeciDeg(0)),
+            control::A8MiniComplexCommand::SetYawPitchAngle(DeciDeg(101), DeciDeg(0)),
+            // Unacknowledged, so the same setpoint is sent again.
+            control::A8MiniComplexCommand::SetYawPitchAngle(DeciDeg(110), DeciDeg(0)),
+            control::A8MiniComplexCommand::SetYawPitchAngle(DeciDeg(110), DeciDeg(0)),
+            control::A8MiniComplexCommand::SetYawPitchSpeed(5, 0),
+            control::A8MiniComplexCommand::SetYawPitchSpeed(5, 0),
+        ] {
+            acknowledged.push(cam.send_command(command).await.is_ok());
+        }
+        // Blind setpoints are the reference as soon as they are sent.
+        for _ in 0..2 {
+            cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchSpeed(10, 0))
+                .await?;
+        }
+
+        assert_eq!(acknowledged, [true, false, false, false, true, false]);
+        let outcomes: Vec<_> = cam
+            .audit_log()
+            .into_iter()
+            .map(|entry| entry.outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                audit::AuditOutcome::Sent,
+                audit::AuditOutcome::Suppressed,
+                audit::AuditOutcome::Sent,
+                audit::AuditOutcome::Sent,
+                audit::AuditOutcome::Sent,
+                audit::AuditOutcome::Suppressed,
+                audit::AuditOutcome::Sent,
+                audit::AuditOutcome::Suppressed,
+            ]
+        );
+        // Told apart from a link failure.
+        assert!(matches!(
+            cam.send_command(control::A8MiniComplexCommand::SetYawPitchSpeed(10, 0))
+                .await,
+            Err(A8MiniError::NotSent(audit::AuditOutcome::Suppressed))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delta_suppression_blind_loop() -> Result<(), Box<dyn Error>> {
+        let (setpoints, mut received) = mpsc::unbounded_channel();
+        let camera = fake_camera(move |request| {
+            setpoints
+                .send((
+                    i16::from_le_bytes([request[8], request[9]]),
+                    checksum::seq(request),
+                ))
+                .unwrap();
+            vec![reply(request, &[0x01])]
+        })
+        .await;
+        let mut cam = test_client(camera).await?;
+        cam.set_delta_filter(Some(pacing::DeltaFilter::new(2, 0)));
+
+        // A control loop publishing at a fixed rate, with one change of setpoint.
+        for yaw in [10.0, 10.0, 10.1, 20.0, 20.0] {
+            cam.set_attitude_degrees(yaw, 0.0).await?;
+        }
+
+        // Suppressed setpoints take no SEQ.
+        assert_eq!(take(&mut received, 2).await, [(100, 0), (200, 1)]);
+        let outcomes: Vec<_> = cam
+            .audit_log()
+            .into_iter()
+            .map(|entry| entry.outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                audit::AuditOutcome::Sent,
+                audit::AuditOutcome::Suppressed,
+                audit::AuditOutcome::Suppressed,
+                audit::AuditOutcome::Sent,
+                audit::AuditOutcome::Suppressed,
+            ]
+        );
+        Ok(())
+    }
 
     #[tokio::test]
     async fn test_control_lock() -> Result<(), Box<dyn Error>> {
         let cam: A8Mini = A8Mini::connect().await?;
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(900, 0))
-            .await?;
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(900),
+            DeciDeg(0),
+        ))
+        .await?;
         sleep(Duration::from_millis(1000));
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(900, -900))
-            .await?;
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(900),
+            DeciDeg(-900),
+        ))
+        .await?;
         sleep(Duration::from_millis(1000));
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(900, 250))
-            .await?;
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(900),
+            DeciDeg(250),
+        ))
+        .await?;
         sleep(Duration::from_millis(1000));
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(-900, 0))
-            .await?;
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(-900),
+            DeciDeg(0),
+        ))
+        .await?;
         sleep(Duration::from_millis(2500));
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(-900, -900))
-            .await?;
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(-900),
+            DeciDeg(-900),
+        ))
+        .await?;
         sleep(Duration::from_millis(1000));
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(-900, 250))
-            .await?;
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(-900),
+            DeciDeg(250),
+        ))
+        .await?;
         sleep(Duration::from_millis(1000));
 
         cam.send_command_blind(control::A8MiniSimpleCommand::AutoCenter)
@@ -194,7 +5710,9 @@ mod tests {
             .unwrap();
         dbg!(num_pictures);
         let picture_bytes = cam
-            .send_http_image_query(control::A8MiniComplexHTTPQuery::GetPhoto(num_pictures as u8))
+            .send_http_image_query(control::A8MiniComplexHTTPQuery::GetPhoto(
+                num_pictures as u8,
+            ))
             .await?;
         File::create("tmp.jpeg")
             .await?
@@ -260,24 +5778,39 @@ mod tests {
             .await?;
         sleep(Duration::from_millis(1000));
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(90, 0))
-            .await?;
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(90),
+            DeciDeg(0),
+        ))
+        .await?;
         sleep(Duration::from_millis(1000));
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(90, -90))
-            .await?;
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(90),
+            DeciDeg(-90),
+        ))
+        .await?;
         sleep(Duration::from_millis(1000));
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(-90, -90))
-            .await?;
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(-90),
+            DeciDeg(-90),
+        ))
+        .await?;
         sleep(Duration::from_millis(1000));
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(-90, 0))
-            .await?;
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(-90),
+            DeciDeg(0),
+        ))
+        .await?;
         sleep(Duration::from_millis(1000));
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(0, 0))
-            .await?;
+        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(
+            DeciDeg(0),
+            DeciDeg(0),
+        ))
+        .await?;
         sleep(Duration::from_millis(1000));
 
         cam.send_command_blind(control::A8MiniSimpleCommand::AutoCenter)
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+/// Behaviour when an angle setpoint falls outside the configured soft limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitMode {
+    /// Refuse to send the command and return an error.
+    #[default]
+    Reject,
+    /// Send the nearest legal angle instead and emit a `LimitClamped` event.
+    Clamp,
+}
+
+/// Client-side soft travel limits for angle setpoints. Angles are in degrees * 10.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SoftLimits {
+    pub yaw_min: i16,
+    pub yaw_max: i16,
+    pub pitch_min: i16,
+    pub pitch_max: i16,
+    pub mode: LimitMode,
+}
+
+impl SoftLimits {
+    pub fn new(yaw_range: (i16, i16), pitch_range: (i16, i16)) -> Self {
+        Self {
+            yaw_min: yaw_range.0.min(yaw_range.1),
+            yaw_max: yaw_range.0.max(yaw_range.1),
+            pitch_min: pitch_range.0.min(pitch_range.1),
+            pitch_max: pitch_range.0.max(pitch_range.1),
+            mode: LimitMode::default(),
+        }
+    }
+
+    pub fn with_mode(mut self, mode: LimitMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn contains(&self, theta_yaw: i16, theta_pitch: i16) -> bool {
+        (self.yaw_min..=self.yaw_max).contains(&theta_yaw)
+            && (self.pitch_min..=self.pitch_max).contains(&theta_pitch)
+    }
+
+    /// Returns the nearest setpoint that lies within the limits.
+    pub fn clamp(&self, theta_yaw: i16, theta_pitch: i16) -> (i16, i16) {
+        (
+            theta_yaw.clamp(self.yaw_min, self.yaw_max),
+            theta_pitch.clamp(self.pitch_min, self.pitch_max),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soft_limits_contains() {
+        let limits = SoftLimits::new((-900, 900), (-450, 0));
+        assert!(limits.contains(0, 0));
+        assert!(limits.contains(900, -450));
+        assert!(!limits.contains(901, 0));
+        assert!(!limits.contains(0, 10));
+        assert_eq!(limits.mode, LimitMode::Reject);
+    }
+
+    #[test]
+    fn test_soft_limits_clamp() {
+        let limits = SoftLimits::new((900, -900), (-450, 0)).with_mode(LimitMode::Clamp);
+        assert_eq!(limits.clamp(1350, -900), (900, -450));
+        assert_eq!(limits.clamp(-1000, 250), (-900, 0));
+        assert_eq!(limits.clamp(100, -100), (100, -100));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::correlation::{self, CorrelationId};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Number of clients writing a JSON log. While any does, the human-readable lines keep out of
+/// its stream.
+static TEXT_TO_STDERR: AtomicUsize = AtomicUsize::new(0);
+
+/// Writes one human-readable log line, such as `[COMMAND] ...`, tagged with the current
+/// correlation ID. With a `tracing` subscriber installed it becomes an `info` event, inside the
+/// send and ack spans of the command; otherwise it is printed.
+macro_rules! log_line {
+    ($($arg:tt)*) => {
+        $crate::logging::write_line(format_args!($($arg)*))
+    };
+}
+
+/// Sends the printed log lines to stderr until dropped. Stdout is process-wide, so the lines go
+/// back to it only once every client holding one has dropped it.
+#[derive(Debug)]
+pub struct TextToStderr(());
+
+impl TextToStderr {
+    pub fn acquire() -> Self {
+        TEXT_TO_STDERR.fetch_add(1, Ordering::Relaxed);
+        TextToStderr(())
+    }
+}
+
+impl Drop for TextToStderr {
+    fn drop(&mut self) {
+        TEXT_TO_STDERR.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+pub fn text_to_stderr() -> bool {
+    TEXT_TO_STDERR.load(Ordering::Relaxed) > 0
+}
+
+#[doc(hidden)]
+pub fn write_line(args: fmt::Arguments<'_>) {
+    let correlation = correlation::current();
+    if tracing::dispatcher::has_been_set() {
+        match correlation {
+            Some(id) => tracing::info!(correlation = %id, "{}", args),
+            None => tracing::info!("{}", args),
+        }
+        return;
+    }
+
+    let line = with_correlation(&args.to_string(), correlation);
+    if text_to_stderr() {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// Inserts the correlation ID after the line's `[TAG]`, or in front of an untagged line.
+fn with_correlation(line: &str, correlation: Option<CorrelationId>) -> String {
+    let Some(id) = correlation else {
+        return line.to_string();
+    };
+    match line.split_once("] ") {
+        Some((tag, rest)) if tag.starts_with('[') => format!("{}] {} {}", tag, id, rest),
+        _ => format!("{} {}", id, line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_correlation() {
+        let id = CorrelationId::generate();
+        assert_eq!(
+            with_correlation("[COMMAND] Waiting for response.", Some(id)),
+            format!("[COMMAND] {} Waiting for response.", id)
+        );
+        assert_eq!(
+            with_correlation("No tag.", Some(id)),
+            format!("{} No tag.", id)
+        );
+        assert_eq!(
+            with_correlation("[COMMAND] Waiting for response.", None),
+            "[COMMAND] Waiting for response."
+        );
+    }
+
+    #[test]
+    fn test_text_to_stderr_counted() {
+        assert!(!text_to_stderr());
+        let first = TextToStderr::acquire();
+        let second = TextToStderr::acquire();
+        drop(first);
+        assert!(text_to_stderr());
+        drop(second);
+        assert!(!text_to_stderr());
+    }
+}
@@ -11,7 +11,8 @@ fn print_ascii_command_table() {
     "MaxZoomInformation", "FocusIn", "FocusOut", "TakePicture", "RecordVideo", "Rotate100100", "CameraInformation",
     "AutoFocus", "HardwareIDInformation", "FirmwareVersionInformation", "SetLockMode", "SetFollowMode", "SetFPVMode",
     "AttitudeInformation", "SetVideoOutputHDMI", "SetVideoOutputCVBS", "SetVideoOutputOff", "LaserRangefinderInformation", 
-    "RebootCamera", "RebootGimbal",
+    "RebootCamera", "RebootGimbal", "CurrentZoomInformation", "NetworkInformation",
+    "ToggleHDR", "ImageModeInformation", "ZoomStop", "FocusStop",
   ];
 
   let header = "+----+------------------------------+";
@@ -31,12 +32,11 @@ async fn main() -> Result<(), Box<dyn Error>> {
   print_ascii_command_table();
   
   loop {
-    let command: &str;
     println!("Awaiting command:");
     let stdin = io::stdin();
     let buf = &mut String::new();
     stdin.read_line(buf)?;
-    command = buf.strip_suffix("\n").unwrap();
+    let command: &str = buf.strip_suffix("\n").unwrap();
 
     let command_enum: Option<A8MiniSimpleCommand> = match command {
       "0"   | "AutoCenter" => Some(A8MiniSimpleCommand::AutoCenter),
@@ -68,13 +68,19 @@ async fn main() -> Result<(), Box<dyn Error>> {
       "26"  | "LaserRangefinderInformation" => Some(A8MiniSimpleCommand::LaserRangefinderInformation),
       "27"  | "RebootCamera" => Some(A8MiniSimpleCommand::RebootCamera),
       "28"  | "RebootGimbal" => Some(A8MiniSimpleCommand::RebootGimbal),
+      "29"  | "CurrentZoomInformation" => Some(A8MiniSimpleCommand::CurrentZoomInformation),
+      "30"  | "NetworkInformation" => Some(A8MiniSimpleCommand::NetworkInformation),
+      "31"  | "ToggleHDR" => Some(A8MiniSimpleCommand::ToggleHDR),
+      "32"  | "ImageModeInformation" => Some(A8MiniSimpleCommand::ImageModeInformation),
+      "33"  | "ZoomStop" => Some(A8MiniSimpleCommand::ZoomStop),
+      "34"  | "FocusStop" => Some(A8MiniSimpleCommand::FocusStop),
       _ => None,
     };
 
-    if command_enum.is_some() {
+    if let Some(command_enum) = command_enum {
       println!("Sending {:?}", command_enum);
       let camera: A8Mini = A8Mini::connect().await?;
-      camera.send_command_blind(command_enum.unwrap()).await?;
+      camera.send_command_blind(command_enum).await?;
     }
     else {
       break;
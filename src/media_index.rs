@@ -0,0 +1,266 @@
+use crate::capture::CaptureKind;
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Download state of a media file in the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadStatus {
+    Seen,
+    Downloaded,
+    Failed,
+}
+
+impl DownloadStatus {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            DownloadStatus::Seen => "seen",
+            DownloadStatus::Downloaded => "downloaded",
+            DownloadStatus::Failed => "failed",
+        }
+    }
+
+    fn from_str(status: &str) -> Option<Self> {
+        match status {
+            "seen" => Some(DownloadStatus::Seen),
+            "downloaded" => Some(DownloadStatus::Downloaded),
+            "failed" => Some(DownloadStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// One media file known to the index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaRecord {
+    pub kind: CaptureKind,
+    pub index: u32,
+    pub status: DownloadStatus,
+    pub sha256: Option<String>,
+    pub seen_at_unix_ms: i64,
+    pub downloaded_at_unix_ms: Option<i64>,
+}
+
+/// Outcome of an offload run, by media index.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OffloadReport {
+    pub downloaded: Vec<u32>,
+    pub skipped: Vec<u32>,
+    pub failed: Vec<u32>,
+}
+
+/// SQLite-backed index of media seen on the camera and downloaded locally.
+#[derive(Debug)]
+pub struct MediaIndex {
+    connection: Connection,
+}
+
+impl MediaIndex {
+    pub fn open(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, Box<dyn Error>> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(connection: Connection) -> Result<Self, Box<dyn Error>> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS media (
+                kind TEXT NOT NULL,
+                idx INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                sha256 TEXT,
+                seen_at INTEGER NOT NULL,
+                downloaded_at INTEGER,
+                PRIMARY KEY (kind, idx)
+            );",
+        )?;
+        Ok(Self { connection })
+    }
+
+    /// Records that the camera reported a media file. Existing entries are left untouched.
+    pub fn record_seen(&self, kind: CaptureKind, index: u32) -> Result<(), Box<dyn Error>> {
+        self.connection.execute(
+            "INSERT OR IGNORE INTO media (kind, idx, status, seen_at) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                kind_str(kind),
+                index,
+                DownloadStatus::Seen.as_str(),
+                now_unix_ms()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Records a completed download along with the SHA-256 of its contents.
+    pub fn record_downloaded(
+        &self,
+        kind: CaptureKind,
+        index: u32,
+        contents: &[u8],
+    ) -> Result<(), Box<dyn Error>> {
+        self.record_seen(kind, index)?;
+        self.connection.execute(
+            "UPDATE media SET status = ?3, sha256 = ?4, downloaded_at = ?5 WHERE kind = ?1 AND idx = ?2",
+            params![
+                kind_str(kind),
+                index,
+                DownloadStatus::Downloaded.as_str(),
+                sha256_hex(contents),
+                now_unix_ms()
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn record_failed(&self, kind: CaptureKind, index: u32) -> Result<(), Box<dyn Error>> {
+        self.record_seen(kind, index)?;
+        self.connection.execute(
+            "UPDATE media SET status = ?3 WHERE kind = ?1 AND idx = ?2 AND status != ?4",
+            params![
+                kind_str(kind),
+                index,
+                DownloadStatus::Failed.as_str(),
+                DownloadStatus::Downloaded.as_str()
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get(
+        &self,
+        kind: CaptureKind,
+        index: u32,
+    ) -> Result<Option<MediaRecord>, Box<dyn Error>> {
+        let row = self
+            .connection
+            .query_row(
+                "SELECT status, sha256, seen_at, downloaded_at FROM media WHERE kind = ?1 AND idx = ?2",
+                params![kind_str(kind), index],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                        row.get::<_, i64>(2)?,
+                        row.get::<_, Option<i64>>(3)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        let Some((status, sha256, seen_at_unix_ms, downloaded_at_unix_ms)) = row else {
+            return Ok(None);
+        };
+
+        Ok(Some(MediaRecord {
+            kind,
+            index,
+            status: DownloadStatus::from_str(&status).ok_or("Unknown download status in index.")?,
+            sha256,
+            seen_at_unix_ms,
+            downloaded_at_unix_ms,
+        }))
+    }
+
+    pub fn is_downloaded(&self, kind: CaptureKind, index: u32) -> Result<bool, Box<dyn Error>> {
+        Ok(self
+            .get(kind, index)?
+            .is_some_and(|record| record.status == DownloadStatus::Downloaded))
+    }
+
+    /// Indices that were seen on the camera but not downloaded yet.
+    pub fn pending(&self, kind: CaptureKind) -> Result<Vec<u32>, Box<dyn Error>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT idx FROM media WHERE kind = ?1 AND status != ?2 ORDER BY idx")?;
+        let indices = statement
+            .query_map(
+                params![kind_str(kind), DownloadStatus::Downloaded.as_str()],
+                |row| row.get(0),
+            )?
+            .collect::<Result<Vec<u32>, _>>()?;
+        Ok(indices)
+    }
+
+    /// Indices missing from the index between 1 and the highest index seen.
+    pub fn gaps(&self, kind: CaptureKind) -> Result<Vec<u32>, Box<dyn Error>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT idx FROM media WHERE kind = ?1 ORDER BY idx")?;
+        let indices = statement
+            .query_map(params![kind_str(kind)], |row| row.get(0))?
+            .collect::<Result<Vec<u32>, _>>()?;
+
+        let Some(&max_index) = indices.last() else {
+            return Ok(Vec::new());
+        };
+        Ok((1..=max_index)
+            .filter(|index| indices.binary_search(index).is_err())
+            .collect())
+    }
+}
+
+fn kind_str(kind: CaptureKind) -> &'static str {
+    match kind {
+        CaptureKind::Photo => "photo",
+        CaptureKind::Video => "video",
+    }
+}
+
+fn now_unix_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn sha256_hex(contents: &[u8]) -> String {
+    Sha256::digest(contents)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_index_download_tracking() -> Result<(), Box<dyn Error>> {
+        let index = MediaIndex::open_in_memory()?;
+        index.record_seen(CaptureKind::Photo, 1)?;
+        index.record_seen(CaptureKind::Photo, 2)?;
+        index.record_downloaded(CaptureKind::Photo, 1, b"abc")?;
+        index.record_failed(CaptureKind::Photo, 2)?;
+        index.record_failed(CaptureKind::Photo, 1)?;
+
+        let record = index.get(CaptureKind::Photo, 1)?.unwrap();
+        assert_eq!(record.status, DownloadStatus::Downloaded);
+        assert_eq!(
+            record.sha256.as_deref(),
+            Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert!(record.downloaded_at_unix_ms.is_some());
+
+        assert!(index.is_downloaded(CaptureKind::Photo, 1)?);
+        assert!(!index.is_downloaded(CaptureKind::Photo, 2)?);
+        assert!(!index.is_downloaded(CaptureKind::Video, 1)?);
+        assert_eq!(index.pending(CaptureKind::Photo)?, vec![2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_media_index_gaps() -> Result<(), Box<dyn Error>> {
+        let index = MediaIndex::open_in_memory()?;
+        assert!(index.gaps(CaptureKind::Video)?.is_empty());
+
+        for i in [1, 2, 5, 7] {
+            index.record_seen(CaptureKind::Video, i)?;
+        }
+        assert_eq!(index.gaps(CaptureKind::Video)?, vec![3, 4, 6]);
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Upper bounds of the response time histogram buckets. A final bucket catches the rest.
+pub const RTT_BUCKETS: [Duration; 9] = [
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(25),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_millis(1000),
+    Duration::from_millis(2000),
+];
+
+/// Label for a CMD_ID, stable across releases so dashboards can rely on it.
+pub fn command_name(cmd_id: u8) -> &'static str {
+    match cmd_id {
+        0x00 => "heartbeat",
+        0x01 => "firmware_version",
+        0x02 => "hardware_id",
+        0x04 => "auto_focus",
+        0x05 => "manual_zoom",
+        0x06 => "manual_focus",
+        0x07 => "gimbal_rotation",
+        0x08 => "center",
+        0x0a => "gimbal_info",
+        0x0b => "function_feedback",
+        0x0c => "photo_video",
+        0x0d => "gimbal_attitude",
+        0x0e => "set_gimbal_angles",
+        0x0f => "absolute_zoom",
+        0x10 => "image_mode",
+        0x11 => "set_image_mode",
+        0x15 => "laser_distance",
+        0x16 => "max_zoom",
+        0x18 => "current_zoom",
+        0x20 => "encoding_params",
+        0x21 => "set_encoding_params",
+        0x25 => "data_stream",
+        0x30 => "utc_time",
+        0x80 => "reboot",
+        0x81 => "network_config",
+        0x82 => "set_network_config",
+        _ => "unknown",
+    }
+}
+
+/// Response time distribution with the buckets in `RTT_BUCKETS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Histogram {
+    /// Per-bucket (not cumulative) counts, the last one for responses above every bound.
+    pub buckets: [u64; RTT_BUCKETS.len() + 1],
+    pub count: u64,
+    pub sum: Duration,
+}
+
+impl Histogram {
+    pub fn observe(&mut self, value: Duration) {
+        let bucket = RTT_BUCKETS
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(RTT_BUCKETS.len());
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.sum += value;
+    }
+}
+
+/// Traffic counters and response times of one CMD_ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CommandMetrics {
+    pub sent: u64,
+    pub failed: u64,
+    pub responses: u64,
+    pub timeouts: u64,
+    pub rtt: Histogram,
+}
+
+/// Per-command breakdown of the link counters in `telemetry::LinkStats`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CommandBreakdown {
+    commands: BTreeMap<u8, CommandMetrics>,
+}
+
+impl CommandBreakdown {
+    pub fn record_sent(&mut self, cmd_id: u8) {
+        self.entry(cmd_id).sent += 1;
+    }
+
+    pub fn record_failed(&mut self, cmd_id: u8) {
+        self.entry(cmd_id).failed += 1;
+    }
+
+    pub fn record_response(&mut self, cmd_id: u8, rtt: Duration) {
+        let metrics = self.entry(cmd_id);
+        metrics.responses += 1;
+        metrics.rtt.observe(rtt);
+    }
+
+    pub fn record_timeout(&mut self, cmd_id: u8) {
+        self.entry(cmd_id).timeouts += 1;
+    }
+
+    pub fn get(&self, cmd_id: u8) -> Option<&CommandMetrics> {
+        self.commands.get(&cmd_id)
+    }
+
+    /// Metrics by CMD_ID, in CMD_ID order.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &CommandMetrics)> {
+        self.commands
+            .iter()
+            .map(|(cmd_id, metrics)| (*cmd_id, metrics))
+    }
+
+    /// Renders the breakdown in the Prometheus text format, labelled by `cmd_id` and `command`.
+    pub fn render_prometheus(&self) -> String {
+        let mut output = String::new();
+        let counters: [Counter; 4] = [
+            ("a8mini_commands_sent_total", |metrics| metrics.sent),
+            ("a8mini_commands_failed_total", |metrics| metrics.failed),
+            ("a8mini_command_responses_total", |metrics| {
+                metrics.responses
+            }),
+            ("a8mini_command_timeouts_total", |metrics| metrics.timeouts),
+        ];
+        for (name, value) in counters {
+            let _ = writeln!(output, "# TYPE {} counter", name);
+            for (cmd_id, metrics) in self.iter() {
+                let _ = writeln!(output, "{}{{{}}} {}", name, labels(cmd_id), value(metrics));
+            }
+        }
+
+        let name = "a8mini_command_rtt_seconds";
+        let _ = writeln!(output, "# TYPE {} histogram", name);
+        for (cmd_id, metrics) in self.iter() {
+            let labels = labels(cmd_id);
+            let mut cumulative = 0;
+            for (bucket, count) in metrics.rtt.buckets.iter().enumerate() {
+                cumulative += count;
+                let le = RTT_BUCKETS
+                    .get(bucket)
+                    .map_or("+Inf".to_string(), |bound| bound.as_secs_f64().to_string());
+                let _ = writeln!(
+                    output,
+                    "{}_bucket{{{},le=\"{}\"}} {}",
+                    name, labels, le, cumulative
+                );
+            }
+            let _ = writeln!(
+                output,
+                "{}_sum{{{}}} {}",
+                name,
+                labels,
+                metrics.rtt.sum.as_secs_f64()
+            );
+            let _ = writeln!(output, "{}_count{{{}}} {}", name, labels, metrics.rtt.count);
+        }
+        output
+    }
+
+    fn entry(&mut self, cmd_id: u8) -> &mut CommandMetrics {
+        self.commands.entry(cmd_id).or_default()
+    }
+}
+
+/// A counter's metric name and how to read it.
+type Counter = (&'static str, fn(&CommandMetrics) -> u64);
+
+fn labels(cmd_id: u8) -> String {
+    format!(
+        "cmd_id=\"0x{:02x}\",command=\"{}\"",
+        cmd_id,
+        command_name(cmd_id)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakdown() {
+        let mut breakdown = CommandBreakdown::default();
+        breakdown.record_sent(0x0d);
+        breakdown.record_response(0x0d, Duration::from_millis(8));
+        breakdown.record_sent(0x0c);
+        breakdown.record_timeout(0x0c);
+        breakdown.record_failed(0x0c);
+        breakdown.record_response(0x0c, Duration::from_secs(3));
+
+        let attitude = breakdown.get(0x0d).unwrap();
+        assert_eq!(
+            (attitude.sent, attitude.responses, attitude.timeouts),
+            (1, 1, 0)
+        );
+        assert_eq!(attitude.rtt.buckets[1], 1);
+        let capture = breakdown.get(0x0c).unwrap();
+        assert_eq!((capture.sent, capture.failed, capture.timeouts), (1, 1, 1));
+        assert_eq!(capture.rtt.buckets[RTT_BUCKETS.len()], 1);
+        assert_eq!(
+            breakdown
+                .iter()
+                .map(|(cmd_id, _)| cmd_id)
+                .collect::<Vec<_>>(),
+            vec![0x0c, 0x0d]
+        );
+    }
+
+    #[test]
+    fn test_render_prometheus() {
+        let mut breakdown = CommandBreakdown::default();
+        breakdown.record_sent(0x0c);
+        breakdown.record_timeout(0x0c);
+        breakdown.record_response(0x0d, Duration::from_millis(20));
+
+        let output = breakdown.render_prometheus();
+        assert!(output
+            .contains("a8mini_commands_sent_total{cmd_id=\"0x0c\",command=\"photo_video\"} 1\n"));
+        assert!(output.contains(
+            "a8mini_command_timeouts_total{cmd_id=\"0x0c\",command=\"photo_video\"} 1\n"
+        ));
+        assert!(output.contains(
+            "a8mini_command_rtt_seconds_bucket{cmd_id=\"0x0d\",command=\"gimbal_attitude\",le=\"0.01\"} 0\n"
+        ));
+        assert!(output.contains(
+            "a8mini_command_rtt_seconds_bucket{cmd_id=\"0x0d\",command=\"gimbal_attitude\",le=\"0.025\"} 1\n"
+        ));
+        assert!(output.contains(
+            "a8mini_command_rtt_seconds_bucket{cmd_id=\"0x0d\",command=\"gimbal_attitude\",le=\"+Inf\"} 1\n"
+        ));
+        assert!(output.contains(
+            "a8mini_command_rtt_seconds_count{cmd_id=\"0x0d\",command=\"gimbal_attitude\"} 1\n"
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::arbitration::CommandSource;
+use crate::control::Command;
+use crate::correlation::CorrelationId;
+use std::fmt;
+
+/// An outgoing command as seen by middleware. `bytes` is the encoded frame after interlock and
+/// soft limit checks; middleware may rewrite it, and the CRC is recomputed afterwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Context {
+    pub bytes: Vec<u8>,
+    pub source: Option<CommandSource>,
+    pub correlation: Option<CorrelationId>,
+}
+
+/// What a middleware layer decides for a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    /// Pass the command on to the next layer, then send it.
+    Continue,
+    /// Do not send the command and report success, e.g. when a simulator shadows the camera.
+    Skip,
+    /// Refuse the command with a reason.
+    Reject(String),
+}
+
+pub type Middleware = Box<dyn Fn(&dyn Command, &mut Context) -> Decision + Send + Sync>;
+
+/// Middleware layers applied in order to every outgoing command. The first layer that does not
+/// continue decides.
+#[derive(Default)]
+pub struct MiddlewareChain {
+    layers: Vec<Middleware>,
+}
+
+impl MiddlewareChain {
+    pub fn push<F>(&mut self, layer: F)
+    where
+        F: Fn(&dyn Command, &mut Context) -> Decision + Send + Sync + 'static,
+    {
+        self.layers.push(Box::new(layer));
+    }
+
+    pub fn run(&self, command: &dyn Command, context: &mut Context) -> Decision {
+        for layer in &self.layers {
+            match layer(command, context) {
+                Decision::Continue => {}
+                decision => return decision,
+            }
+        }
+        Decision::Continue
+    }
+
+    pub fn clear(&mut self) {
+        self.layers.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+}
+
+impl fmt::Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MiddlewareChain")
+            .field("layers", &self.layers.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::control::A8MiniSimpleCommand;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_chain_order() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut chain = MiddlewareChain::default();
+        chain.push(|_, context| {
+            context.bytes.push(0xaa);
+            Decision::Continue
+        });
+        chain.push(|command, _| {
+            if command.is_motion() {
+                Decision::Reject("No motion.".into())
+            } else {
+                Decision::Continue
+            }
+        });
+        let counter = calls.clone();
+        chain.push(move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Decision::Skip
+        });
+        assert_eq!(chain.len(), 3);
+
+        let mut context = Context {
+            bytes: vec![],
+            source: None,
+            correlation: None,
+        };
+        assert_eq!(
+            chain.run(&A8MiniSimpleCommand::RotateUp, &mut context),
+            Decision::Reject("No motion.".into())
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(
+            chain.run(&A8MiniSimpleCommand::ZoomIn, &mut context),
+            Decision::Skip
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(context.bytes, vec![0xaa, 0xaa]);
+
+        chain.clear();
+        assert!(chain.is_empty());
+        assert_eq!(
+            chain.run(&A8MiniSimpleCommand::RotateUp, &mut context),
+            Decision::Continue
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use crate::control::{A8MiniSimpleCommand, ImageMode, Sensor};
+use crate::A8MiniError;
+use serde::Serialize;
+use std::error::Error;
+
+/// SIYI camera models, identified by the first two characters of the hardware ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CameraModel {
+    ZR10,
+    A8Mini,
+    A2Mini,
+    ZR30,
+    ZT30,
+    ZT6,
+    /// Model code not known to this crate.
+    Unknown(u8),
+}
+
+impl CameraModel {
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            0x6B => CameraModel::ZR10,
+            0x73 => CameraModel::A8Mini,
+            0x75 => CameraModel::A2Mini,
+            0x78 => CameraModel::ZR30,
+            0x7A => CameraModel::ZT30,
+            0x82 => CameraModel::ZT6,
+            _ => CameraModel::Unknown(code),
+        }
+    }
+
+    /// Decodes the model from the DATA field of the hardware ID ACK, an ASCII string whose
+    /// first two characters are the model code in hex.
+    pub fn from_hardware_id(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let code = data
+            .get(..2)
+            .and_then(|code| std::str::from_utf8(code).ok())
+            .and_then(|code| u8::from_str_radix(code, 16).ok())
+            .ok_or_else(|| {
+                A8MiniError::MalformedAck("Hardware ID does not start with a model code.".into())
+            })?;
+        Ok(Self::from_code(code))
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        let base = Capabilities {
+            model: *self,
+            zoom: true,
+            optical_zoom: false,
+            autofocus: false,
+            thermal: false,
+            laser_rangefinder: false,
+            recording_4k: false,
+            multi_sensor: false,
+        };
+        match *self {
+            CameraModel::A8Mini => Capabilities {
+                recording_4k: true,
+                ..base
+            },
+            CameraModel::A2Mini => Capabilities {
+                zoom: false,
+                ..base
+            },
+            CameraModel::ZR10 => Capabilities {
+                optical_zoom: true,
+                autofocus: true,
+                ..base
+            },
+            CameraModel::ZR30 => Capabilities {
+                optical_zoom: true,
+                autofocus: true,
+                recording_4k: true,
+                ..base
+            },
+            CameraModel::ZT30 => Capabilities {
+                optical_zoom: true,
+                autofocus: true,
+                thermal: true,
+                laser_rangefinder: true,
+                recording_4k: true,
+                multi_sensor: true,
+                ..base
+            },
+            CameraModel::ZT6 => Capabilities {
+                thermal: true,
+                laser_rangefinder: true,
+                recording_4k: true,
+                multi_sensor: true,
+                ..base
+            },
+            // Unknown models are assumed capable so newer payloads are not locked out.
+            CameraModel::Unknown(_) => Capabilities {
+                optical_zoom: true,
+                autofocus: true,
+                thermal: true,
+                laser_rangefinder: true,
+                recording_4k: true,
+                multi_sensor: true,
+                ..base
+            },
+        }
+    }
+}
+
+/// Decoded hardware ID (CMD 0x02). The full ID distinguishes individual units on a network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HardwareId {
+    pub id: String,
+    pub model: CameraModel,
+}
+
+impl HardwareId {
+    /// Decodes the DATA field of the hardware ID ACK, an ASCII string padded with NULs.
+    pub fn from_payload(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let id = std::str::from_utf8(data)
+            .map_err(|_| A8MiniError::MalformedAck("Hardware ID is not ASCII.".into()))?
+            .trim_end_matches('\0')
+            .to_string();
+        Ok(HardwareId {
+            model: CameraModel::from_hardware_id(id.as_bytes())?,
+            id,
+        })
+    }
+}
+
+impl std::fmt::Display for HardwareId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
+/// Features of a camera model, from the manufacturer's published specifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Capabilities {
+    pub model: CameraModel,
+    /// Digital or optical zoom.
+    pub zoom: bool,
+    pub optical_zoom: bool,
+    pub autofocus: bool,
+    pub thermal: bool,
+    pub laser_rangefinder: bool,
+    pub recording_4k: bool,
+    /// Several image sensors selectable with `ImageMode`. Models without an optical zoom sensor
+    /// only have the wide and thermal sensors.
+    pub multi_sensor: bool,
+}
+
+impl Capabilities {
+    /// Whether the model implements a simple command.
+    pub fn supports(&self, command: A8MiniSimpleCommand) -> bool {
+        match command {
+            A8MiniSimpleCommand::ZoomIn
+            | A8MiniSimpleCommand::ZoomOut
+            | A8MiniSimpleCommand::ZoomStop
+            | A8MiniSimpleCommand::ZoomMax
+            | A8MiniSimpleCommand::MaxZoomInformation
+            | A8MiniSimpleCommand::CurrentZoomInformation => self.zoom,
+            A8MiniSimpleCommand::FocusIn
+            | A8MiniSimpleCommand::FocusOut
+            | A8MiniSimpleCommand::FocusStop
+            | A8MiniSimpleCommand::AutoFocus => self.autofocus,
+            A8MiniSimpleCommand::LaserRangefinderInformation => self.laser_rangefinder,
+            A8MiniSimpleCommand::ImageModeInformation => self.multi_sensor,
+            _ => true,
+        }
+    }
+
+    /// Whether the model has every sensor the image mode shows.
+    pub fn supports_image_mode(&self, mode: ImageMode) -> bool {
+        self.multi_sensor
+            && mode
+                .sensors()
+                .iter()
+                .all(|&sensor| sensor != Sensor::Zoom || self.optical_zoom)
+    }
+
+    /// Every image mode the model supports, empty for single-sensor models.
+    pub fn image_modes(&self) -> Vec<ImageMode> {
+        ImageMode::ALL
+            .into_iter()
+            .filter(|&mode| self.supports_image_mode(mode))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_from_hardware_id() {
+        assert_eq!(
+            CameraModel::from_hardware_id(b"7315A8000000").unwrap(),
+            CameraModel::A8Mini
+        );
+        assert_eq!(
+            CameraModel::from_hardware_id(b"7A2200000000").unwrap(),
+            CameraModel::ZT30
+        );
+        assert_eq!(
+            CameraModel::from_hardware_id(b"FF").unwrap(),
+            CameraModel::Unknown(0xFF)
+        );
+        assert!(CameraModel::from_hardware_id(b"Z").is_err());
+        assert!(CameraModel::from_hardware_id(b"ZZ").is_err());
+    }
+
+    #[test]
+    fn test_hardware_id_from_payload() {
+        let hardware_id = HardwareId::from_payload(b"7315A8123456\0\0").unwrap();
+        assert_eq!(hardware_id.id, "7315A8123456");
+        assert_eq!(hardware_id.model, CameraModel::A8Mini);
+        assert_eq!(hardware_id.to_string(), "7315A8123456");
+        assert!(HardwareId::from_payload(&[0xff, 0xfe]).is_err());
+        assert!(HardwareId::from_payload(b"").is_err());
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let a8mini = CameraModel::A8Mini.capabilities();
+        assert!(a8mini.recording_4k && !a8mini.thermal);
+        assert!(a8mini.supports(A8MiniSimpleCommand::ZoomIn));
+        assert!(!a8mini.supports(A8MiniSimpleCommand::LaserRangefinderInformation));
+        assert!(!a8mini.supports(A8MiniSimpleCommand::AutoFocus));
+
+        assert!(!a8mini.supports_image_mode(ImageMode::WideWithThermal));
+        assert!(CameraModel::ZT6
+            .capabilities()
+            .supports_image_mode(ImageMode::WideWithThermal));
+        assert!(!CameraModel::ZT6
+            .capabilities()
+            .supports_image_mode(ImageMode::ZoomWithThermal));
+
+        assert!(a8mini.image_modes().is_empty());
+        assert_eq!(
+            CameraModel::ZT6.capabilities().image_modes(),
+            vec![ImageMode::WideWithThermal, ImageMode::ThermalWithWide]
+        );
+
+        let zt30 = CameraModel::ZT30.capabilities();
+        assert!(zt30.supports_image_mode(ImageMode::SplitZoomThermal));
+        assert_eq!(zt30.image_modes().len(), ImageMode::ALL.len());
+        assert!(zt30.supports(A8MiniSimpleCommand::LaserRangefinderInformation));
+        assert!(!CameraModel::A2Mini
+            .capabilities()
+            .supports(A8MiniSimpleCommand::ZoomIn));
+    }
+}
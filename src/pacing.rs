@@ -0,0 +1,188 @@
+use std::time::{Duration, Instant};
+
+/// Enforces a minimum interval between angle setpoints. A setpoint submitted while an earlier
+/// one is still waiting for its slot supersedes it, so only the newest setpoint is sent.
+#[derive(Debug, Clone)]
+pub struct SetpointPacer {
+    min_interval: Duration,
+    last_sent: Option<Instant>,
+    generation: u64,
+}
+
+impl SetpointPacer {
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_sent: None,
+            generation: 0,
+        }
+    }
+
+    pub fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+
+    /// Registers a new setpoint. Returns its generation and how long to wait before sending it.
+    pub fn submit(&mut self, now: Instant) -> (u64, Duration) {
+        self.generation += 1;
+        let wait = self.last_sent.map_or(Duration::ZERO, |last_sent| {
+            (last_sent + self.min_interval).saturating_duration_since(now)
+        });
+        (self.generation, wait)
+    }
+
+    /// Whether the setpoint of the given generation is still the newest one.
+    pub fn is_current(&self, generation: u64) -> bool {
+        self.generation == generation
+    }
+
+    /// Supersedes the setpoint currently waiting for its slot, if any.
+    pub fn cancel_pending(&mut self) {
+        self.generation += 1;
+    }
+
+    pub fn mark_sent(&mut self, now: Instant) {
+        self.last_sent = Some(now);
+    }
+}
+
+/// A gimbal setpoint as far as delta suppression is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setpoint {
+    /// Yaw/pitch angle in degrees * 10.
+    Angle(i16, i16),
+    /// Yaw/pitch speed.
+    Rate(i8, i8),
+}
+
+/// Suppresses setpoints that match the last acknowledged one within a tolerance, so control
+/// loops publishing at a fixed rate do not resend an unchanged setpoint. A setpoint that was
+/// sent but never acknowledged may not have reached the gimbal, so it is never a reference.
+#[derive(Debug, Clone)]
+pub struct DeltaFilter {
+    angle_tolerance: i16,
+    rate_tolerance: i8,
+    last: Option<Setpoint>,
+    in_flight: Option<Setpoint>,
+}
+
+impl DeltaFilter {
+    pub fn new(angle_tolerance: i16, rate_tolerance: i8) -> Self {
+        Self {
+            angle_tolerance: angle_tolerance.max(0),
+            rate_tolerance: rate_tolerance.max(0),
+            last: None,
+            in_flight: None,
+        }
+    }
+
+    pub fn is_redundant(&self, setpoint: Setpoint) -> bool {
+        match (self.last, setpoint) {
+            (Some(Setpoint::Angle(last_yaw, last_pitch)), Setpoint::Angle(yaw, pitch)) => {
+                last_yaw.abs_diff(yaw) <= self.angle_tolerance.unsigned_abs()
+                    && last_pitch.abs_diff(pitch) <= self.angle_tolerance.unsigned_abs()
+            }
+            (Some(Setpoint::Rate(last_yaw, last_pitch)), Setpoint::Rate(yaw, pitch)) => {
+                last_yaw.abs_diff(yaw) <= self.rate_tolerance.unsigned_abs()
+                    && last_pitch.abs_diff(pitch) <= self.rate_tolerance.unsigned_abs()
+            }
+            _ => false,
+        }
+    }
+
+    /// Records a motion command sent blind. No ACK is awaited for it, so it becomes the reference
+    /// at once. Motion commands without a setpoint (rotations, centering) leave the gimbal state
+    /// unknown, so they are recorded as `None`.
+    pub fn record(&mut self, setpoint: Option<Setpoint>) {
+        self.last = setpoint;
+        self.in_flight = None;
+    }
+
+    /// Records a motion command whose ACK is awaited. The gimbal state is unknown until the ACK
+    /// arrives, so nothing is redundant in the meantime.
+    pub fn sent(&mut self, setpoint: Option<Setpoint>) {
+        self.last = None;
+        self.in_flight = setpoint;
+    }
+
+    /// Records the ACK of `setpoint`, which becomes the reference unless another motion command
+    /// was sent after it.
+    pub fn acknowledged(&mut self, setpoint: Setpoint) {
+        if self.in_flight == Some(setpoint) {
+            self.last = self.in_flight.take();
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.in_flight = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pacer_wait() {
+        let now = Instant::now();
+        let mut pacer = SetpointPacer::new(Duration::from_millis(100));
+        assert_eq!(pacer.submit(now).1, Duration::ZERO);
+        pacer.mark_sent(now);
+
+        assert_eq!(
+            pacer.submit(now + Duration::from_millis(30)).1,
+            Duration::from_millis(70)
+        );
+        assert_eq!(
+            pacer.submit(now + Duration::from_millis(150)).1,
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_delta_filter() {
+        let mut filter = DeltaFilter::new(5, 0);
+        assert!(!filter.is_redundant(Setpoint::Angle(100, 0)));
+
+        filter.sent(Some(Setpoint::Angle(100, 0)));
+        assert!(!filter.is_redundant(Setpoint::Angle(100, 0)));
+        filter.acknowledged(Setpoint::Angle(100, 0));
+        assert!(filter.is_redundant(Setpoint::Angle(100, 0)));
+        assert!(filter.is_redundant(Setpoint::Angle(95, 5)));
+        assert!(!filter.is_redundant(Setpoint::Angle(94, 0)));
+        assert!(!filter.is_redundant(Setpoint::Rate(0, 0)));
+
+        filter.sent(Some(Setpoint::Rate(10, -10)));
+        filter.acknowledged(Setpoint::Rate(10, -10));
+        assert!(filter.is_redundant(Setpoint::Rate(10, -10)));
+        assert!(!filter.is_redundant(Setpoint::Rate(11, -10)));
+
+        filter.sent(None);
+        assert!(!filter.is_redundant(Setpoint::Rate(10, -10)));
+
+        filter.record(Some(Setpoint::Angle(-50, 0)));
+        assert!(filter.is_redundant(Setpoint::Angle(-50, 0)));
+
+        // A late ACK of a setpoint that has since been replaced.
+        filter.sent(Some(Setpoint::Angle(100, 0)));
+        filter.sent(Some(Setpoint::Angle(200, 0)));
+        filter.acknowledged(Setpoint::Angle(100, 0));
+        assert!(!filter.is_redundant(Setpoint::Angle(100, 0)));
+    }
+
+    #[test]
+    fn test_pacer_supersede() {
+        let now = Instant::now();
+        let mut pacer = SetpointPacer::new(Duration::from_millis(100));
+        pacer.mark_sent(now);
+
+        let (first, _) = pacer.submit(now);
+        let (second, _) = pacer.submit(now);
+        assert!(!pacer.is_current(first));
+        assert!(pacer.is_current(second));
+
+        pacer.cancel_pending();
+        assert!(!pacer.is_current(second));
+    }
+}
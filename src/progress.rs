@@ -0,0 +1,76 @@
+use crate::correlation::CorrelationId;
+use std::time::Duration;
+
+/// Progress of a long-running mission such as a trajectory. Subscribe with
+/// `A8Mini::subscribe_progress`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissionProgress {
+    pub correlation: Option<CorrelationId>,
+    /// Steps completed so far.
+    pub step: usize,
+    pub total: usize,
+    /// Estimated time to completion, extrapolated from the time spent per completed step.
+    pub eta: Option<Duration>,
+    /// Error that ended the mission, if any.
+    pub last_error: Option<String>,
+}
+
+impl MissionProgress {
+    pub fn new(step: usize, total: usize, elapsed: Duration) -> Self {
+        let eta = match step {
+            0 => None,
+            _ => Some(elapsed / step as u32 * total.saturating_sub(step) as u32),
+        };
+        Self {
+            correlation: crate::correlation::current(),
+            step,
+            total,
+            eta,
+            last_error: None,
+        }
+    }
+
+    pub fn failed(mut self, error: String) -> Self {
+        self.eta = None;
+        self.last_error = Some(error);
+        self
+    }
+
+    pub fn percent(&self) -> f32 {
+        if self.total == 0 {
+            return 100.0;
+        }
+        self.step as f32 * 100.0 / self.total as f32
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.step >= self.total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress() {
+        let start = MissionProgress::new(0, 4, Duration::ZERO);
+        assert_eq!(start.eta, None);
+        assert_eq!(start.percent(), 0.0);
+
+        let halfway = MissionProgress::new(2, 4, Duration::from_secs(3));
+        assert_eq!(halfway.eta, Some(Duration::from_secs(3)));
+        assert_eq!(halfway.percent(), 50.0);
+        assert!(!halfway.is_done());
+
+        let done = MissionProgress::new(4, 4, Duration::from_secs(6));
+        assert_eq!(done.eta, Some(Duration::ZERO));
+        assert!(done.is_done());
+
+        let failed = MissionProgress::new(1, 4, Duration::from_secs(1)).failed("Timeout".into());
+        assert_eq!(failed.eta, None);
+        assert_eq!(failed.last_error.as_deref(), Some("Timeout"));
+
+        assert_eq!(MissionProgress::new(0, 0, Duration::ZERO).percent(), 100.0);
+    }
+}
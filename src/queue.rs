@@ -0,0 +1,118 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Encoded command waiting for the link to come back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedCommand {
+    pub bytes: Vec<u8>,
+    pub expires: Instant,
+}
+
+/// Buffer for non-motion commands that could not be sent during a link outage.
+/// Entries older than `ttl` are dropped instead of being flushed; when full, the oldest entry is dropped.
+#[derive(Debug, Clone)]
+pub struct OfflineQueue {
+    ttl: Duration,
+    capacity: usize,
+    pending: VecDeque<QueuedCommand>,
+}
+
+impl OfflineQueue {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            capacity: crate::constants::OFFLINE_QUEUE_CAPACITY,
+            pending: VecDeque::new(),
+        }
+    }
+
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn push(&mut self, bytes: Vec<u8>, now: Instant) {
+        if self.pending.len() == self.capacity {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(QueuedCommand {
+            bytes,
+            expires: now + self.ttl,
+        });
+    }
+
+    /// Puts a command back at the head of the queue after a failed flush.
+    pub fn push_front(&mut self, command: QueuedCommand) {
+        if self.pending.len() == self.capacity {
+            self.pending.pop_back();
+        }
+        self.pending.push_front(command);
+    }
+
+    /// Removes and returns the oldest command that has not expired, discarding expired ones.
+    pub fn pop_live(&mut self, now: Instant) -> Option<QueuedCommand> {
+        while let Some(command) = self.pending.pop_front() {
+            if command.expires > now {
+                return Some(command);
+            }
+            log_line!("[QUEUE] Dropping expired command: {:?}", command.bytes);
+        }
+        None
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+impl Default for OfflineQueue {
+    fn default() -> Self {
+        Self::new(crate::constants::OFFLINE_QUEUE_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_queue_ttl() {
+        let now = Instant::now();
+        let mut queue = OfflineQueue::new(Duration::from_secs(10));
+        queue.push(vec![1], now);
+        queue.push(vec![2], now + Duration::from_secs(5));
+
+        let later = now + Duration::from_secs(12);
+        assert_eq!(queue.pop_live(later).unwrap().bytes, vec![2]);
+        assert_eq!(queue.pop_live(later), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_offline_queue_capacity() {
+        let now = Instant::now();
+        let mut queue = OfflineQueue::new(Duration::from_secs(10)).with_capacity(2);
+        for byte in 1..=3 {
+            queue.push(vec![byte], now);
+        }
+        assert_eq!(queue.len(), 2);
+
+        let head = queue.pop_live(now).unwrap();
+        assert_eq!(head.bytes, vec![2]);
+        queue.push_front(head);
+        assert_eq!(queue.pop_live(now).unwrap().bytes, vec![2]);
+        assert_eq!(queue.pop_live(now).unwrap().bytes, vec![3]);
+    }
+}
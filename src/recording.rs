@@ -0,0 +1,62 @@
+use std::time::{Duration, SystemTime};
+
+/// Boundaries of one recording segment produced by segment rotation, in host wall-clock time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordingSegment {
+    pub index: u32,
+    pub started: SystemTime,
+    pub stopped: SystemTime,
+}
+
+impl RecordingSegment {
+    pub fn duration(&self) -> Duration {
+        self.stopped
+            .duration_since(self.started)
+            .unwrap_or(Duration::ZERO)
+    }
+}
+
+/// Settings for periodically stopping and restarting a recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentRotation {
+    /// Length of each segment.
+    pub segment_length: Duration,
+    /// Pause between stopping a segment and starting the next, giving the camera time to close the file.
+    pub restart_gap: Duration,
+}
+
+impl SegmentRotation {
+    pub fn new(segment_length: Duration) -> Self {
+        Self {
+            segment_length,
+            restart_gap: crate::constants::SEGMENT_RESTART_GAP,
+        }
+    }
+
+    pub fn with_restart_gap(mut self, restart_gap: Duration) -> Self {
+        self.restart_gap = restart_gap;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_duration() {
+        let started = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let segment = RecordingSegment {
+            index: 0,
+            started,
+            stopped: started + Duration::from_secs(300),
+        };
+        assert_eq!(segment.duration(), Duration::from_secs(300));
+
+        let reversed = RecordingSegment {
+            stopped: started - Duration::from_secs(1),
+            ..segment
+        };
+        assert_eq!(reversed.duration(), Duration::ZERO);
+    }
+}
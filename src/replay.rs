@@ -0,0 +1,171 @@
+use crate::audit::{AuditEntry, AuditOutcome};
+use crate::control::Command;
+use crate::{constants, A8MiniError};
+use std::time::Duration;
+
+const ROTATION_CMD_ID: u8 = 0x07;
+const CENTER_CMD_ID: u8 = 0x08;
+const FUNCTION_CMD_ID: u8 = 0x0c;
+const ANGLE_CMD_ID: u8 = 0x0e;
+const REBOOT_CMD_ID: u8 = 0x80;
+const NETWORK_CONFIG_CMD_ID: u8 = 0x82;
+
+/// A frame captured from an earlier session, sent again as-is. Interlock, soft limit and
+/// restricted-handle checks still apply, based on the frame's CMD_ID and payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedCommand(pub Vec<u8>);
+
+impl RecordedCommand {
+    fn cmd_id(&self) -> u8 {
+        self.0.get(7).copied().unwrap_or_default()
+    }
+
+    fn data(&self) -> &[u8] {
+        self.0
+            .get(constants::HEADER_LEN..self.0.len().saturating_sub(2))
+            .unwrap_or_default()
+    }
+}
+
+impl Command for RecordedCommand {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+
+    fn angle_setpoint(&self) -> Option<(i16, i16)> {
+        match (self.cmd_id(), self.data()) {
+            (ANGLE_CMD_ID, [yaw_lo, yaw_hi, pitch_lo, pitch_hi, ..]) => Some((
+                i16::from_le_bytes([*yaw_lo, *yaw_hi]),
+                i16::from_le_bytes([*pitch_lo, *pitch_hi]),
+            )),
+            _ => None,
+        }
+    }
+
+    fn rate_setpoint(&self) -> Option<(i8, i8)> {
+        match (self.cmd_id(), self.data()) {
+            (ROTATION_CMD_ID, [v_yaw, v_pitch, ..]) => Some((*v_yaw as i8, *v_pitch as i8)),
+            _ => None,
+        }
+    }
+
+    fn is_motion(&self) -> bool {
+        matches!(
+            self.cmd_id(),
+            ROTATION_CMD_ID | CENTER_CMD_ID | ANGLE_CMD_ID
+        )
+    }
+
+    fn is_stop(&self) -> bool {
+        self.rate_setpoint() == Some((0, 0))
+    }
+
+    fn is_destructive(&self) -> bool {
+        matches!(self.cmd_id(), REBOOT_CMD_ID | NETWORK_CONFIG_CMD_ID)
+    }
+
+    fn ack_cmd_id(&self) -> u8 {
+        match self.cmd_id() {
+            FUNCTION_CMD_ID => constants::FUNCTION_FEEDBACK_CMD_ID,
+            cmd_id => cmd_id,
+        }
+    }
+}
+
+/// One command of a replay and the wait before sending it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayStep {
+    pub delay: Duration,
+    pub command: RecordedCommand,
+}
+
+/// Builds a replay of the commands an audit log shows were sent, keeping their original spacing
+/// divided by `speed` (2.0 replays twice as fast). Commands that were not transmitted are skipped.
+pub fn schedule(entries: &[AuditEntry], speed: f32) -> Result<Vec<ReplayStep>, A8MiniError> {
+    if !speed.is_finite() || speed <= 0.0 {
+        return Err(A8MiniError::InvalidArgument(format!(
+            "Replay speed {} must be positive.",
+            speed
+        )));
+    }
+
+    let mut previous = None;
+    Ok(entries
+        .iter()
+        .filter(|entry| entry.outcome == AuditOutcome::Sent)
+        .map(|entry| {
+            let gap = previous
+                .and_then(|previous| entry.time.duration_since(previous).ok())
+                .unwrap_or_default();
+            previous = Some(entry.time);
+            ReplayStep {
+                delay: gap.div_f64(f64::from(speed)),
+                command: RecordedCommand(entry.bytes.clone()),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::control::{A8MiniComplexCommand, A8MiniSimpleCommand};
+    use crate::decideg::DeciDeg;
+    use std::time::SystemTime;
+
+    fn entry(offset_ms: u64, bytes: Vec<u8>, outcome: AuditOutcome) -> AuditEntry {
+        AuditEntry {
+            time: SystemTime::UNIX_EPOCH + Duration::from_millis(offset_ms),
+            bytes,
+            correlation: None,
+            source: None,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_schedule() {
+        let entries = [
+            entry(1000, vec![1], AuditOutcome::Sent),
+            entry(1500, vec![2], AuditOutcome::DryRun),
+            entry(3000, vec![3], AuditOutcome::Sent),
+            entry(3200, vec![4], AuditOutcome::Sent),
+        ];
+        let steps = schedule(&entries, 2.0).unwrap();
+        let delays: Vec<_> = steps.iter().map(|step| step.delay).collect();
+        assert_eq!(
+            delays,
+            vec![
+                Duration::ZERO,
+                Duration::from_secs(1),
+                Duration::from_millis(100)
+            ]
+        );
+        assert_eq!(steps[1].command, RecordedCommand(vec![3]));
+
+        assert!(schedule(&entries, 0.0).is_err());
+        assert!(schedule(&entries, f32::NAN).is_err());
+    }
+
+    #[test]
+    fn test_recorded_command_classification() {
+        let angle = RecordedCommand(
+            A8MiniComplexCommand::SetYawPitchAngle(DeciDeg(-300), DeciDeg(150)).to_bytes(),
+        );
+        assert_eq!(angle.angle_setpoint(), Some((-300, 150)));
+        assert!(angle.is_motion() && !angle.is_stop());
+
+        let stop = RecordedCommand(A8MiniSimpleCommand::StopRotation.to_bytes());
+        assert!(stop.is_motion() && stop.is_stop());
+
+        let rate = RecordedCommand(A8MiniComplexCommand::SetYawPitchSpeed(10, -5).to_bytes());
+        assert_eq!(rate.rate_setpoint(), Some((10, -5)));
+
+        let photo = RecordedCommand(A8MiniSimpleCommand::TakePicture.to_bytes());
+        assert!(!photo.is_motion());
+        assert_eq!(photo.ack_cmd_id(), constants::FUNCTION_FEEDBACK_CMD_ID);
+
+        assert!(RecordedCommand(A8MiniSimpleCommand::RebootGimbal.to_bytes()).is_destructive());
+        assert!(!RecordedCommand(vec![]).is_motion());
+    }
+}
@@ -0,0 +1,62 @@
+use crate::control::{FirmwareVersion, ImageMode};
+use crate::models::{CameraModel, Capabilities};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Everything known about a camera from one round of queries, see `A8Mini::camera_report`. Meant
+/// for inventory systems that track what each airframe carries.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CameraReport {
+    pub hardware_id: String,
+    pub model: CameraModel,
+    pub firmware: FirmwareVersion,
+    /// Features of the model from its specifications.
+    pub capabilities: Capabilities,
+    pub image_modes: Vec<ImageMode>,
+    /// Whether the camera answered each probe query, by feature. A model feature whose probe
+    /// went unanswered may be missing from this unit or its firmware.
+    pub probes: BTreeMap<&'static str, bool>,
+    /// Highest zoom level, when the camera reports it.
+    pub max_zoom: Option<f32>,
+}
+
+impl CameraReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("report serializes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::control::Version;
+
+    #[test]
+    fn test_report_json() {
+        let version = |minor| Version {
+            major: 0,
+            minor,
+            patch: 1,
+        };
+        let report = CameraReport {
+            hardware_id: "7315A8000000".to_string(),
+            model: CameraModel::A8Mini,
+            firmware: FirmwareVersion {
+                camera: version(2),
+                gimbal: version(3),
+                zoom: None,
+            },
+            capabilities: CameraModel::A8Mini.capabilities(),
+            image_modes: Vec::new(),
+            probes: BTreeMap::from([("max_zoom", true), ("laser_rangefinder", false)]),
+            max_zoom: Some(6.0),
+        };
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["model"], "A8Mini");
+        assert_eq!(json["firmware"]["gimbal"]["minor"], 3);
+        assert!(json["firmware"]["zoom"].is_null());
+        assert_eq!(json["capabilities"]["recording_4k"], true);
+        assert_eq!(json["probes"]["laser_rangefinder"], false);
+        assert_eq!(json["max_zoom"], 6.0);
+    }
+}
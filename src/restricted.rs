@@ -0,0 +1,89 @@
+use crate::{arbitration, constants, control, events, A8Mini, A8MiniError};
+use std::error::Error;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Handle that shares a client but refuses destructive commands (reboots). Meant for
+/// less-trusted subsystems such as a web UI while the full `A8Mini` stays with the mission manager.
+#[derive(Debug, Clone)]
+pub struct RestrictedA8Mini {
+    camera: Arc<A8Mini>,
+}
+
+impl RestrictedA8Mini {
+    pub fn new(camera: Arc<A8Mini>) -> Self {
+        Self { camera }
+    }
+
+    fn check<T: control::Command>(command: &T) -> Result<(), A8MiniError> {
+        if command.is_destructive() {
+            log_line!("[RESTRICTED] Destructive command rejected.");
+            return Err(A8MiniError::Rejected(
+                "Destructive commands are not allowed on a restricted handle.".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn send_command_blind<T: control::Command>(
+        &self,
+        command: T,
+    ) -> Result<(), Box<dyn Error>> {
+        Self::check(&command)?;
+        self.camera.send_command_blind(command).await
+    }
+
+    pub async fn send_command_blind_from<T: control::Command>(
+        &self,
+        source: arbitration::CommandSource,
+        command: T,
+    ) -> Result<(), Box<dyn Error>> {
+        Self::check(&command)?;
+        self.camera.send_command_blind_from(source, command).await
+    }
+
+    pub async fn send_command<T: control::Command>(
+        &self,
+        command: T,
+    ) -> Result<[u8; constants::RECV_BUFF_SIZE], A8MiniError> {
+        Self::check(&command)?;
+        self.camera.send_command(command).await
+    }
+
+    pub async fn send_command_from<T: control::Command>(
+        &self,
+        source: arbitration::CommandSource,
+        command: T,
+    ) -> Result<[u8; constants::RECV_BUFF_SIZE], A8MiniError> {
+        Self::check(&command)?;
+        self.camera.send_command_from(source, command).await
+    }
+
+    pub async fn get_attitude_information(
+        &self,
+    ) -> Result<control::A8MiniAtittude, Box<dyn Error>> {
+        self.camera.get_attitude_information().await
+    }
+
+    pub fn storage_status(&self) -> control::StorageStatus {
+        self.camera.storage_status()
+    }
+
+    pub fn subscribe_events(&self) -> broadcast::Receiver<events::EventRecord> {
+        self.camera.subscribe_events()
+    }
+
+    pub async fn send_http_query<T: control::HTTPQuery>(
+        &self,
+        query: T,
+    ) -> Result<control::HTTPResponse, A8MiniError> {
+        self.camera.send_http_query(query).await
+    }
+
+    pub async fn send_http_image_query<T: control::HTTPQuery>(
+        &self,
+        query: T,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.camera.send_http_image_query(query).await
+    }
+}
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+/// Smooths measured round-trip times and derives response timeouts from them.
+/// The timeout is `multiplier` times the smoothed RTT, kept between `floor` and `ceiling`.
+/// Each timeout doubles the next one (up to the ceiling) until a response is measured again.
+#[derive(Debug, Clone)]
+pub struct RttEstimator {
+    multiplier: u32,
+    floor: Duration,
+    ceiling: Duration,
+    smoothed: Option<Duration>,
+    backoff: u32,
+}
+
+/// A new sample contributes 1/SMOOTHING of the smoothed RTT.
+const SMOOTHING: u32 = 8;
+const MAX_BACKOFF: u32 = 4;
+
+impl RttEstimator {
+    pub fn new(multiplier: u32, floor: Duration, ceiling: Duration) -> Self {
+        Self {
+            multiplier: multiplier.max(1),
+            floor,
+            ceiling: ceiling.max(floor),
+            smoothed: None,
+            backoff: 0,
+        }
+    }
+
+    pub fn observe(&mut self, rtt: Duration) {
+        self.smoothed = Some(match self.smoothed {
+            Some(smoothed) => (smoothed * (SMOOTHING - 1) + rtt) / SMOOTHING,
+            None => rtt,
+        });
+        self.backoff = 0;
+    }
+
+    pub fn on_timeout(&mut self) {
+        self.backoff = (self.backoff + 1).min(MAX_BACKOFF);
+    }
+
+    pub fn smoothed(&self) -> Option<Duration> {
+        self.smoothed
+    }
+
+    /// Response timeout for the next command. The ceiling is used until the first sample.
+    pub fn timeout(&self) -> Duration {
+        let Some(smoothed) = self.smoothed else {
+            return self.ceiling;
+        };
+        (smoothed * self.multiplier * (1 << self.backoff)).clamp(self.floor, self.ceiling)
+    }
+}
+
+impl Default for RttEstimator {
+    fn default() -> Self {
+        Self::new(
+            crate::constants::RTT_TIMEOUT_MULTIPLIER,
+            crate::constants::RTT_TIMEOUT_FLOOR,
+            crate::constants::RTT_TIMEOUT_CEILING,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rtt_timeout() {
+        let mut rtt = RttEstimator::new(3, Duration::from_millis(50), Duration::from_millis(500));
+        assert_eq!(rtt.timeout(), Duration::from_millis(500));
+
+        rtt.observe(Duration::from_millis(40));
+        assert_eq!(rtt.timeout(), Duration::from_millis(120));
+        rtt.observe(Duration::from_millis(120));
+        assert_eq!(rtt.smoothed(), Some(Duration::from_millis(50)));
+        assert_eq!(rtt.timeout(), Duration::from_millis(150));
+
+        rtt.on_timeout();
+        assert_eq!(rtt.timeout(), Duration::from_millis(300));
+        rtt.on_timeout();
+        assert_eq!(rtt.timeout(), Duration::from_millis(500));
+        rtt.observe(Duration::from_millis(50));
+        assert_eq!(rtt.timeout(), Duration::from_millis(150));
+
+        rtt.observe(Duration::from_millis(1));
+        for _ in 0..50 {
+            rtt.observe(Duration::from_millis(1));
+        }
+        assert_eq!(rtt.timeout(), Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::transport::StreamTransport;
+use crate::A8MiniError;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::io::unix::AsyncFd;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+/// A UART configured for the SIYI SDK: raw mode, 8N1, no flow control.
+#[derive(Debug)]
+pub struct SerialPort {
+    fd: AsyncFd<File>,
+}
+
+fn speed(baud_rate: u32) -> Option<libc::speed_t> {
+    Some(match baud_rate {
+        9600 => libc::B9600,
+        19200 => libc::B19200,
+        38400 => libc::B38400,
+        57600 => libc::B57600,
+        115200 => libc::B115200,
+        230400 => libc::B230400,
+        460800 => libc::B460800,
+        921600 => libc::B921600,
+        _ => return None,
+    })
+}
+
+impl SerialPort {
+    pub fn open(path: &str, baud_rate: u32) -> Result<Self, A8MiniError> {
+        let speed = speed(baud_rate).ok_or_else(|| {
+            A8MiniError::InvalidArgument(format!("Unsupported baud rate {}.", baud_rate))
+        })?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(libc::O_NOCTTY | libc::O_NONBLOCK)
+            .open(path)?;
+
+        // SAFETY: `file` owns a valid open descriptor and `termios` is fully initialized by
+        // tcgetattr before use.
+        unsafe {
+            let fd = file.as_raw_fd();
+            let mut termios = std::mem::zeroed::<libc::termios>();
+            if libc::tcgetattr(fd, &mut termios) != 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+            libc::cfmakeraw(&mut termios);
+            termios.c_cflag |= libc::CLOCAL | libc::CREAD;
+            termios.c_cflag &= !(libc::CSTOPB | libc::CRTSCTS);
+            if libc::cfsetspeed(&mut termios, speed) != 0
+                || libc::tcsetattr(fd, libc::TCSANOW, &termios) != 0
+            {
+                return Err(io::Error::last_os_error().into());
+            }
+        }
+
+        log_line!("[SERIAL] Opened {} at {} baud.", path, baud_rate);
+        Ok(Self {
+            fd: AsyncFd::new(file)?,
+        })
+    }
+}
+
+impl AsyncRead for SerialPort {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            let mut guard = ready!(self.fd.poll_read_ready(cx))?;
+            let unfilled = buf.initialize_unfilled();
+            match guard.try_io(|inner| inner.get_ref().read(unfilled)) {
+                Ok(Ok(read)) => {
+                    buf.advance(read);
+                    return Poll::Ready(Ok(()));
+                }
+                Ok(Err(e)) => return Poll::Ready(Err(e)),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+}
+
+impl AsyncWrite for SerialPort {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        loop {
+            let mut guard = ready!(self.fd.poll_write_ready(cx))?;
+            match guard.try_io(|inner| inner.get_ref().write(buf)) {
+                Ok(result) => return Poll::Ready(result),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Opens `path` as a framed SDK transport.
+pub fn open(path: &str, baud_rate: u32) -> Result<StreamTransport<SerialPort>, A8MiniError> {
+    Ok(StreamTransport::new(SerialPort::open(path, baud_rate)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_baud_rate() {
+        assert!(matches!(
+            SerialPort::open("/dev/null", 12345),
+            Err(A8MiniError::InvalidArgument(_))
+        ));
+        assert_eq!(speed(115200), Some(libc::B115200));
+    }
+}
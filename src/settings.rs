@@ -0,0 +1,199 @@
+pub use crate::calibration::CalibrationOffsets;
+use crate::control::MotionMode;
+use crate::error::A8MiniError;
+use crate::limits::SoftLimits;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// Schema version written by this release. Bump it and add a step to `migrate` whenever the
+/// layout of `Settings` changes in a way serde defaults do not cover.
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// A named gimbal pose, in decidegrees, with an optional zoom level.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    pub yaw: i16,
+    pub pitch: i16,
+    #[serde(default)]
+    pub zoom: Option<f32>,
+}
+
+/// A named set of client options to switch between, e.g. for different airframes or missions.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Profile {
+    #[serde(default)]
+    pub soft_limits: Option<SoftLimits>,
+    #[serde(default)]
+    pub motion_mode: Option<MotionMode>,
+}
+
+/// Client-side configuration kept across restarts.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub presets: BTreeMap<String, Preset>,
+    #[serde(default)]
+    pub soft_limits: Option<SoftLimits>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+    #[serde(default)]
+    pub calibration: CalibrationOffsets,
+}
+
+/// On-disk layout: the settings next to the schema version they were written with.
+#[derive(Serialize)]
+struct VersionedSettings<'a> {
+    version: u32,
+    #[serde(flatten)]
+    settings: &'a Settings,
+}
+
+/// Brings a settings document written with `version` up to `SETTINGS_VERSION`. Documents from a
+/// newer release are refused rather than silently losing what this release does not know.
+pub fn migrate(document: Value, version: u32) -> Result<Value, A8MiniError> {
+    if version > SETTINGS_VERSION {
+        return Err(A8MiniError::InvalidArgument(format!(
+            "Settings schema version {} is newer than the supported version {}.",
+            version, SETTINGS_VERSION
+        )));
+    }
+    // No schema changes yet; steps go here as `if version < N { ... }`.
+    Ok(document)
+}
+
+/// Settings persisted as a JSON file. Saving writes a temporary file and renames it over the
+/// old one, so a crash mid-write never leaves a truncated file behind.
+#[derive(Debug, Clone)]
+pub struct SettingsStore {
+    path: PathBuf,
+}
+
+impl SettingsStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the settings, migrating older schema versions. A missing file loads the defaults.
+    pub fn load(&self) -> Result<Settings, Box<dyn Error>> {
+        let contents = match std::fs::read(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Settings::default()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut document: Value = serde_json::from_slice(&contents)?;
+        let version = document
+            .as_object_mut()
+            .ok_or("Settings file is not a JSON object.")?
+            .remove("version")
+            .and_then(|version| version.as_u64())
+            .ok_or("Settings file has no schema version.")? as u32;
+        Ok(serde_json::from_value(migrate(document, version)?)?)
+    }
+
+    pub fn save(&self, settings: &Settings) -> Result<(), Box<dyn Error>> {
+        let document = serde_json::to_vec_pretty(&VersionedSettings {
+            version: SETTINGS_VERSION,
+            settings,
+        })?;
+        let temporary = self.path.with_extension("tmp");
+        std::fs::write(&temporary, document)?;
+        std::fs::rename(&temporary, &self.path)?;
+        Ok(())
+    }
+
+    /// Loads the settings, applies `change` and saves the result.
+    pub fn update(&self, change: impl FnOnce(&mut Settings)) -> Result<Settings, Box<dyn Error>> {
+        let mut settings = self.load()?;
+        change(&mut settings);
+        self.save(&settings)?;
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::limits::LimitMode;
+
+    #[test]
+    fn test_settings_round_trip() -> Result<(), Box<dyn Error>> {
+        let store = SettingsStore::new(std::env::temp_dir().join("a8mini_test_settings.json"));
+        let _ = std::fs::remove_file(store.path());
+        assert_eq!(store.load()?, Settings::default());
+
+        store.update(|settings| {
+            settings.presets.insert(
+                "runway".into(),
+                Preset {
+                    yaw: 450,
+                    pitch: -300,
+                    zoom: Some(2.5),
+                },
+            );
+            settings.soft_limits =
+                Some(SoftLimits::new((-900, 900), (-900, 0)).with_mode(LimitMode::Clamp));
+            settings.profiles.insert(
+                "survey".into(),
+                Profile {
+                    soft_limits: None,
+                    motion_mode: Some(MotionMode::Lock),
+                },
+            );
+            settings.calibration = CalibrationOffsets {
+                yaw: 12,
+                pitch: -4,
+                invert_yaw: true,
+                ..Default::default()
+            };
+        })?;
+
+        let loaded = store.load()?;
+        assert_eq!(loaded.presets["runway"].zoom, Some(2.5));
+        assert_eq!(loaded.soft_limits.unwrap().mode, LimitMode::Clamp);
+        assert_eq!(
+            loaded.profiles["survey"].motion_mode,
+            Some(MotionMode::Lock)
+        );
+        assert_eq!(
+            loaded.calibration,
+            CalibrationOffsets {
+                yaw: 12,
+                pitch: -4,
+                invert_yaw: true,
+                invert_pitch: false,
+            }
+        );
+
+        let document: Value = serde_json::from_slice(&std::fs::read(store.path())?)?;
+        assert_eq!(document["version"], SETTINGS_VERSION);
+        std::fs::remove_file(store.path())?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_newer_schema_refused() -> Result<(), Box<dyn Error>> {
+        let store =
+            SettingsStore::new(std::env::temp_dir().join("a8mini_test_settings_newer.json"));
+        std::fs::write(
+            store.path(),
+            format!(
+                r#"{{"version": {}, "presets": {{}}}}"#,
+                SETTINGS_VERSION + 1
+            ),
+        )?;
+        assert!(store.load().is_err());
+
+        // Fields missing from older files take their defaults.
+        std::fs::write(store.path(), r#"{"version": 1}"#)?;
+        assert_eq!(store.load()?, Settings::default());
+        std::fs::remove_file(store.path())?;
+        Ok(())
+    }
+}
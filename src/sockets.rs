@@ -0,0 +1,110 @@
+use crate::A8MiniError;
+use std::error::Error;
+use std::io;
+
+use socket2::SockRef;
+use tokio::net::UdpSocket;
+
+/// DSCP class for expedited forwarding, the usual marking for latency-sensitive control traffic.
+pub const DSCP_EXPEDITED_FORWARDING: u8 = 46;
+
+/// Socket options applied to a UDP socket. Unset options keep the OS default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SocketOptions {
+    pub recv_buffer_size: Option<usize>,
+    pub send_buffer_size: Option<usize>,
+    /// 6-bit DSCP value written to the upper bits of the IP TOS byte.
+    pub dscp: Option<u8>,
+}
+
+impl SocketOptions {
+    pub fn with_buffer_sizes(mut self, recv_buffer_size: usize, send_buffer_size: usize) -> Self {
+        self.recv_buffer_size = Some(recv_buffer_size);
+        self.send_buffer_size = Some(send_buffer_size);
+        self
+    }
+
+    pub fn with_dscp(mut self, dscp: u8) -> Self {
+        self.dscp = Some(dscp);
+        self
+    }
+
+    /// IP TOS byte carrying the DSCP value, with the ECN bits left clear.
+    pub fn tos(&self) -> Result<Option<u32>, Box<dyn Error>> {
+        match self.dscp {
+            Some(dscp) if dscp > 0x3F => Err(A8MiniError::InvalidArgument(format!(
+                "DSCP {} is out of range 0-63.",
+                dscp
+            ))
+            .into()),
+            Some(dscp) => Ok(Some((dscp as u32) << 2)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn apply(&self, socket: &UdpSocket) -> Result<(), Box<dyn Error>> {
+        let tos = self.tos()?;
+        let socket = SockRef::from(socket);
+
+        if let Some(size) = self.recv_buffer_size {
+            socket.set_recv_buffer_size(size)?;
+        }
+        if let Some(size) = self.send_buffer_size {
+            socket.set_send_buffer_size(size)?;
+        }
+        if let Some(tos) = tos {
+            set_tos(&socket, tos)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(any(
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "solaris",
+    target_os = "illumos"
+)))]
+fn set_tos(socket: &SockRef, tos: u32) -> io::Result<()> {
+    socket.set_tos(tos)
+}
+
+#[cfg(any(
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "solaris",
+    target_os = "illumos"
+))]
+fn set_tos(_socket: &SockRef, _tos: u32) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "IP TOS is not supported on this platform.",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dscp_to_tos() {
+        let options = SocketOptions::default().with_dscp(DSCP_EXPEDITED_FORWARDING);
+        assert_eq!(options.tos().unwrap(), Some(0xB8));
+        assert_eq!(SocketOptions::default().tos().unwrap(), None);
+        assert!(SocketOptions::default().with_dscp(64).tos().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply() -> Result<(), Box<dyn Error>> {
+        let socket = UdpSocket::bind("127.0.0.1:0").await?;
+        SocketOptions::default()
+            .with_buffer_sizes(1 << 16, 1 << 16)
+            .with_dscp(DSCP_EXPEDITED_FORWARDING)
+            .apply(&socket)?;
+
+        let socket = SockRef::from(&socket);
+        assert!(socket.recv_buffer_size()? >= 1 << 16);
+        assert_eq!(socket.tos()?, 0xB8);
+        Ok(())
+    }
+}
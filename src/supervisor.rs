@@ -0,0 +1,129 @@
+use crate::constants;
+use std::time::Duration;
+
+/// Health of the link to the camera as judged by consecutive request outcomes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConnectionState {
+    #[default]
+    Connected,
+    /// Some requests in a row have timed out; the link may be flapping.
+    Degraded,
+    /// Too many requests in a row have timed out. The supervisor is reconnecting.
+    Disconnected,
+}
+
+/// When to consider the link degraded or lost, and how to retry once it is lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionPolicy {
+    /// Consecutive failures after which the link is degraded.
+    pub degraded_after: u32,
+    /// Consecutive failures after which the link is lost.
+    pub disconnected_after: u32,
+    /// The link is probed when nothing has been received for this long.
+    pub probe_interval: Duration,
+    pub backoff_min: Duration,
+    pub backoff_max: Duration,
+}
+
+impl ConnectionPolicy {
+    /// Wait before reconnect attempt `attempt` (from 0), doubling up to `backoff_max`.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.backoff_min
+            .saturating_mul(1 << attempt.min(16))
+            .min(self.backoff_max)
+    }
+}
+
+impl Default for ConnectionPolicy {
+    fn default() -> Self {
+        Self {
+            degraded_after: constants::CONNECTION_DEGRADED_AFTER,
+            disconnected_after: constants::CONNECTION_LOST_AFTER,
+            probe_interval: constants::CONNECTION_PROBE_INTERVAL,
+            backoff_min: constants::RECONNECT_BACKOFF_MIN,
+            backoff_max: constants::RECONNECT_BACKOFF_MAX,
+        }
+    }
+}
+
+/// Counts consecutive failures against a connection policy. Any success reconnects.
+#[derive(Debug, Clone)]
+pub struct ConnectionMonitor {
+    policy: ConnectionPolicy,
+    failures: u32,
+    state: ConnectionState,
+}
+
+impl ConnectionMonitor {
+    pub fn new(policy: ConnectionPolicy) -> Self {
+        Self {
+            policy,
+            failures: 0,
+            state: ConnectionState::Connected,
+        }
+    }
+
+    pub fn policy(&self) -> ConnectionPolicy {
+        self.policy
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    /// Records the outcome of a request. Returns the new state if it changed.
+    pub fn record(&mut self, success: bool) -> Option<ConnectionState> {
+        self.failures = if success {
+            0
+        } else {
+            self.failures.saturating_add(1)
+        };
+        let state = if self.failures >= self.policy.disconnected_after {
+            ConnectionState::Disconnected
+        } else if self.failures >= self.policy.degraded_after {
+            ConnectionState::Degraded
+        } else {
+            ConnectionState::Connected
+        };
+        if state == self.state {
+            return None;
+        }
+        self.state = state;
+        Some(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_monitor() {
+        let policy = ConnectionPolicy {
+            degraded_after: 2,
+            disconnected_after: 3,
+            ..Default::default()
+        };
+        let mut monitor = ConnectionMonitor::new(policy);
+        assert_eq!(monitor.record(false), None);
+        assert_eq!(monitor.record(false), Some(ConnectionState::Degraded));
+        assert_eq!(monitor.record(false), Some(ConnectionState::Disconnected));
+        assert_eq!(monitor.record(false), None);
+        assert_eq!(monitor.record(true), Some(ConnectionState::Connected));
+        assert_eq!(monitor.record(false), None);
+        assert_eq!(monitor.state(), ConnectionState::Connected);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = ConnectionPolicy {
+            backoff_min: Duration::from_millis(100),
+            backoff_max: Duration::from_secs(1),
+            ..Default::default()
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_secs(1));
+        assert_eq!(policy.backoff(40), Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,387 @@
+use crate::breaker::LinkState;
+use crate::control::{A8MiniAtittude, GimbalInfo, MotionMode, RecordingStatus, StorageStatus};
+use crate::estimate::AttitudeEstimate;
+use crate::A8Mini;
+use futures_core::Stream;
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+
+/// Standard deviation of the angular rates over the jitter window, in degrees/s * 10.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JitterScore {
+    pub v_yaw: f32,
+    pub v_pitch: f32,
+    pub v_roll: f32,
+    /// Euclidean norm of the per-axis deviations.
+    pub combined: f32,
+}
+
+/// Rolling vibration/jitter estimate computed from attitude samples.
+#[derive(Debug, Clone)]
+pub struct JitterMeter {
+    window: usize,
+    samples: VecDeque<[i16; 3]>,
+}
+
+impl JitterMeter {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(2),
+            samples: VecDeque::with_capacity(window.max(2)),
+        }
+    }
+
+    pub fn push(&mut self, attitude: &A8MiniAtittude) {
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+        self.samples
+            .push_back([attitude.v_yaw, attitude.v_pitch, attitude.v_roll]);
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Returns `None` until at least two samples have been collected.
+    pub fn score(&self) -> Option<JitterScore> {
+        if self.samples.len() < 2 {
+            return None;
+        }
+
+        let n = self.samples.len() as f32;
+        let mut std_dev = [0f32; 3];
+        for (axis, axis_std_dev) in std_dev.iter_mut().enumerate() {
+            let mean = self.samples.iter().map(|s| s[axis] as f32).sum::<f32>() / n;
+            let variance = self
+                .samples
+                .iter()
+                .map(|s| (s[axis] as f32 - mean).powi(2))
+                .sum::<f32>()
+                / n;
+            *axis_std_dev = variance.sqrt();
+        }
+
+        Some(JitterScore {
+            v_yaw: std_dev[0],
+            v_pitch: std_dev[1],
+            v_roll: std_dev[2],
+            combined: std_dev.iter().map(|d| d * d).sum::<f32>().sqrt(),
+        })
+    }
+}
+
+impl Default for JitterMeter {
+    fn default() -> Self {
+        Self::new(crate::constants::JITTER_WINDOW)
+    }
+}
+
+/// Attitude frame pushed by the gimbal, stamped with its host arrival time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttitudeSample {
+    pub attitude: A8MiniAtittude,
+    pub received: Instant,
+}
+
+/// Passes at most one sample per `period`.
+#[derive(Debug, Clone)]
+pub struct Downsampler {
+    period: Duration,
+    last: Option<Instant>,
+}
+
+impl Downsampler {
+    pub fn new(period: Duration) -> Self {
+        Self { period, last: None }
+    }
+
+    pub fn admit(&mut self, now: Instant) -> bool {
+        if self
+            .last
+            .is_some_and(|last| now.saturating_duration_since(last) < self.period)
+        {
+            return false;
+        }
+        self.last = Some(now);
+        true
+    }
+}
+
+/// Attitude frames pushed by the gimbal, as a `Stream`. Returned by `A8Mini::attitude_stream`.
+/// Ends when the link fails.
+pub struct AttitudeStream<'a> {
+    camera: &'a A8Mini,
+    frames: mpsc::Receiver<(Vec<u8>, Instant)>,
+    done: bool,
+}
+
+impl<'a> AttitudeStream<'a> {
+    /// Yields the frames taken from `frames`, see `demux::Demux::tap`.
+    pub fn new(camera: &'a A8Mini, frames: mpsc::Receiver<(Vec<u8>, Instant)>) -> Self {
+        Self {
+            camera,
+            frames,
+            done: false,
+        }
+    }
+}
+
+impl Stream for AttitudeStream<'_> {
+    type Item = AttitudeSample;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        while !self.done {
+            let Some((frame, received)) = ready!(self.frames.poll_recv(cx)) else {
+                log_line!("[ATTITUDE] Attitude stream ended, link closed.");
+                self.done = true;
+                break;
+            };
+            self.camera.route_pushed();
+            if let Some(sample) = self.camera.accept_pushed_attitude(&frame, received) {
+                return Poll::Ready(Some(sample));
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+impl fmt::Debug for AttitudeStream<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AttitudeStream")
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A telemetry value carrying its host arrival time, which subscriptions downsample by.
+pub trait Sample: Clone {
+    fn received(&self) -> Instant;
+}
+
+impl Sample for AttitudeSample {
+    fn received(&self) -> Instant {
+        self.received
+    }
+}
+
+impl<T: Clone> Sample for Timestamped<T> {
+    fn received(&self) -> Instant {
+        self.at
+    }
+}
+
+/// One consumer's view of a telemetry feed, downsampled to its own rate. Every subscriber has
+/// its own bounded queue: one that falls behind loses its oldest samples and never holds back
+/// the client or other subscribers.
+#[derive(Debug)]
+pub struct Subscription<T> {
+    receiver: broadcast::Receiver<T>,
+    downsampler: Downsampler,
+}
+
+pub type AttitudeSubscription = Subscription<AttitudeSample>;
+
+impl<T: Sample> Subscription<T> {
+    pub fn new(receiver: broadcast::Receiver<T>, period: Duration) -> Self {
+        Self {
+            receiver,
+            downsampler: Downsampler::new(period),
+        }
+    }
+
+    /// Waits for the next sample due for this subscriber. Samples missed while the subscriber
+    /// lagged are skipped. Returns `None` once the client is dropped.
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(sample) if self.downsampler.admit(sample.received()) => return Some(sample),
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+/// A cached value and when it was received.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timestamped<T> {
+    pub value: T,
+    pub at: Instant,
+}
+
+impl<T> Timestamped<T> {
+    pub fn new(value: T, at: Instant) -> Self {
+        Self { value, at }
+    }
+
+    pub fn age(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.at)
+    }
+}
+
+/// Command traffic counters since the client was created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LinkStats {
+    pub sent: u64,
+    pub failed: u64,
+    pub responses: u64,
+    /// Acknowledged commands that got no valid response in time.
+    pub timeouts: u64,
+    /// Responses accepted within the grace window after the response timeout. Also counted in
+    /// `responses`.
+    pub late_responses: u64,
+    pub last_response: Option<Instant>,
+}
+
+/// Latest values reported by the camera, updated by the query methods and the attitude stream.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TelemetryCache {
+    pub attitude: Option<Timestamped<A8MiniAtittude>>,
+    pub zoom: Option<Timestamped<f32>>,
+    pub gimbal_info: Option<Timestamped<GimbalInfo>>,
+    pub link: LinkStats,
+}
+
+/// View of all cached telemetry at `taken`. `attitude`, `zoom`, `gimbal_info` and `link` are
+/// copied under one lock and agree with each other; `storage`, `link_state`, `jitter` and
+/// `estimate` are read from their own state right after, so an update racing the snapshot can
+/// show in one group but not yet the other.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TelemetrySnapshot {
+    pub taken: Instant,
+    pub attitude: Option<Timestamped<A8MiniAtittude>>,
+    pub zoom: Option<Timestamped<f32>>,
+    pub gimbal_info: Option<Timestamped<GimbalInfo>>,
+    pub storage: StorageStatus,
+    pub link_state: LinkState,
+    pub link: LinkStats,
+    pub jitter: Option<JitterScore>,
+    /// Attitude dead-reckoned to `taken`, see `A8Mini::attitude_estimate`.
+    pub estimate: Option<AttitudeEstimate>,
+}
+
+impl TelemetrySnapshot {
+    pub fn recording(&self) -> Option<RecordingStatus> {
+        self.gimbal_info.map(|info| info.value.recording)
+    }
+
+    pub fn motion_mode(&self) -> Option<MotionMode> {
+        self.gimbal_info.map(|info| info.value.motion_mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attitude(v_yaw: i16, v_pitch: i16, v_roll: i16) -> A8MiniAtittude {
+        A8MiniAtittude {
+            theta_yaw: 0,
+            theta_pitch: 0,
+            theta_roll: 0,
+            v_yaw,
+            v_pitch,
+            v_roll,
+        }
+    }
+
+    #[test]
+    fn test_downsampler() {
+        let now = Instant::now();
+        let mut downsampler = Downsampler::new(Duration::from_millis(200));
+        let admitted = (0..10)
+            .filter(|i| downsampler.admit(now + Duration::from_millis(20 * i)))
+            .count();
+        assert_eq!(admitted, 1);
+        assert!(downsampler.admit(now + Duration::from_millis(200)));
+    }
+
+    #[tokio::test]
+    async fn test_attitude_subscription_downsamples() {
+        let (sender, receiver) = broadcast::channel(64);
+        let mut subscription = AttitudeSubscription::new(receiver, Duration::from_millis(100));
+
+        let now = Instant::now();
+        for i in 0..10 {
+            sender
+                .send(AttitudeSample {
+                    attitude: attitude(i, 0, 0),
+                    received: now + Duration::from_millis(20 * i as u64),
+                })
+                .unwrap();
+        }
+        drop(sender);
+
+        let mut received = Vec::new();
+        while let Some(sample) = subscription.recv().await {
+            received.push(sample.attitude.v_yaw);
+        }
+        assert_eq!(received, vec![0, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber_drops_oldest() {
+        let (sender, receiver) = broadcast::channel(4);
+        let mut slow = Subscription::new(receiver, Duration::ZERO);
+        let mut fast = Subscription::new(sender.subscribe(), Duration::ZERO);
+
+        let now = Instant::now();
+        let mut fast_received = Vec::new();
+        for i in 0..10 {
+            sender
+                .send(Timestamped::new(i, now + Duration::from_millis(i)))
+                .unwrap();
+            fast_received.push(fast.recv().await.unwrap().value);
+        }
+        drop(sender);
+        assert_eq!(fast_received, (0..10).collect::<Vec<_>>());
+
+        let mut slow_received = Vec::new();
+        while let Some(sample) = slow.recv().await {
+            slow_received.push(sample.value);
+        }
+        assert_eq!(slow_received, vec![6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_jitter_score_steady_rates() {
+        let mut meter = JitterMeter::new(8);
+        assert_eq!(meter.score(), None);
+        for _ in 0..8 {
+            meter.push(&attitude(5, -3, 0));
+        }
+        let score = meter.score().unwrap();
+        assert_eq!(score.combined, 0.0);
+    }
+
+    #[test]
+    fn test_jitter_score_rolling_window() {
+        let mut meter = JitterMeter::new(4);
+        for v in [10, -10, 10, -10] {
+            meter.push(&attitude(v, 0, 0));
+        }
+        let score = meter.score().unwrap();
+        assert_eq!(score.v_yaw, 10.0);
+        assert_eq!(score.v_pitch, 0.0);
+        assert_eq!(score.combined, 10.0);
+
+        for _ in 0..4 {
+            meter.push(&attitude(3, 0, 0));
+        }
+        assert_eq!(meter.len(), 4);
+        assert_eq!(meter.score().unwrap().combined, 0.0);
+    }
+}
@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+/// Byte-rate cap for media downloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub bytes_per_second: u64,
+}
+
+impl RateLimit {
+    pub fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second: bytes_per_second.max(1),
+        }
+    }
+
+    /// How long to wait so that `transferred` bytes over `elapsed` do not exceed the cap.
+    pub fn delay(&self, transferred: u64, elapsed: Duration) -> Duration {
+        let allowed_at = Duration::from_secs_f64(transferred as f64 / self.bytes_per_second as f64);
+        allowed_at.saturating_sub(elapsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_delay() {
+        let limit = RateLimit::new(1000);
+        assert_eq!(
+            limit.delay(500, Duration::from_millis(100)),
+            Duration::from_millis(400)
+        );
+        assert_eq!(limit.delay(500, Duration::from_secs(1)), Duration::ZERO);
+        assert_eq!(RateLimit::new(0).bytes_per_second, 1);
+    }
+}
@@ -0,0 +1,147 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+/// One pose of a scan pattern: a yaw/pitch setpoint in degrees, in the client's angle convention,
+/// and how long to hold it before moving on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub dwell: Duration,
+}
+
+/// Circles a bearing: the line of sight traces a circle of `radius` degrees around
+/// (`bearing`, `pitch`), e.g. to look around a tower top or a nacelle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Orbit {
+    pub bearing: f32,
+    pub pitch: f32,
+    pub radius: f32,
+    pub steps_per_turn: usize,
+    pub turns: usize,
+    pub dwell: Duration,
+}
+
+impl Orbit {
+    pub fn keyframes(&self) -> Vec<Keyframe> {
+        let steps = self.steps_per_turn.max(1);
+        (0..steps * self.turns.max(1))
+            .map(|step| {
+                let angle = TAU * (step % steps) as f32 / steps as f32;
+                Keyframe {
+                    yaw: self.bearing + self.radius * angle.cos(),
+                    pitch: self.pitch + self.radius * angle.sin(),
+                    dwell: self.dwell,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Sweeps yaw back and forth across `bearing ± half_width` while pitch moves linearly from
+/// `pitch_from` to `pitch_to`, covering the band between them, e.g. down a tower or turbine mast.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spiral {
+    pub bearing: f32,
+    pub half_width: f32,
+    pub pitch_from: f32,
+    pub pitch_to: f32,
+    pub steps_per_sweep: usize,
+    pub sweeps: usize,
+    pub dwell: Duration,
+}
+
+impl Spiral {
+    pub fn keyframes(&self) -> Vec<Keyframe> {
+        let steps = self.steps_per_sweep.max(1);
+        let total = steps * self.sweeps.max(1);
+        (0..=total)
+            .map(|step| {
+                // Position across the current sweep, reversing direction on odd sweeps.
+                let sweep = step / steps;
+                let along = (step % steps) as f32 / steps as f32;
+                let along = if sweep.is_multiple_of(2) {
+                    along
+                } else {
+                    1.0 - along
+                };
+                Keyframe {
+                    yaw: self.bearing - self.half_width + 2.0 * self.half_width * along,
+                    pitch: self.pitch_from
+                        + (self.pitch_to - self.pitch_from) * step as f32 / total as f32,
+                    dwell: self.dwell,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Total time spent dwelling on `keyframes`, excluding gimbal travel.
+pub fn dwell_time(keyframes: &[Keyframe]) -> Duration {
+    keyframes.iter().map(|keyframe| keyframe.dwell).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-3
+    }
+
+    #[test]
+    fn test_orbit() {
+        let orbit = Orbit {
+            bearing: 30.0,
+            pitch: -10.0,
+            radius: 5.0,
+            steps_per_turn: 4,
+            turns: 2,
+            dwell: Duration::from_millis(500),
+        };
+        let keyframes = orbit.keyframes();
+        assert_eq!(keyframes.len(), 8);
+        assert!(close(keyframes[0].yaw, 35.0) && close(keyframes[0].pitch, -10.0));
+        assert!(close(keyframes[1].yaw, 30.0) && close(keyframes[1].pitch, -5.0));
+        assert!(close(keyframes[2].yaw, 25.0) && close(keyframes[2].pitch, -10.0));
+        assert_eq!(keyframes[4], keyframes[0]);
+        assert_eq!(dwell_time(&keyframes), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_spiral() {
+        let spiral = Spiral {
+            bearing: 0.0,
+            half_width: 20.0,
+            pitch_from: 0.0,
+            pitch_to: -40.0,
+            steps_per_sweep: 2,
+            sweeps: 2,
+            dwell: Duration::ZERO,
+        };
+        let poses: Vec<(f32, f32)> = spiral
+            .keyframes()
+            .iter()
+            .map(|keyframe| (keyframe.yaw, keyframe.pitch))
+            .collect();
+        assert_eq!(
+            poses,
+            vec![
+                (-20.0, 0.0),
+                (0.0, -10.0),
+                (20.0, -20.0),
+                (0.0, -30.0),
+                (-20.0, -40.0),
+            ]
+        );
+
+        // A single sweep ends on the far side.
+        let single = Spiral {
+            sweeps: 1,
+            ..spiral
+        }
+        .keyframes();
+        assert_eq!(single.last().unwrap().yaw, 20.0);
+        assert_eq!(single.last().unwrap().pitch, -40.0);
+    }
+}
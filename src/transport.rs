@@ -0,0 +1,175 @@
+use crate::{checksum, constants};
+use std::fmt::{self, Debug};
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf};
+use tokio::net::UdpSocket;
+use tokio::sync::Mutex;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Carries whole SDK frames between the client and the camera. The command layer (encoding,
+/// sequence numbers, ACK matching) is the same whatever the backend.
+pub trait Transport: Debug + Send + Sync {
+    /// Sends one frame, returning the number of bytes written.
+    fn send<'a>(&'a self, frame: &'a [u8]) -> BoxFuture<'a, io::Result<usize>>;
+
+    /// Receives one frame into `buf`, returning its length. 0 means the transport is closed.
+    fn recv<'a>(&'a self, buf: &'a mut [u8]) -> BoxFuture<'a, io::Result<usize>>;
+
+    /// The underlying UDP socket, for operations only UDP supports (socket options, re-addressing).
+    fn as_udp(&self) -> Option<&UdpSocket> {
+        None
+    }
+}
+
+/// A connected UDP socket, the SDK's default transport.
+impl Transport for UdpSocket {
+    fn send<'a>(&'a self, frame: &'a [u8]) -> BoxFuture<'a, io::Result<usize>> {
+        Box::pin(UdpSocket::send(self, frame))
+    }
+
+    fn recv<'a>(&'a self, buf: &'a mut [u8]) -> BoxFuture<'a, io::Result<usize>> {
+        Box::pin(UdpSocket::recv(self, buf))
+    }
+
+    fn as_udp(&self) -> Option<&UdpSocket> {
+        Some(self)
+    }
+}
+
+/// Removes the next complete, valid frame from the front of `buffer`. Bytes before an STX and
+/// candidate frames that fail validation are skipped, so the stream resynchronizes after noise.
+pub fn take_frame(buffer: &mut Vec<u8>) -> Option<Vec<u8>> {
+    loop {
+        let Some(start) = buffer.windows(2).position(|stx| stx == [0x55, 0x66]) else {
+            // Keep a trailing first STX byte, the second may still arrive.
+            let keep = usize::from(buffer.last() == Some(&0x55));
+            buffer.drain(..buffer.len() - keep);
+            return None;
+        };
+        buffer.drain(..start);
+        if buffer.len() < constants::HEADER_LEN {
+            return None;
+        }
+
+        let data_len = u16::from_le_bytes([buffer[3], buffer[4]]) as usize;
+        let frame_len = constants::HEADER_LEN + data_len + 2;
+        if buffer.len() < frame_len {
+            return None;
+        }
+        if checksum::validate_frame(&buffer[..frame_len]).is_ok() {
+            return Some(buffer.drain(..frame_len).collect());
+        }
+        buffer.drain(..1);
+    }
+}
+
+/// Carries frames over a byte stream such as a TCP connection or a serial port, splitting the
+/// stream back into frames on receive.
+pub struct StreamTransport<S> {
+    reader: Mutex<(ReadHalf<S>, Vec<u8>)>,
+    writer: Mutex<WriteHalf<S>>,
+}
+
+impl<S: AsyncRead + AsyncWrite> StreamTransport<S> {
+    pub fn new(stream: S) -> Self {
+        let (reader, writer) = tokio::io::split(stream);
+        Self {
+            reader: Mutex::new((reader, Vec::new())),
+            writer: Mutex::new(writer),
+        }
+    }
+}
+
+impl<S> Debug for StreamTransport<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamTransport").finish_non_exhaustive()
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Send> Transport for StreamTransport<S> {
+    fn send<'a>(&'a self, frame: &'a [u8]) -> BoxFuture<'a, io::Result<usize>> {
+        Box::pin(async move {
+            let mut writer = self.writer.lock().await;
+            writer.write_all(frame).await?;
+            writer.flush().await?;
+            Ok(frame.len())
+        })
+    }
+
+    fn recv<'a>(&'a self, buf: &'a mut [u8]) -> BoxFuture<'a, io::Result<usize>> {
+        Box::pin(async move {
+            let mut reader = self.reader.lock().await;
+            let (stream, pending) = &mut *reader;
+            let mut chunk = [0; constants::RECV_BUFF_SIZE];
+            loop {
+                if let Some(frame) = take_frame(pending) {
+                    let len = frame.len().min(buf.len());
+                    buf[..len].copy_from_slice(&frame[..len]);
+                    return Ok(len);
+                }
+                // Partial frames stay in `pending`, so a cancelled receive loses nothing.
+                let read = stream.read(&mut chunk).await?;
+                if read == 0 {
+                    return Ok(0);
+                }
+                pending.extend_from_slice(&chunk[..read]);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_frame() {
+        let frame = constants::HARDCODED_COMMANDS[0].to_vec();
+
+        let mut buffer = vec![0x00, 0x55];
+        buffer.extend_from_slice(&frame);
+        buffer.extend_from_slice(&frame[..5]);
+        assert_eq!(take_frame(&mut buffer), Some(frame.clone()));
+        assert_eq!(take_frame(&mut buffer), None);
+        assert_eq!(buffer, frame[..5]);
+
+        buffer.extend_from_slice(&frame[5..]);
+        assert_eq!(take_frame(&mut buffer), Some(frame.clone()));
+        assert!(buffer.is_empty());
+
+        // A corrupted frame is skipped.
+        let mut corrupted = frame.clone();
+        corrupted[8] ^= 0xff;
+        corrupted.extend_from_slice(&frame);
+        assert_eq!(take_frame(&mut corrupted), Some(frame.clone()));
+
+        let mut noise = vec![0x01, 0x02, 0x55];
+        assert_eq!(take_frame(&mut noise), None);
+        assert_eq!(noise, [0x55]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_transport() {
+        let frame = constants::HARDCODED_COMMANDS[0];
+        let (client, mut camera) = tokio::io::duplex(256);
+        let transport = StreamTransport::new(client);
+
+        assert_eq!(transport.send(frame).await.unwrap(), frame.len());
+        let mut echoed = vec![0; frame.len()];
+        camera.read_exact(&mut echoed).await.unwrap();
+        assert_eq!(echoed, frame);
+
+        // A frame split across writes is reassembled.
+        camera.write_all(&frame[..4]).await.unwrap();
+        camera.write_all(&frame[4..]).await.unwrap();
+        let mut buf = [0; constants::RECV_BUFF_SIZE];
+        let len = transport.recv(&mut buf).await.unwrap();
+        assert_eq!(&buf[..len], frame);
+
+        drop(camera);
+        assert_eq!(transport.recv(&mut buf).await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::capture::CaptureKind;
+use crate::constants;
+use crate::control::StreamType;
+use std::net::SocketAddr;
+
+/// Builds every HTTP and RTSP URL the camera serves from one address, so media queries follow
+/// the camera when its IP changes. The camera's web server has no authentication or session
+/// state, so the URLs are all that is needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaUrls {
+    host: String,
+    http_port: u16,
+}
+
+impl MediaUrls {
+    pub fn new(host: impl Into<String>, http_port: u16) -> Self {
+        Self {
+            host: host.into(),
+            http_port,
+        }
+    }
+
+    pub fn from_addr(addr: SocketAddr) -> Self {
+        Self::new(addr.ip().to_string(), addr.port())
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// A media API endpoint, e.g. `api("getmediacount", &[("media_type", "0")])`.
+    pub fn api(&self, endpoint: &str, params: &[(&str, &str)]) -> String {
+        let query = params
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join("&");
+        let mut url = format!(
+            "http://{}:{}/{}/api/v1/{}",
+            self.host,
+            self.http_port,
+            constants::API_BASE_URL.trim_start_matches('/'),
+            endpoint
+        );
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query);
+        }
+        url
+    }
+
+    pub fn directories(&self, kind: CaptureKind) -> String {
+        self.api("getdirectories", &[("media_type", media_type(kind))])
+    }
+
+    pub fn media_count(&self, kind: CaptureKind) -> String {
+        self.api(
+            "getmediacount",
+            &[("media_type", media_type(kind)), ("path", directory(kind))],
+        )
+    }
+
+    /// The file of the `index`th photo or video.
+    pub fn media_file(&self, kind: CaptureKind, index: u8) -> String {
+        let folder = match kind {
+            CaptureKind::Photo => "photo",
+            CaptureKind::Video => "video",
+        };
+        format!(
+            "http://{}:{}/{}/{}/{}",
+            self.host,
+            self.http_port,
+            folder,
+            directory(kind),
+            media_file_name(kind, index)
+        )
+    }
+
+    /// The RTSP URL of a live video stream. The recording stream is not served over RTSP.
+    pub fn rtsp(&self, stream: StreamType) -> Option<String> {
+        let path = match stream {
+            StreamType::Main => "main.264",
+            StreamType::Sub => "sub.264",
+            StreamType::Recording => return None,
+        };
+        Some(format!(
+            "rtsp://{}:{}/{}",
+            self.host,
+            constants::CAMERA_RTSP_PORT,
+            path
+        ))
+    }
+}
+
+impl Default for MediaUrls {
+    fn default() -> Self {
+        Self::new(
+            constants::CAMERA_IP,
+            constants::CAMERA_HTTP_PORT.parse().unwrap(),
+        )
+    }
+}
+
+/// Name the camera gives the `index`th photo or video, e.g. `IMG_0007.jpg`.
+pub fn media_file_name(kind: CaptureKind, index: u8) -> String {
+    match kind {
+        CaptureKind::Photo => format!("IMG_{:0>4}.jpg", index),
+        CaptureKind::Video => format!("VID_{:0>4}.mpeg", index),
+    }
+}
+
+fn media_type(kind: CaptureKind) -> &'static str {
+    match kind {
+        CaptureKind::Photo => "0",
+        CaptureKind::Video => "1",
+    }
+}
+
+fn directory(kind: CaptureKind) -> &'static str {
+    match kind {
+        CaptureKind::Photo => "101SIYI_IMG",
+        CaptureKind::Video => "101SIYI_VID",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_urls() {
+        let urls = MediaUrls::default();
+        assert_eq!(
+            urls.directories(CaptureKind::Photo),
+            "http://192.168.144.25:82/cgi-bin/media.cgi/api/v1/getdirectories?media_type=0"
+        );
+        assert_eq!(
+            urls.media_count(CaptureKind::Video),
+            "http://192.168.144.25:82/cgi-bin/media.cgi/api/v1/getmediacount?media_type=1&path=101SIYI_VID"
+        );
+        assert_eq!(
+            urls.media_file(CaptureKind::Photo, 7),
+            "http://192.168.144.25:82/photo/101SIYI_IMG/IMG_0007.jpg"
+        );
+        assert_eq!(
+            urls.rtsp(StreamType::Main).unwrap(),
+            "rtsp://192.168.144.25:8554/main.264"
+        );
+        assert_eq!(urls.rtsp(StreamType::Recording), None);
+    }
+
+    #[test]
+    fn test_urls_follow_address() {
+        let urls = MediaUrls::from_addr("10.0.0.9:8082".parse().unwrap());
+        assert_eq!(urls.host(), "10.0.0.9");
+        assert_eq!(
+            urls.media_file(CaptureKind::Video, 12),
+            "http://10.0.0.9:8082/video/101SIYI_VID/VID_0012.mpeg"
+        );
+        assert_eq!(
+            urls.api("getmediacount", &[]),
+            "http://10.0.0.9:8082/cgi-bin/media.cgi/api/v1/getmediacount"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{constants, A8MiniError};
+use std::fmt;
+use std::time::Duration;
+
+/// Splits a zoom level into the integer and tenths bytes used on the wire.
+pub fn to_wire(zoom: f32) -> (u8, u8) {
+    let tenths = (zoom.max(0.0) * 10.0).round() as u32;
+    ((tenths / 10).min(u8::MAX as u32) as u8, (tenths % 10) as u8)
+}
+
+pub fn from_wire(zoom_int: u8, zoom_float: u8) -> f32 {
+    zoom_int as f32 + zoom_float as f32 / 10.0
+}
+
+/// A zoom factor reported by the camera, e.g. 4.5 for 4.5x.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct ZoomLevel(pub f32);
+
+impl ZoomLevel {
+    /// Combines the integer and tenths bytes of a zoom ACK.
+    pub fn from_wire(zoom_int: u8, zoom_float: u8) -> Self {
+        Self(from_wire(zoom_int, zoom_float))
+    }
+
+    pub fn value(&self) -> f32 {
+        self.0
+    }
+}
+
+impl From<ZoomLevel> for f32 {
+    fn from(level: ZoomLevel) -> Self {
+        level.0
+    }
+}
+
+impl fmt::Display for ZoomLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.1}x", self.0)
+    }
+}
+
+/// Decodes the zoom level reported by manual zoom ACKs, a little-endian count of tenths.
+pub fn from_tenths(bytes: [u8; 2]) -> f32 {
+    u16::from_le_bytes(bytes) as f32 / 10.0
+}
+
+/// Checks that `zoom` is within the A8 mini's digital zoom range.
+pub fn validate(zoom: f32) -> Result<f32, A8MiniError> {
+    if (constants::MIN_ZOOM..=constants::MAX_ZOOM).contains(&zoom) {
+        Ok(zoom)
+    } else {
+        Err(A8MiniError::InvalidArgument(format!(
+            "Zoom {} is outside {}x to {}x.",
+            zoom,
+            constants::MIN_ZOOM,
+            constants::MAX_ZOOM
+        )))
+    }
+}
+
+/// Intermediate zoom levels for moving from `from` to `to` at no more than `max_rate`
+/// (zoom levels per second), one per `step_interval`. The last level is always `to`.
+pub fn ramp(from: f32, to: f32, max_rate: f32, step_interval: Duration) -> Vec<f32> {
+    let max_step = (max_rate.abs() * step_interval.as_secs_f32()).max(0.1);
+    let steps = ((to - from).abs() / max_step).ceil().max(1.0) as usize;
+    (1..=steps)
+        .map(|step| from + (to - from) * step as f32 / steps as f32)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoom_wire_format() {
+        assert_eq!(to_wire(4.5), (4, 5));
+        assert_eq!(to_wire(1.0), (1, 0));
+        assert_eq!(to_wire(2.96), (3, 0));
+        assert_eq!(from_wire(4, 5), 4.5);
+        assert_eq!(from_tenths([0x2d, 0x00]), 4.5);
+
+        let level = ZoomLevel::from_wire(2, 3);
+        assert_eq!(f32::from(level), 2.3);
+        assert_eq!(level.to_string(), "2.3x");
+        assert_eq!(ZoomLevel(1.0).to_string(), "1.0x");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(validate(1.0).unwrap(), 1.0);
+        assert_eq!(validate(6.0).unwrap(), 6.0);
+        for zoom in [0.9, 6.1, -1.0, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                validate(zoom),
+                Err(A8MiniError::InvalidArgument(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_ramp() {
+        let levels = ramp(1.0, 3.0, 5.0, Duration::from_millis(100));
+        assert_eq!(levels.len(), 4);
+        assert_eq!(*levels.last().unwrap(), 3.0);
+        assert!(levels
+            .windows(2)
+            .all(|pair| pair[1] - pair[0] <= 0.5 + f32::EPSILON));
+
+        assert_eq!(ramp(4.0, 2.0, 100.0, Duration::from_millis(100)), vec![2.0]);
+        assert_eq!(ramp(2.0, 2.0, 1.0, Duration::from_millis(100)), vec![2.0]);
+    }
+}